@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use strum_macros::EnumIter;
 
 #[allow(clippy::upper_case_acronyms)]
-#[derive(Clone, Debug, Hash, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Hash, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[cfg_attr(test, derive(EnumIter))]
 pub enum BlackBoxFunc {
     /// Bitwise AND.
@@ -48,6 +48,16 @@ pub enum BlackBoxFunc {
     /// Compute a recursive aggregation object when verifying a proof inside another circuit.
     /// This outputted aggregation object will then be either checked in a top-level verifier or aggregated upon again.
     RecursiveAggregation,
+    /// Calculates the Poseidon hash of the inputs over the native field, using the same parameters as circomlib.
+    Poseidon,
+    /// Calculates the SHA512 hash of the inputs.
+    Sha512,
+    /// Calculates the SHA384 hash of the inputs.
+    Sha384,
+    /// Verifies a pure Ed25519 signature as specified in [RFC 8032][rfc8032], i.e. the message is not prehashed.
+    ///
+    /// [rfc8032]: https://datatracker.ietf.org/doc/html/rfc8032
+    EddsaVerify,
 }
 
 impl std::fmt::Display for BlackBoxFunc {
@@ -72,6 +82,10 @@ impl BlackBoxFunc {
             BlackBoxFunc::Keccak256 => "keccak256",
             BlackBoxFunc::RecursiveAggregation => "recursive_aggregation",
             BlackBoxFunc::EcdsaSecp256r1 => "ecdsa_secp256r1",
+            BlackBoxFunc::Poseidon => "poseidon",
+            BlackBoxFunc::Sha512 => "sha512",
+            BlackBoxFunc::Sha384 => "sha384",
+            BlackBoxFunc::EddsaVerify => "eddsa_verify",
         }
     }
     pub fn lookup(op_name: &str) -> Option<BlackBoxFunc> {
@@ -89,6 +103,10 @@ impl BlackBoxFunc {
             "range" => Some(BlackBoxFunc::RANGE),
             "keccak256" => Some(BlackBoxFunc::Keccak256),
             "recursive_aggregation" => Some(BlackBoxFunc::RecursiveAggregation),
+            "poseidon" => Some(BlackBoxFunc::Poseidon),
+            "sha512" => Some(BlackBoxFunc::Sha512),
+            "sha384" => Some(BlackBoxFunc::Sha384),
+            "eddsa_verify" => Some(BlackBoxFunc::EddsaVerify),
             _ => None,
         }
     }
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 /// Inputs for the Brillig VM. These are the initial inputs
 /// that the Brillig VM will use to start.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BrilligInputs {
     Single(Expression),
     Array(Vec<Expression>),
@@ -14,12 +15,14 @@ pub enum BrilligInputs {
 /// Outputs for the Brillig VM. Once the VM has completed
 /// execution, this will be the object that is returned.
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BrilligOutputs {
     Simple(Witness),
     Array(Vec<Witness>),
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Brillig {
     pub inputs: Vec<BrilligInputs>,
     pub outputs: Vec<BrilligOutputs>,
@@ -28,6 +31,19 @@ pub struct Brillig {
     pub foreign_call_results: Vec<ForeignCallResult>,
     /// The Brillig VM bytecode to be executed by this ACIR opcode.
     pub bytecode: Vec<BrilligOpcode>,
+    /// Messages reported by the [`BrilligOpcode::TrapWithMessage`] opcodes of `bytecode`, which refer to them by
+    /// their index in this table.
+    pub trap_messages: Vec<String>,
     /// Predicate of the Brillig execution - indicates if it should be skipped
+    ///
+    /// If the predicate evaluates to zero then the bytecode is not executed and every output is assigned zero.
     pub predicate: Option<Expression>,
 }
+
+impl Brillig {
+    /// Returns the message at index `message` of [`Brillig::trap_messages`], as reported by a
+    /// [`BrilligOpcode::TrapWithMessage`] opcode.
+    pub fn trap_message(&self, message: usize) -> Option<&str> {
+        self.trap_messages.get(message).map(String::as_str)
+    }
+}
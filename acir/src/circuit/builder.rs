@@ -0,0 +1,259 @@
+use acir_field::FieldElement;
+
+use super::{
+    directives::{Directive, QuotientDirective},
+    opcodes::{BlackBoxFuncCall, FunctionInput},
+    Circuit, Opcode,
+};
+use crate::native_types::{Expression, Witness};
+
+/// A fluent builder for [`Circuit`]s.
+///
+/// ```
+/// # use acir::{circuit::CircuitBuilder, native_types::{Expression, Witness}};
+/// // w1 + w2 - w3 == 0
+/// let sum = &(&Expression::from(Witness(1)) + &Expression::from(Witness(2))) - &Expression::from(Witness(3));
+/// let circuit = CircuitBuilder::new()
+///     .arithmetic(sum)
+///     .private(Witness(1))
+///     .private(Witness(2))
+///     .public_return(Witness(3))
+///     .build();
+/// assert_eq!(circuit.current_witness_index, 3);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct CircuitBuilder {
+    circuit: Circuit,
+}
+
+impl CircuitBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `opcode` to the circuit.
+    pub fn opcode(mut self, opcode: Opcode) -> Self {
+        self.circuit.opcodes.push(opcode);
+        self
+    }
+
+    /// Appends an [`Opcode::Arithmetic`] constraining `expr` to equal zero.
+    pub fn arithmetic(self, expr: Expression) -> Self {
+        self.opcode(Opcode::Arithmetic(expr))
+    }
+
+    /// Appends an [`Opcode::BlackBoxFuncCall`].
+    pub fn blackbox(self, call: BlackBoxFuncCall) -> Self {
+        self.opcode(Opcode::BlackBoxFuncCall(call))
+    }
+
+    /// Appends a [`BlackBoxFuncCall::FixedBaseScalarMul`] of the full field element `scalar`, writing the resulting
+    /// point to `outputs`.
+    ///
+    /// The scalar is split into its low 128 bits and the remaining high bits using two fresh witnesses, which are
+    /// range checked and constrained to recombine to `scalar` before being passed to the black box function.
+    ///
+    /// As `low + 2^128 * high` may exceed the field modulus `p`, the halves are also constrained against the limbs of
+    /// `p` so that the decomposition is unique. A borrow bit `borrow = (low >= p_low)` is computed for the low limbs,
+    /// and the differences `low + 2^128 - p_low - 2^128 * borrow` and `p_high - high - borrow` are range checked to
+    /// 128 bits. Together these require `high < p_high`, or `high == p_high` and `low < p_low`.
+    pub fn fixed_base_scalar_mul(self, scalar: FunctionInput, outputs: (Witness, Witness)) -> Self {
+        let next_index = [scalar.witness, outputs.0, outputs.1]
+            .into_iter()
+            .map(|witness| witness.witness_index())
+            .fold(self.circuit.max_witness_index(), u32::max)
+            + 1;
+        let low = Witness(next_index);
+        let high = Witness(next_index + 1);
+        let borrow = Witness(next_index + 2);
+        let low_difference = Witness(next_index + 3);
+        let high_difference = Witness(next_index + 4);
+        let two_pow_128 = FieldElement::from(2_u128).pow(128);
+        let (modulus_low, modulus_high) = modulus_limbs();
+
+        // low + 2^128 * high - scalar == 0
+        let recombination = Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), low),
+                (two_pow_128, high),
+                (-FieldElement::one(), scalar.witness),
+            ],
+            q_c: FieldElement::zero(),
+        };
+
+        self.opcode(Opcode::Directive(Directive::Quotient(QuotientDirective {
+            a: scalar.witness.into(),
+            b: two_pow_128.into(),
+            q: high,
+            r: low,
+            predicate: None,
+        })))
+        .blackbox(BlackBoxFuncCall::RANGE { input: FunctionInput { witness: low, num_bits: 128 } })
+        .blackbox(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: high, num_bits: FieldElement::max_num_bits() - 128 },
+        })
+        .arithmetic(recombination)
+        // borrow = (low >= p_low), low_difference = low + 2^128 - p_low - 2^128 * borrow
+        .opcode(Opcode::Directive(Directive::Quotient(QuotientDirective {
+            a: Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: vec![(FieldElement::one(), low)],
+                q_c: two_pow_128 - modulus_low,
+            },
+            b: two_pow_128.into(),
+            q: borrow,
+            r: low_difference,
+            predicate: None,
+        })))
+        .blackbox(BlackBoxFuncCall::RANGE { input: FunctionInput { witness: borrow, num_bits: 1 } })
+        .blackbox(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: low_difference, num_bits: 128 },
+        })
+        .arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), low),
+                (-two_pow_128, borrow),
+                (-FieldElement::one(), low_difference),
+            ],
+            q_c: two_pow_128 - modulus_low,
+        })
+        // high_difference = p_high - high - borrow
+        .arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (-FieldElement::one(), high),
+                (-FieldElement::one(), borrow),
+                (-FieldElement::one(), high_difference),
+            ],
+            q_c: modulus_high,
+        })
+        .blackbox(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: high_difference, num_bits: 128 },
+        })
+        .blackbox(BlackBoxFuncCall::FixedBaseScalarMul {
+            low: FunctionInput { witness: low, num_bits: 128 },
+            high: FunctionInput { witness: high, num_bits: 128 },
+            outputs,
+        })
+    }
+
+    /// Marks `witness` as a private parameter of the circuit.
+    pub fn private(mut self, witness: Witness) -> Self {
+        self.circuit.private_parameters.insert(witness);
+        self
+    }
+
+    /// Marks `witness` as a public parameter of the circuit.
+    pub fn public(mut self, witness: Witness) -> Self {
+        self.circuit.public_parameters.0.insert(witness);
+        self
+    }
+
+    /// Marks `witness` as a public return value of the circuit.
+    pub fn public_return(mut self, witness: Witness) -> Self {
+        self.circuit.return_values.0.insert(witness);
+        self
+    }
+
+    /// Returns the built circuit.
+    ///
+    /// `current_witness_index` is set to the largest witness index referenced by the circuit, as by
+    /// [`Circuit::recompute_witness_index`].
+    pub fn build(mut self) -> Circuit {
+        self.circuit.recompute_witness_index();
+        self.circuit
+    }
+}
+
+/// Returns the low 128 bits and the remaining high bits of the field modulus.
+fn modulus_limbs() -> (FieldElement, FieldElement) {
+    // The modulus is odd, so subtracting one from it does not borrow from the high limb.
+    let modulus_minus_one = (-FieldElement::one()).to_be_bytes();
+    let (high, low) = modulus_minus_one.split_at(modulus_minus_one.len() - 16);
+    (
+        FieldElement::from_be_bytes_reduce(low) + FieldElement::one(),
+        FieldElement::from_be_bytes_reduce(high),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir_field::FieldElement;
+
+    use super::{modulus_limbs, CircuitBuilder};
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            Circuit, ConstraintViolation, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness, WitnessMap},
+    };
+
+    #[test]
+    fn computes_current_witness_index() {
+        let range =
+            BlackBoxFuncCall::RANGE { input: FunctionInput { witness: Witness(7), num_bits: 8 } };
+        let circuit = CircuitBuilder::new()
+            .blackbox(range.clone())
+            .arithmetic(Expression::from(Witness(2)))
+            .public(Witness(1))
+            .build();
+
+        let expected = Circuit {
+            current_witness_index: 7,
+            opcodes: vec![
+                Opcode::BlackBoxFuncCall(range),
+                Opcode::Arithmetic(Expression::from(Witness(2))),
+            ],
+            public_parameters: PublicInputs([Witness(1)].into()),
+            ..Circuit::default()
+        };
+        assert_eq!(circuit, expected);
+
+        let circuit = CircuitBuilder::new().public_return(Witness(10)).build();
+        assert_eq!(circuit.current_witness_index, 10);
+    }
+
+    #[test]
+    fn fixed_base_scalar_mul_decomposition_is_unique() {
+        let circuit = CircuitBuilder::new()
+            .fixed_base_scalar_mul(
+                FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() },
+                (Witness(2), Witness(3)),
+            )
+            .private(Witness(1))
+            .build();
+
+        let (modulus_low, modulus_high) = modulus_limbs();
+        let two_pow_128 = FieldElement::from(2_u128).pow(128);
+        let scalar = FieldElement::from(5_u128);
+        // Assigns the decomposition `low + 2^128 * high` of the scalar along with the borrow witnesses.
+        let assignment = |low: FieldElement, high: FieldElement, borrow: FieldElement| {
+            WitnessMap::from(BTreeMap::from([
+                (Witness(1), scalar),
+                (Witness(4), low),
+                (Witness(5), high),
+                (Witness(6), borrow),
+                (Witness(7), low + two_pow_128 - modulus_low - borrow * two_pow_128),
+                (Witness(8), modulus_high - high - borrow),
+            ]))
+        };
+
+        let canonical = assignment(scalar, FieldElement::zero(), FieldElement::zero());
+        // Only the scalar multiplication itself cannot be checked.
+        let unchecked = ConstraintViolation::Unchecked { opcode_index: circuit.opcodes.len() - 1 };
+        assert_eq!(circuit.check_witness(&canonical), vec![unchecked.clone()]);
+
+        // `scalar + p` also recombines to `scalar`, but its high limb is not less than that of `p`.
+        let overflowing = assignment(scalar + modulus_low, modulus_high, FieldElement::one());
+        assert!(matches!(
+            circuit.check_witness(&overflowing).as_slice(),
+            [ConstraintViolation::RangeExceeded { witness: Witness(8), .. }, violation]
+                if *violation == unchecked
+        ));
+    }
+}
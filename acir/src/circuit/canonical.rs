@@ -0,0 +1,201 @@
+use sha2::{Digest, Sha256};
+
+use super::{
+    brillig::BrilligInputs,
+    directives::{Directive, QuotientDirective},
+    Circuit, Opcode,
+};
+
+impl Circuit {
+    /// Rewrites the circuit into a canonical form so that equivalent circuits compare (and serialize) equally.
+    ///
+    /// Each expression has its terms merged, sorted and stripped of zero coefficients. Opcodes are then ordered
+    /// such that every opcode follows the opcodes which produce its inputs (as in [`Circuit::topological_sort`]),
+    /// breaking ties by the opcodes' serialized form rather than by their original position.
+    /// If some opcodes cannot be ordered in this way then they are left at the end of the circuit in their
+    /// original relative order.
+    ///
+    /// The set of constraints is unchanged so the circuit is satisfied by exactly the same witnesses as before.
+    pub fn canonicalize(&mut self) {
+        self.opcodes.iter_mut().for_each(canonicalize_expressions);
+
+        let keys: Vec<Vec<u8>> = self
+            .opcodes
+            .iter()
+            .map(|opcode| bincode::serialize(opcode).expect("opcodes are serializable"))
+            .collect();
+        let order = match self.dependency_order(|index| &keys[index]) {
+            Ok(order) => order,
+            Err((mut order, remaining)) => {
+                order.extend(remaining);
+                order
+            }
+        };
+        self.reorder_opcodes(order);
+        self.assert_messages.sort_by_key(|(location, _)| *location);
+    }
+
+    /// Returns a SHA-256 hash identifying the circuit, suitable for keying caches of artifacts derived from it.
+    ///
+    /// The hash is taken over the serialized [canonical form][Circuit::canonicalize] of the circuit, so circuits
+    /// which differ only in the ordering of terms or opcodes share a fingerprint. Assert messages are not included.
+    pub fn fingerprint(&self) -> [u8; 32] {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        let bytes = bincode::serialize(&canonical).expect("circuits are serializable");
+        Sha256::digest(bytes).into()
+    }
+}
+
+fn canonicalize_expressions(opcode: &mut Opcode) {
+    match opcode {
+        Opcode::Arithmetic(expr) => expr.canonicalize(),
+        Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, predicate, .. })) => {
+            a.canonicalize();
+            b.canonicalize();
+            if let Some(predicate) = predicate {
+                predicate.canonicalize();
+            }
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, .. }) => a.canonicalize(),
+        Opcode::Directive(Directive::PermutationSort { inputs, .. }) => {
+            inputs.iter_mut().flatten().for_each(|expr| expr.canonicalize());
+        }
+        Opcode::Brillig(brillig) => {
+            for input in &mut brillig.inputs {
+                match input {
+                    BrilligInputs::Single(expr) => expr.canonicalize(),
+                    BrilligInputs::Array(exprs) => {
+                        exprs.iter_mut().for_each(|expr| expr.canonicalize())
+                    }
+                }
+            }
+            if let Some(predicate) = &mut brillig.predicate {
+                predicate.canonicalize();
+            }
+        }
+        Opcode::MemoryOp { op, predicate, .. } => {
+            op.operation.canonicalize();
+            op.index.canonicalize();
+            op.value.canonicalize();
+            if let Some(predicate) = predicate {
+                predicate.canonicalize();
+            }
+        }
+        Opcode::BlackBoxFuncCall(_) | Opcode::MemoryInit { .. } | Opcode::Nop { .. } => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use crate::{
+        circuit::{test_utils::addition_circuit, Circuit, Opcode, PublicInputs},
+        native_types::{Expression, Witness},
+    };
+
+    fn write(circuit: &Circuit) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        circuit.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn equivalent_addition_circuits_canonicalize_equally() {
+        // w3 = w1 + w2, w4 = w3 + w1
+        let addition_circuit = Circuit {
+            current_witness_index: 5,
+            opcodes: vec![
+                Opcode::Arithmetic(Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![
+                        (FieldElement::one(), Witness(1)),
+                        (FieldElement::one(), Witness(2)),
+                        (-FieldElement::one(), Witness(3)),
+                    ],
+                    q_c: FieldElement::zero(),
+                }),
+                Opcode::Arithmetic(Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![
+                        (FieldElement::one(), Witness(1)),
+                        (FieldElement::one(), Witness(3)),
+                        (-FieldElement::one(), Witness(4)),
+                    ],
+                    q_c: FieldElement::zero(),
+                }),
+            ],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs([Witness(4)].into()),
+            ..Circuit::default()
+        };
+
+        // The same constraints with the opcodes reversed, the terms shuffled and some redundant terms.
+        let shuffled_circuit = Circuit {
+            opcodes: vec![
+                Opcode::Arithmetic(Expression {
+                    mul_terms: vec![(FieldElement::zero(), Witness(3), Witness(1))],
+                    linear_combinations: vec![
+                        (-FieldElement::one(), Witness(4)),
+                        (FieldElement::one(), Witness(3)),
+                        (FieldElement::from(2u128), Witness(1)),
+                        (-FieldElement::one(), Witness(1)),
+                    ],
+                    q_c: FieldElement::zero(),
+                }),
+                Opcode::Arithmetic(Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![
+                        (-FieldElement::one(), Witness(3)),
+                        (FieldElement::zero(), Witness(4)),
+                        (FieldElement::one(), Witness(2)),
+                        (FieldElement::one(), Witness(1)),
+                    ],
+                    q_c: FieldElement::zero(),
+                }),
+            ],
+            ..addition_circuit.clone()
+        };
+        assert_ne!(write(&addition_circuit), write(&shuffled_circuit));
+
+        let mut canonical = addition_circuit;
+        canonical.canonicalize();
+        let mut canonical_shuffled = shuffled_circuit;
+        canonical_shuffled.canonicalize();
+        assert_eq!(write(&canonical), write(&canonical_shuffled));
+        assert_eq!(canonical, canonical_shuffled);
+    }
+
+    #[test]
+    fn merges_commuted_multiplication_terms() {
+        let mut expr = Expression {
+            mul_terms: vec![
+                (FieldElement::one(), Witness(2), Witness(1)),
+                (FieldElement::one(), Witness(1), Witness(2)),
+            ],
+            linear_combinations: Vec::new(),
+            q_c: FieldElement::one(),
+        };
+        expr.canonicalize();
+        assert_eq!(expr.mul_terms, vec![(FieldElement::from(2u128), Witness(1), Witness(2))]);
+    }
+
+    #[test]
+    fn fingerprint_ignores_term_order() {
+        let circuit = addition_circuit();
+
+        let mut reordered = addition_circuit();
+        let Opcode::Arithmetic(expr) = &mut reordered.opcodes[0] else { unreachable!() };
+        expr.linear_combinations.reverse();
+        assert_ne!(circuit, reordered);
+        assert_eq!(circuit.fingerprint(), reordered.fingerprint());
+
+        let mut modified = addition_circuit();
+        let Opcode::Arithmetic(expr) = &mut modified.opcodes[0] else { unreachable!() };
+        expr.linear_combinations[0].0 = FieldElement::from(2_u128);
+        assert_ne!(circuit.fingerprint(), modified.fingerprint());
+    }
+}
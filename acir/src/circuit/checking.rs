@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+
+use acir_field::FieldElement;
+use thiserror::Error;
+
+use super::{
+    opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+    Circuit, Opcode,
+};
+use crate::native_types::{Expression, Witness, WitnessMap};
+
+/// A constraint which is not satisfied by a witness assignment, as returned by [`Circuit::check_witness`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConstraintViolation {
+    #[error("opcode {opcode_index} evaluates to {residual} rather than zero")]
+    Unsatisfied { opcode_index: usize, residual: FieldElement },
+    #[error("opcode {opcode_index} constrains witness _{} with value {value} to {num_bits} bits", witness.witness_index())]
+    RangeExceeded { opcode_index: usize, witness: Witness, value: FieldElement, num_bits: u32 },
+    #[error("opcode {opcode_index} references witness _{} which has no assigned value", witness.witness_index())]
+    MissingAssignment { opcode_index: usize, witness: Witness },
+    #[error("opcode {opcode_index} accesses index {index} of memory block {} which is out of bounds", block_id.0)]
+    MemoryOutOfBounds { opcode_index: usize, block_id: BlockId, index: FieldElement },
+    /// The opcode is a black box function which cannot be checked without executing it.
+    #[error("opcode {opcode_index} cannot be checked without executing it")]
+    Unchecked { opcode_index: usize },
+}
+
+impl Circuit {
+    /// Checks a full witness assignment against the circuit's constraints, returning every violation found.
+    ///
+    /// Unlike the solver no witnesses are derived, so every witness read by a checked opcode must already be
+    /// assigned a value in `witnesses`. Arithmetic opcodes, range constraints and AND/XOR are checked directly,
+    /// while memory operations are checked by replaying them against the contents of each block. Other black box
+    /// functions are reported as [`ConstraintViolation::Unchecked`] as checking them requires executing them.
+    /// Directives and Brillig calls only compute witness values and do not constrain them, so are skipped.
+    pub fn check_witness(&self, witnesses: &WitnessMap) -> Vec<ConstraintViolation> {
+        let mut violations = Vec::new();
+        let mut memory: HashMap<BlockId, Vec<FieldElement>> = HashMap::new();
+        for (opcode_index, opcode) in self.opcodes.iter().enumerate() {
+            let result = match opcode {
+                Opcode::Arithmetic(expr) => {
+                    evaluate(opcode_index, expr, witnesses).map(|residual| {
+                        if !residual.is_zero() {
+                            violations
+                                .push(ConstraintViolation::Unsatisfied { opcode_index, residual });
+                        }
+                    })
+                }
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE { input }) => {
+                    check_range(opcode_index, input, witnesses).map(|violation| {
+                        violations.extend(violation);
+                    })
+                }
+                Opcode::BlackBoxFuncCall(
+                    call @ (BlackBoxFuncCall::AND { lhs, rhs, output }
+                    | BlackBoxFuncCall::XOR { lhs, rhs, output }),
+                ) => check_logic_opcode(opcode_index, call, (lhs, rhs), *output, witnesses)
+                    .map(|new_violations| violations.extend(new_violations)),
+                Opcode::BlackBoxFuncCall(_) => {
+                    violations.push(ConstraintViolation::Unchecked { opcode_index });
+                    Ok(())
+                }
+                Opcode::MemoryInit { block_id, init } => init
+                    .iter()
+                    .map(|witness| assignment(opcode_index, *witness, witnesses))
+                    .collect::<Result<_, _>>()
+                    .map(|values| {
+                        memory.insert(*block_id, values);
+                    }),
+                Opcode::MemoryOp { block_id, op, predicate } => {
+                    let block = memory.entry(*block_id).or_default();
+                    check_memory_op(opcode_index, *block_id, block, op, predicate, witnesses)
+                        .map(|violation| violations.extend(violation))
+                }
+                Opcode::Directive(_) | Opcode::Brillig(_) | Opcode::Nop { .. } => Ok(()),
+            };
+            if let Err(violation) = result {
+                violations.push(violation);
+            }
+        }
+        violations
+    }
+}
+
+fn assignment(
+    opcode_index: usize,
+    witness: Witness,
+    witnesses: &WitnessMap,
+) -> Result<FieldElement, ConstraintViolation> {
+    witnesses
+        .get(&witness)
+        .copied()
+        .ok_or(ConstraintViolation::MissingAssignment { opcode_index, witness })
+}
+
+fn evaluate(
+    opcode_index: usize,
+    expr: &Expression,
+    witnesses: &WitnessMap,
+) -> Result<FieldElement, ConstraintViolation> {
+    expr.evaluate(witnesses)
+        .map_err(|witness| ConstraintViolation::MissingAssignment { opcode_index, witness })
+}
+
+fn check_range(
+    opcode_index: usize,
+    input: &FunctionInput,
+    witnesses: &WitnessMap,
+) -> Result<Option<ConstraintViolation>, ConstraintViolation> {
+    let value = assignment(opcode_index, input.witness, witnesses)?;
+    Ok((value.num_bits() > input.num_bits).then_some(ConstraintViolation::RangeExceeded {
+        opcode_index,
+        witness: input.witness,
+        value,
+        num_bits: input.num_bits,
+    }))
+}
+
+/// Checks that both inputs of an AND or XOR fit within their bit widths and that the output is their result.
+fn check_logic_opcode(
+    opcode_index: usize,
+    call: &BlackBoxFuncCall,
+    (lhs, rhs): (&FunctionInput, &FunctionInput),
+    output: Witness,
+    witnesses: &WitnessMap,
+) -> Result<Vec<ConstraintViolation>, ConstraintViolation> {
+    let mut violations = Vec::new();
+    violations.extend(check_range(opcode_index, lhs, witnesses)?);
+    violations.extend(check_range(opcode_index, rhs, witnesses)?);
+
+    let lhs_value = assignment(opcode_index, lhs.witness, witnesses)?;
+    let rhs_value = assignment(opcode_index, rhs.witness, witnesses)?;
+    let expected = match call {
+        BlackBoxFuncCall::AND { .. } => lhs_value.and(&rhs_value, lhs.num_bits),
+        _ => lhs_value.xor(&rhs_value, lhs.num_bits),
+    };
+    let residual = assignment(opcode_index, output, witnesses)? - expected;
+    if !residual.is_zero() {
+        violations.push(ConstraintViolation::Unsatisfied { opcode_index, residual });
+    }
+    Ok(violations)
+}
+
+/// Replays a memory operation against `block`, checking that a read returns the value held at its index.
+fn check_memory_op(
+    opcode_index: usize,
+    block_id: BlockId,
+    block: &mut [FieldElement],
+    op: &MemOp,
+    predicate: &Option<Expression>,
+    witnesses: &WitnessMap,
+) -> Result<Option<ConstraintViolation>, ConstraintViolation> {
+    let predicate = match predicate {
+        Some(predicate) => evaluate(opcode_index, predicate, witnesses)?,
+        None => FieldElement::one(),
+    };
+    let is_read = evaluate(opcode_index, &op.operation, witnesses)?.is_zero();
+    let index = evaluate(opcode_index, &op.index, witnesses)?;
+    let value = evaluate(opcode_index, &op.value, witnesses)?;
+
+    // As in the solver, a read with a zero predicate yields zero and a write with a zero predicate is skipped.
+    if predicate.is_zero() {
+        return Ok((is_read && !value.is_zero())
+            .then_some(ConstraintViolation::Unsatisfied { opcode_index, residual: value }));
+    }
+    let Some(cell) =
+        index.try_to_u64().and_then(|index| block.get_mut(usize::try_from(index).ok()?))
+    else {
+        return Err(ConstraintViolation::MemoryOutOfBounds { opcode_index, block_id, index });
+    };
+    if is_read {
+        let residual = value - *cell;
+        Ok((!residual.is_zero())
+            .then_some(ConstraintViolation::Unsatisfied { opcode_index, residual }))
+    } else {
+        *cell = value;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir_field::FieldElement;
+
+    use super::ConstraintViolation;
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+            test_utils::addition_circuit,
+            Circuit, Opcode,
+        },
+        native_types::{Expression, Witness, WitnessMap},
+    };
+
+    #[test]
+    fn reports_range_and_missing_assignments() {
+        let range = |witness, num_bits| {
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput { witness: Witness(witness), num_bits },
+            })
+        };
+        let circuit = Circuit {
+            opcodes: vec![
+                range(1, 8),
+                range(2, 8),
+                Opcode::Arithmetic(&Expression::from(Witness(1)) - Witness(3)),
+            ],
+            ..Circuit::default()
+        };
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(255_u128)),
+            (Witness(2), FieldElement::from(256_u128)),
+        ]));
+
+        assert_eq!(
+            circuit.check_witness(&witnesses),
+            vec![
+                ConstraintViolation::RangeExceeded {
+                    opcode_index: 1,
+                    witness: Witness(2),
+                    value: FieldElement::from(256_u128),
+                    num_bits: 8
+                },
+                ConstraintViolation::MissingAssignment { opcode_index: 2, witness: Witness(3) },
+            ]
+        );
+    }
+
+    #[test]
+    fn checks_logic_opcodes_directly() {
+        let input = |witness| FunctionInput { witness: Witness(witness), num_bits: 8 };
+        let circuit = Circuit {
+            opcodes: vec![
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+                    lhs: input(1),
+                    rhs: input(2),
+                    output: Witness(3),
+                }),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::XOR {
+                    lhs: input(1),
+                    rhs: input(2),
+                    output: Witness(4),
+                }),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SHA256 {
+                    inputs: vec![input(1)],
+                    outputs: vec![Witness(5)],
+                }),
+            ],
+            ..Circuit::default()
+        };
+        // 0b1100 & 0b1010 == 0b1000, while 0b1100 ^ 0b1010 == 0b0110 rather than 0b0111.
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(0b1100_u128)),
+            (Witness(2), FieldElement::from(0b1010_u128)),
+            (Witness(3), FieldElement::from(0b1000_u128)),
+            (Witness(4), FieldElement::from(0b0111_u128)),
+        ]));
+
+        assert_eq!(
+            circuit.check_witness(&witnesses),
+            vec![
+                ConstraintViolation::Unsatisfied { opcode_index: 1, residual: FieldElement::one() },
+                ConstraintViolation::Unchecked { opcode_index: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn replays_memory_operations() {
+        let block_id = BlockId(0);
+        let circuit = Circuit {
+            opcodes: vec![
+                Opcode::MemoryInit { block_id, init: vec![Witness(1), Witness(2)] },
+                // block[0] = w3
+                Opcode::MemoryOp {
+                    block_id,
+                    op: MemOp::write_to_mem_index(FieldElement::zero().into(), Witness(3).into()),
+                    predicate: None,
+                },
+                // w4 = block[0]
+                Opcode::MemoryOp {
+                    block_id,
+                    op: MemOp::read_at_mem_index(FieldElement::zero().into(), Witness(4)),
+                    predicate: None,
+                },
+                // w5 = block[1]
+                Opcode::MemoryOp {
+                    block_id,
+                    op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(5)),
+                    predicate: None,
+                },
+                // w5 = block[2]
+                Opcode::MemoryOp {
+                    block_id,
+                    op: MemOp::read_at_mem_index(FieldElement::from(2_u128).into(), Witness(5)),
+                    predicate: None,
+                },
+            ],
+            ..Circuit::default()
+        };
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(10_u128)),
+            (Witness(2), FieldElement::from(20_u128)),
+            (Witness(3), FieldElement::from(30_u128)),
+            (Witness(4), FieldElement::from(30_u128)),
+            (Witness(5), FieldElement::from(10_u128)),
+        ]));
+
+        assert_eq!(
+            circuit.check_witness(&witnesses),
+            vec![
+                // The initial value of block[1] is 20.
+                ConstraintViolation::Unsatisfied {
+                    opcode_index: 3,
+                    residual: -FieldElement::from(10_u128)
+                },
+                ConstraintViolation::MemoryOutOfBounds {
+                    opcode_index: 4,
+                    block_id,
+                    index: FieldElement::from(2_u128)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_witness_reports_unsatisfied_addition() {
+        let circuit = addition_circuit();
+        // w1 + w2 - w3 == 2 + 3 - 6
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(2_u128)),
+            (Witness(2), FieldElement::from(3_u128)),
+            (Witness(3), FieldElement::from(6_u128)),
+        ]));
+
+        assert_eq!(
+            circuit.check_witness(&witnesses),
+            vec![ConstraintViolation::Unsatisfied {
+                opcode_index: 0,
+                residual: -FieldElement::one()
+            }]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use super::{Circuit, Opcode};
+
+/// A difference between the opcodes of two circuits, as returned by [`diff_circuits`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CircuitChange {
+    /// An opcode which is only present in the second circuit, at `index`.
+    Added { index: usize, opcode: Opcode },
+    /// An opcode which is only present in the first circuit, at `index`.
+    Removed { index: usize, opcode: Opcode },
+    /// An opcode of the first circuit which was replaced by the opcode at `index` in the second circuit.
+    Changed { index: usize, before: Box<Opcode>, after: Box<Opcode> },
+}
+
+/// Compares the opcodes of two circuits, returning the changes which turn `a` into `b` in opcode order.
+///
+/// Opcodes are matched up using the longest common subsequence of the two opcode lists. Within each run of
+/// unmatched opcodes, removed opcodes are paired with added opcodes as [`CircuitChange::Changed`] and any
+/// excess is reported as [`CircuitChange::Removed`] or [`CircuitChange::Added`].
+///
+/// Only opcodes are compared, the circuits' parameters and return values are ignored.
+pub fn diff_circuits(a: &Circuit, b: &Circuit) -> Vec<CircuitChange> {
+    let (a, b) = (&a.opcodes, &b.opcodes);
+
+    // Common prefixes and suffixes are matched directly to keep the quadratic table small for similar circuits.
+    let prefix = a.iter().zip(b).take_while(|(a, b)| a == b).count();
+    let suffix =
+        a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+    let (a_mid, b_mid) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    // `lcs[i][j]` is the length of the longest common subsequence of `a_mid[i..]` and `b_mid[j..]`.
+    let mut lcs = vec![vec![0usize; b_mid.len() + 1]; a_mid.len() + 1];
+    for i in (0..a_mid.len()).rev() {
+        for j in (0..b_mid.len()).rev() {
+            lcs[i][j] = if a_mid[i] == b_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a_mid.len() || j < b_mid.len() {
+        if i < a_mid.len() && j < b_mid.len() && a_mid[i] == b_mid[j] {
+            flush_changes(&mut changes, &mut removed, &mut added, a, b);
+            i += 1;
+            j += 1;
+        } else if j < b_mid.len() && (i == a_mid.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            added.push(prefix + j);
+            j += 1;
+        } else {
+            removed.push(prefix + i);
+            i += 1;
+        }
+    }
+    flush_changes(&mut changes, &mut removed, &mut added, a, b);
+    changes
+}
+
+/// Records a run of unmatched opcodes, pairing up removals and additions as changes.
+fn flush_changes(
+    changes: &mut Vec<CircuitChange>,
+    removed: &mut Vec<usize>,
+    added: &mut Vec<usize>,
+    a: &[Opcode],
+    b: &[Opcode],
+) {
+    let num_changed = removed.len().min(added.len());
+    for (&before, &after) in removed.iter().zip(added.iter()) {
+        changes.push(CircuitChange::Changed {
+            index: after,
+            before: Box::new(a[before].clone()),
+            after: Box::new(b[after].clone()),
+        });
+    }
+    for &index in &removed[num_changed..] {
+        changes.push(CircuitChange::Removed { index, opcode: a[index].clone() });
+    }
+    for &index in &added[num_changed..] {
+        changes.push(CircuitChange::Added { index, opcode: b[index].clone() });
+    }
+    removed.clear();
+    added.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff_circuits, CircuitChange};
+    use crate::circuit::{
+        test_utils::{addition, addition_circuit},
+        Circuit,
+    };
+
+    #[test]
+    fn reports_added_opcode() {
+        let circuit = addition_circuit();
+        let mut extended = addition_circuit();
+        extended.opcodes.push(addition(3, 3, 4));
+
+        assert_eq!(diff_circuits(&circuit, &circuit), Vec::new());
+        assert_eq!(
+            diff_circuits(&circuit, &extended),
+            vec![CircuitChange::Added { index: 1, opcode: addition(3, 3, 4) }]
+        );
+        assert_eq!(
+            diff_circuits(&extended, &circuit),
+            vec![CircuitChange::Removed { index: 1, opcode: addition(3, 3, 4) }]
+        );
+    }
+
+    #[test]
+    fn pairs_replaced_opcodes() {
+        let before = Circuit {
+            opcodes: vec![addition(1, 2, 3), addition(3, 1, 4), addition(4, 4, 5)],
+            ..Circuit::default()
+        };
+        let after = Circuit {
+            opcodes: vec![
+                addition(1, 2, 3),
+                addition(3, 2, 4),
+                addition(4, 3, 6),
+                addition(4, 4, 5),
+            ],
+            ..Circuit::default()
+        };
+
+        assert_eq!(
+            diff_circuits(&before, &after),
+            vec![
+                CircuitChange::Changed {
+                    index: 1,
+                    before: Box::new(addition(3, 1, 4)),
+                    after: Box::new(addition(3, 2, 4))
+                },
+                CircuitChange::Added { index: 2, opcode: addition(4, 3, 6) },
+            ]
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Range,
+};
+
+use super::{opcodes::BlockId, Circuit, Opcode, OpcodeLocation, PublicInputs};
+use crate::native_types::Witness;
+
+impl Circuit {
+    /// Extracts the opcodes in `range` into a standalone circuit, for example so that a large circuit can be
+    /// proven in chunks.
+    ///
+    /// Witnesses keep their indices. Those referenced within the range which are not produced by an opcode in the
+    /// range become parameters of the sub-circuit, remaining public if they are public parameters of this circuit.
+    /// Witnesses produced within the range become return values if they are referenced by an opcode outside of the
+    /// range or are return values of this circuit. Witnesses are produced as described in [`Circuit::producers_of`].
+    ///
+    /// If the range acts on a memory block which it does not initialize, then the contents of the block at the start
+    /// of the range are also an input. An [`Opcode::MemoryInit`] of the same size as the block's last initialization
+    /// before the range is placed at the start of the sub-circuit, initializing the block from new private parameters.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of the circuit's opcodes.
+    pub fn extract_range(&self, range: Range<usize>) -> Circuit {
+        let producers = self.witness_producers();
+        let produced_in_range =
+            |witness: &Witness| producers.get(witness).map_or(false, |index| range.contains(index));
+
+        let referenced: BTreeSet<Witness> =
+            self.opcodes_in_range(range.clone()).flat_map(Opcode::witnesses).collect();
+        let referenced_outside: BTreeSet<Witness> = self
+            .opcodes
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !range.contains(index))
+            .flat_map(|(_, opcode)| opcode.witnesses())
+            .collect();
+
+        let (public_parameters, mut private_parameters): (BTreeSet<_>, BTreeSet<_>) = referenced
+            .iter()
+            .filter(|witness| !produced_in_range(witness))
+            .partition(|witness| self.public_parameters.0.contains(witness));
+        let return_values = referenced
+            .iter()
+            .filter(|witness| produced_in_range(witness))
+            .filter(|witness| {
+                referenced_outside.contains(witness) || self.return_values.0.contains(witness)
+            })
+            .copied()
+            .collect();
+
+        let mut current_witness_index = self.current_witness_index;
+        let mut block_inits = Vec::new();
+        let mut initialized_blocks: BTreeSet<BlockId> = BTreeSet::new();
+        for opcode in self.opcodes_in_range(range.clone()) {
+            match opcode {
+                Opcode::MemoryInit { block_id, .. } => {
+                    initialized_blocks.insert(*block_id);
+                }
+                Opcode::MemoryOp { block_id, .. } if initialized_blocks.insert(*block_id) => {
+                    let Some(size) =
+                        self.opcodes[..range.start].iter().rev().find_map(|opcode| match opcode {
+                            Opcode::MemoryInit { block_id: id, init } if id == block_id => {
+                                Some(init.len())
+                            }
+                            _ => None,
+                        })
+                    else {
+                        continue;
+                    };
+                    let init: Vec<Witness> = (1..=size as u32)
+                        .map(|offset| Witness(current_witness_index + offset))
+                        .collect();
+                    current_witness_index += size as u32;
+                    private_parameters.extend(init.iter().copied());
+                    block_inits.push(Opcode::MemoryInit { block_id: *block_id, init });
+                }
+                _ => (),
+            }
+        }
+
+        let offset = block_inits.len();
+        let assert_messages = self
+            .assert_messages
+            .iter()
+            .filter_map(|(location, message)| {
+                let new_location = match *location {
+                    OpcodeLocation::Acir(index) if range.contains(&index) => {
+                        OpcodeLocation::Acir(index - range.start + offset)
+                    }
+                    OpcodeLocation::Brillig { acir_index, brillig_index }
+                        if range.contains(&acir_index) =>
+                    {
+                        OpcodeLocation::Brillig {
+                            acir_index: acir_index - range.start + offset,
+                            brillig_index,
+                        }
+                    }
+                    _ => return None,
+                };
+                Some((new_location, message.clone()))
+            })
+            .collect();
+
+        let mut opcodes = block_inits;
+        opcodes.extend(self.opcodes_in_range(range).cloned());
+        Circuit {
+            current_witness_index,
+            opcodes,
+            private_parameters,
+            public_parameters: PublicInputs(public_parameters),
+            return_values: PublicInputs(return_values),
+            assert_messages,
+        }
+    }
+
+    /// Returns the index of the first opcode which produces each witness, as described in [`Circuit::producers_of`].
+    fn witness_producers(&self) -> HashMap<Witness, usize> {
+        let mut known = self.circuit_arguments();
+        let mut producers = HashMap::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            for witness in opcode.solved_witness(&known).into_iter().chain(opcode.write_witnesses())
+            {
+                producers.entry(witness).or_insert(index);
+            }
+            known.extend(opcode.witnesses());
+        }
+        producers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        circuit::{test_utils::addition, Circuit, OpcodeLocation, PublicInputs},
+        native_types::Witness,
+    };
+
+    #[test]
+    fn extracts_chunk_of_additions() {
+        // w3 = w1 + w2, w4 = w3 + w1, w5 = w4 + w2, w6 = w5 + w3
+        let circuit = Circuit {
+            current_witness_index: 6,
+            opcodes: vec![
+                addition(1, 2, 3),
+                addition(3, 1, 4),
+                addition(4, 2, 5),
+                addition(5, 3, 6),
+            ],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(2)])),
+            return_values: PublicInputs(BTreeSet::from([Witness(6)])),
+            assert_messages: vec![
+                (OpcodeLocation::Acir(0), "first".to_owned()),
+                (OpcodeLocation::Acir(2), "third".to_owned()),
+            ],
+        };
+
+        let chunk = circuit.extract_range(1..3);
+
+        assert_eq!(
+            chunk,
+            Circuit {
+                current_witness_index: 6,
+                opcodes: vec![addition(3, 1, 4), addition(4, 2, 5)],
+                private_parameters: BTreeSet::from([Witness(1), Witness(3)]),
+                public_parameters: PublicInputs(BTreeSet::from([Witness(2)])),
+                // w4 is only used within the chunk, while w5 is consumed by the last opcode.
+                return_values: PublicInputs(BTreeSet::from([Witness(5)])),
+                assert_messages: vec![(OpcodeLocation::Acir(1), "third".to_owned())],
+            }
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use acir_field::FieldElement;
+use brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex};
+
+use super::{
+    brillig::{Brillig, BrilligInputs, BrilligOutputs},
+    Circuit, Opcode,
+};
+use crate::native_types::Expression;
+
+impl Circuit {
+    /// Replaces [`Opcode::Brillig`] calls which compute their outputs as simple arithmetic on their inputs
+    /// with [`Opcode::Arithmetic`] opcodes assigning each output.
+    ///
+    /// A Brillig call is only inlined if it is always executed and its bytecode runs straight through, using only
+    /// constants, moves and field addition, subtraction, multiplication and division by constants,
+    /// such that each output is an expression of at most degree 2 in the call's inputs.
+    /// Calls which make foreign calls, access memory or branch are left untouched.
+    ///
+    /// Note that unlike the Brillig call, the arithmetic opcodes constrain each output to equal the computed value.
+    /// Assert messages associated with inlined calls are dropped.
+    pub fn inline_trivial_brillig(&mut self) {
+        let mut new_indices = Vec::with_capacity(self.opcodes.len());
+        let mut opcodes = Vec::with_capacity(self.opcodes.len());
+        for opcode in std::mem::take(&mut self.opcodes) {
+            let inlined = match &opcode {
+                Opcode::Brillig(brillig) => inline_brillig(brillig),
+                _ => None,
+            };
+            match inlined {
+                Some(arithmetic_opcodes) => {
+                    new_indices.push(None);
+                    opcodes.extend(arithmetic_opcodes.into_iter().map(Opcode::Arithmetic));
+                }
+                None => {
+                    new_indices.push(Some(opcodes.len()));
+                    opcodes.push(opcode);
+                }
+            }
+        }
+        self.opcodes = opcodes;
+
+        self.remap_assert_messages(&new_indices);
+    }
+}
+
+/// Returns the expressions `output - value == 0` for each of the Brillig call's outputs, if its bytecode can be
+/// reduced to arithmetic on its inputs.
+fn inline_brillig(brillig: &Brillig) -> Option<Vec<Expression>> {
+    let always_executed = brillig
+        .predicate
+        .as_ref()
+        .map_or(true, |predicate| predicate.to_const().map_or(false, |value| !value.is_zero()));
+    if !always_executed {
+        return None;
+    }
+
+    let mut registers: HashMap<RegisterIndex, Expression> = HashMap::new();
+    for (index, input) in brillig.inputs.iter().enumerate() {
+        match input {
+            BrilligInputs::Single(expr) => registers.insert(RegisterIndex(index), expr.clone()),
+            // Arrays are passed through memory.
+            BrilligInputs::Array(_) => return None,
+        };
+    }
+
+    for opcode in &brillig.bytecode {
+        match opcode {
+            BrilligOpcode::Const { destination, value, bit_size } => {
+                let value = value.to_field();
+                // The VM converts constants which do not fit in an integer's bit size to two's complement.
+                if *bit_size < FieldElement::max_num_bits()
+                    && (value.is_negative() || value.num_bits() > *bit_size)
+                {
+                    return None;
+                }
+                registers.insert(*destination, value.into());
+            }
+            BrilligOpcode::Mov { destination, source } => {
+                let value = registers.get(source)?.clone();
+                registers.insert(*destination, value);
+            }
+            BrilligOpcode::BinaryFieldOp { destination, op, lhs, rhs } => {
+                let (lhs, rhs) = (registers.get(lhs)?, registers.get(rhs)?);
+                let result = match op {
+                    BinaryFieldOp::Add => lhs + rhs,
+                    BinaryFieldOp::Sub => lhs - rhs,
+                    BinaryFieldOp::Mul => (lhs * rhs)?,
+                    BinaryFieldOp::Div => {
+                        let divisor = rhs.to_const().filter(|divisor| !divisor.is_zero())?;
+                        lhs * (FieldElement::one() / divisor)
+                    }
+                    BinaryFieldOp::Equals => return None,
+                };
+                registers.insert(*destination, result);
+            }
+            BrilligOpcode::Stop => break,
+            _ => return None,
+        }
+    }
+
+    brillig
+        .outputs
+        .iter()
+        .enumerate()
+        .map(|(index, output)| match output {
+            BrilligOutputs::Simple(witness) => {
+                Some(registers.get(&RegisterIndex(index))? - &Expression::from(*witness))
+            }
+            BrilligOutputs::Array(_) => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+    use brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex, RegisterOrMemory, Value};
+
+    use crate::{
+        circuit::{
+            brillig::{Brillig, BrilligInputs, BrilligOutputs},
+            Circuit, Opcode, OpcodeLocation,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    fn brillig(bytecode: Vec<BrilligOpcode>) -> Opcode {
+        // Computes `w3 = (w1 + w2) * 3` and `w4 = w1 - 5`.
+        Opcode::Brillig(Brillig {
+            inputs: vec![
+                BrilligInputs::Single(Witness(1).into()),
+                BrilligInputs::Single(Witness(2).into()),
+            ],
+            outputs: vec![BrilligOutputs::Simple(Witness(3)), BrilligOutputs::Simple(Witness(4))],
+            foreign_call_results: Vec::new(),
+            bytecode,
+            trap_messages: vec![],
+            predicate: None,
+        })
+    }
+
+    fn linear_bytecode() -> Vec<BrilligOpcode> {
+        vec![
+            BrilligOpcode::Const {
+                destination: RegisterIndex(2),
+                value: Value::from(3u128),
+                bit_size: FieldElement::max_num_bits(),
+            },
+            BrilligOpcode::Const {
+                destination: RegisterIndex(3),
+                value: Value::from(5u128),
+                bit_size: FieldElement::max_num_bits(),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                destination: RegisterIndex(4),
+                op: BinaryFieldOp::Add,
+                lhs: RegisterIndex(0),
+                rhs: RegisterIndex(1),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                destination: RegisterIndex(1),
+                op: BinaryFieldOp::Sub,
+                lhs: RegisterIndex(0),
+                rhs: RegisterIndex(3),
+            },
+            BrilligOpcode::BinaryFieldOp {
+                destination: RegisterIndex(0),
+                op: BinaryFieldOp::Mul,
+                lhs: RegisterIndex(4),
+                rhs: RegisterIndex(2),
+            },
+            BrilligOpcode::Stop,
+        ]
+    }
+
+    #[test]
+    fn inlines_linear_brillig() {
+        let mut circuit = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![brillig(linear_bytecode())],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            assert_messages: vec![(OpcodeLocation::Acir(0), "inlined".to_owned())],
+            ..Circuit::default()
+        };
+
+        circuit.inline_trivial_brillig();
+
+        let three = FieldElement::from(3u128);
+        let w = |index| Expression::from(Witness(index));
+        assert_eq!(
+            circuit.opcodes,
+            vec![
+                Opcode::Arithmetic(&(&(&w(1) * three) + &(&w(2) * three)) - &w(3)),
+                Opcode::Arithmetic(&(&w(1) - &w(4)) - &FieldElement::from(5u128).into()),
+            ]
+        );
+        assert!(circuit.assert_messages.is_empty());
+    }
+
+    #[test]
+    fn leaves_foreign_calls_and_memory_accesses() {
+        let foreign_call = BrilligOpcode::ForeignCall {
+            function: "invert".to_owned(),
+            destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex(0))],
+            destination_types: None,
+            inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex(0))],
+            input_types: None,
+        };
+        let load =
+            BrilligOpcode::Load { destination: RegisterIndex(0), source_pointer: RegisterIndex(1) };
+        for opcode in [foreign_call, load] {
+            let mut bytecode = linear_bytecode();
+            bytecode.insert(2, opcode);
+            let original = Circuit { opcodes: vec![brillig(bytecode)], ..Circuit::default() };
+
+            let mut circuit = original.clone();
+            circuit.inline_trivial_brillig();
+            assert_eq!(circuit, original);
+        }
+    }
+}
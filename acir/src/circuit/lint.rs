@@ -0,0 +1,97 @@
+use std::collections::BTreeSet;
+
+use thiserror::Error;
+
+use super::{Circuit, Opcode};
+use crate::native_types::Witness;
+
+/// A likely soundness issue in a circuit, as returned by [`Circuit::lint`].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum LintWarning {
+    #[error("return value {witness} is not constrained by any opcode, so a prover may assign it any value")]
+    UnconstrainedOutput { witness: Witness },
+}
+
+impl Circuit {
+    /// Checks the circuit for likely soundness issues.
+    ///
+    /// A return value is reported as unconstrained if it is not referenced by any arithmetic, black box or memory
+    /// opcode. Brillig calls and directives only compute hints for the solver without constraining them, so a
+    /// return value which is only assigned by one of these opcodes must still be constrained elsewhere.
+    /// Return values which are also circuit parameters are not reported, as their values are supplied as inputs.
+    pub fn lint(&self) -> Vec<LintWarning> {
+        let constrained: BTreeSet<Witness> = self
+            .opcodes
+            .iter()
+            .filter(|opcode| !matches!(opcode, Opcode::Brillig(_) | Opcode::Directive(_)))
+            .flat_map(Opcode::witnesses)
+            .collect();
+        let parameters = self.circuit_arguments();
+
+        self.return_values
+            .0
+            .iter()
+            .filter(|witness| !constrained.contains(witness) && !parameters.contains(witness))
+            .map(|witness| LintWarning::UnconstrainedOutput { witness: *witness })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+    use brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex};
+
+    use super::LintWarning;
+    use crate::{
+        circuit::{
+            brillig::{Brillig, BrilligInputs, BrilligOutputs},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    #[test]
+    fn flags_output_only_assigned_by_brillig() {
+        // w3 = w1 + w2, computed by Brillig without any constraint.
+        let brillig = Opcode::Brillig(Brillig {
+            inputs: vec![
+                BrilligInputs::Single(Witness(1).into()),
+                BrilligInputs::Single(Witness(2).into()),
+            ],
+            outputs: vec![BrilligOutputs::Simple(Witness(3))],
+            foreign_call_results: Vec::new(),
+            bytecode: vec![BrilligOpcode::BinaryFieldOp {
+                destination: RegisterIndex::from(0),
+                op: BinaryFieldOp::Add,
+                lhs: RegisterIndex::from(0),
+                rhs: RegisterIndex::from(1),
+            }],
+            trap_messages: vec![],
+            predicate: None,
+        });
+        let mut circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![brillig],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(3)])),
+            ..Circuit::default()
+        };
+
+        assert_eq!(circuit.lint(), vec![LintWarning::UnconstrainedOutput { witness: Witness(3) }]);
+
+        // Constraining the Brillig output resolves the warning.
+        circuit.opcodes.push(Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(3)),
+            ],
+            q_c: FieldElement::zero(),
+        }));
+        assert_eq!(circuit.lint(), Vec::new());
+    }
+}
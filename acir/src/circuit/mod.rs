@@ -1,25 +1,50 @@
 pub mod black_box_functions;
 pub mod brillig;
+mod builder;
+mod canonical;
+mod checking;
+mod diff;
 pub mod directives;
+mod extraction;
+mod inlining;
+mod lint;
 pub mod opcodes;
+mod ordering;
+mod partition;
+mod pretty;
+mod pruning;
+pub mod serialization;
+mod specialization;
+mod stats;
+#[cfg(test)]
+mod test_utils;
+mod validation;
 
 use crate::native_types::Witness;
+pub use builder::CircuitBuilder;
+pub use checking::ConstraintViolation;
+pub use diff::{diff_circuits, CircuitChange};
+pub use lint::LintWarning;
 pub use opcodes::Opcode;
+pub use ordering::CyclicDependency;
+#[cfg(not(feature = "serialize-messagepack"))]
+pub use serialization::CircuitStreamReader;
+pub use serialization::{CircuitParameters, CircuitReadError, SerializationError};
+pub use stats::{BlockInfo, CostModel, DefaultCostModel, MemoryStats, OpcodeStats};
 use thiserror::Error;
+pub use validation::CircuitValidationError;
 
-use std::{io::prelude::*, num::ParseIntError, str::FromStr};
-
-use flate2::Compression;
+use std::{num::ParseIntError, ops::Range, str::FromStr};
 
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Circuit {
     // current_witness_index is the highest witness index in the circuit. The next witness to be added to this circuit
     // will take on this value. (The value is cached here as an optimization.)
     pub current_witness_index: u32,
-    pub opcodes: Vec<Opcode>,
 
     /// The set of private inputs to the circuit.
     pub private_parameters: BTreeSet<Witness>,
@@ -30,6 +55,9 @@ pub struct Circuit {
     pub public_parameters: PublicInputs,
     /// The set of public inputs calculated within the circuit.
     pub return_values: PublicInputs,
+    // The opcodes are serialized after the circuit's parameters so that the parameters can be read without first
+    // reading every opcode, see `CircuitStreamReader`.
+    pub opcodes: Vec<Opcode>,
     /// Maps opcode locations to failed assertion messages.
     /// These messages are embedded in the circuit to provide useful feedback to users
     /// when a constraint in the circuit is not satisfied.
@@ -38,6 +66,7 @@ pub struct Circuit {
     // c++ code at the moment when it is, due to OpcodeLocation needing a comparison
     // implementation which is never generated.
     #[serde(skip, default)]
+    #[cfg_attr(feature = "arbitrary", arbitrary(default))]
     pub assert_messages: Vec<(OpcodeLocation, String)>,
 }
 
@@ -118,47 +147,230 @@ impl Circuit {
         self.private_parameters.union(&self.public_parameters.0).cloned().collect()
     }
 
+    /// Returns the opcode at `index`, if it exists.
+    pub fn opcode(&self, index: usize) -> Option<&Opcode> {
+        self.opcodes.get(index)
+    }
+
+    /// Returns an iterator over the opcodes in `range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds of the circuit's opcodes.
+    pub fn opcodes_in_range(&self, range: Range<usize>) -> impl Iterator<Item = &Opcode> {
+        self.opcodes[range].iter()
+    }
+
+    /// Returns a mutable reference to the opcode at `index`, if it exists.
+    ///
+    /// Unlike [`Circuit::replace_opcode`], `current_witness_index` is not updated if the opcode is modified
+    /// to reference new witnesses.
+    pub fn opcode_mut(&mut self, index: usize) -> Option<&mut Opcode> {
+        self.opcodes.get_mut(index)
+    }
+
+    /// Replaces the opcode at `index` with `opcode`, returning the opcode which was replaced.
+    ///
+    /// `current_witness_index` is raised if `opcode` references a witness above it.
+    /// If `index` is out of bounds then the circuit is left unmodified and `None` is returned.
+    pub fn replace_opcode(&mut self, index: usize, opcode: Opcode) -> Option<Opcode> {
+        let slot = self.opcodes.get_mut(index)?;
+        if let Some(witness) = opcode.witnesses().last() {
+            self.current_witness_index = self.current_witness_index.max(witness.witness_index());
+        }
+        Some(std::mem::replace(slot, opcode))
+    }
+
+    /// Returns the witnesses whose values must be provided by the caller in order to solve the circuit.
+    ///
+    /// This is every private parameter along with each public input which is not computed within the circuit.
+    /// A public input is computed within the circuit if an opcode assigns it a value, or if it is the single
+    /// unknown witness of an [`Opcode::Arithmetic`] when the opcodes are executed in order, as in the solver.
+    /// Public parameters which are assigned by an opcode are therefore not reported as required.
+    pub fn required_inputs(&self) -> BTreeSet<Witness> {
+        let mut derived: BTreeSet<Witness> =
+            self.opcodes.iter().flat_map(Opcode::write_witnesses).collect();
+        let mut known: BTreeSet<Witness> = self
+            .private_parameters
+            .iter()
+            .chain(self.public_parameters.0.difference(&derived))
+            .copied()
+            .collect();
+        for opcode in &self.opcodes {
+            derived.extend(opcode.solved_witness(&known));
+            known.extend(opcode.witnesses());
+        }
+
+        let public_inputs = self.public_inputs().0;
+        let required_public_inputs = public_inputs.difference(&derived);
+        self.private_parameters.iter().chain(required_public_inputs).copied().collect()
+    }
+
+    /// Returns the indices of the opcodes which assign a value to `witness`.
+    ///
+    /// An opcode assigns a witness if it is one of the opcode's outputs, or if the witness is the single unknown of an
+    /// [`Opcode::Arithmetic`] when the opcodes are executed in order from the circuit's parameters, as in the solver.
+    /// A well-formed circuit has a single producer for every witness which is not a parameter.
+    pub fn producers_of(&self, witness: Witness) -> Vec<usize> {
+        let mut known = self.circuit_arguments();
+        let mut producers = Vec::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            if opcode.solved_witness(&known) == Some(witness)
+                || opcode.write_witnesses().contains(&witness)
+            {
+                producers.push(index);
+            }
+            known.extend(opcode.witnesses());
+        }
+        producers
+    }
+
     /// Returns all public inputs. This includes those provided as parameters to the circuit and those
     /// computed as return values.
+    ///
+    /// See [`PublicInputs::as_ordered_vec`] for the order in which their values are passed to a verifier.
     pub fn public_inputs(&self) -> PublicInputs {
         let public_inputs =
             self.public_parameters.0.union(&self.return_values.0).cloned().collect();
         PublicInputs(public_inputs)
     }
 
-    #[cfg(feature = "serialize-messagepack")]
-    pub fn write<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
-        let buf = rmp_serde::to_vec(&self).unwrap();
-        let mut deflater = flate2::write::DeflateEncoder::new(writer, Compression::best());
-        deflater.write_all(&buf).unwrap();
+    /// Replaces each witness `w` referenced by the circuit with `f(w)`.
+    ///
+    /// This applies to the witnesses referenced by opcodes as well as the circuit's parameters and return values.
+    /// `current_witness_index` is then set to the largest witness index referenced by the remapped circuit.
+    ///
+    /// `f` should be injective, otherwise distinct witnesses will be merged together.
+    pub fn remap_witnesses(&mut self, f: impl Fn(Witness) -> Witness) {
+        for opcode in &mut self.opcodes {
+            opcode.remap_witnesses(&f);
+        }
+        let remap_set =
+            |witnesses: &BTreeSet<Witness>| witnesses.iter().map(|witness| f(*witness)).collect();
+        self.private_parameters = remap_set(&self.private_parameters);
+        self.public_parameters = PublicInputs(remap_set(&self.public_parameters.0));
+        self.return_values = PublicInputs(remap_set(&self.return_values.0));
+
+        self.recompute_witness_index();
+    }
+
+    /// Sets `current_witness_index` to the largest witness index referenced by the circuit's opcodes,
+    /// parameters or return values.
+    ///
+    /// A `current_witness_index` below this value will be reported by [`Circuit::validate`].
+    pub fn recompute_witness_index(&mut self) {
+        self.current_witness_index = self.max_witness_index();
+    }
 
-        Ok(())
+    /// Adds `offset` to the index of every witness referenced by the circuit.
+    pub fn shift_witnesses(&mut self, offset: u32) {
+        self.remap_witnesses(|witness| Witness(witness.witness_index() + offset));
     }
-    #[cfg(feature = "serialize-messagepack")]
-    pub fn read<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
-        let mut deflater = flate2::read::DeflateDecoder::new(reader);
-        let mut buf_d = Vec::new();
-        deflater.read_to_end(&mut buf_d).unwrap();
-        let circuit = rmp_serde::from_slice(buf_d.as_slice()).unwrap();
-        Ok(circuit)
-    }
-
-    #[cfg(not(feature = "serialize-messagepack"))]
-    pub fn write<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
-        let buf = bincode::serialize(&self).unwrap();
-        let mut encoder = flate2::write::GzEncoder::new(writer, Compression::default());
-        encoder.write_all(&buf).unwrap();
-        encoder.finish().unwrap();
-        Ok(())
+
+    /// Appends the opcodes of `other` to this circuit.
+    ///
+    /// The witnesses and memory blocks of `other` are renumbered so that they do not collide with those of `self`.
+    /// The parameters and return values of both circuits are merged.
+    pub fn append(&mut self, other: Circuit) {
+        self.append_linked(other, &[]);
     }
 
-    #[cfg(not(feature = "serialize-messagepack"))]
-    pub fn read<R: std::io::Read>(reader: R) -> std::io::Result<Self> {
-        let mut gz_decoder = flate2::read::GzDecoder::new(reader);
-        let mut buf_d = Vec::new();
-        gz_decoder.read_to_end(&mut buf_d).unwrap();
-        let circuit = bincode::deserialize(&buf_d).unwrap();
-        Ok(circuit)
+    /// Appends the opcodes of `other` to this circuit as [`Circuit::append`] does, while unifying each pair
+    /// of witnesses in `links`.
+    ///
+    /// Each link is of the form `(witness of self, witness of other)`, with all references to the latter
+    /// being replaced by the former. This allows wiring the outputs of this circuit into the inputs of `other`.
+    /// Linked witnesses of `other` are no longer treated as its parameters as their values are computed by `self`.
+    pub fn append_linked(&mut self, mut other: Circuit, links: &[(Witness, Witness)]) {
+        let witness_offset = self.current_witness_index;
+        let links: BTreeMap<Witness, Witness> =
+            links.iter().map(|(own, others)| (*others, *own)).collect();
+        for linked in links.keys() {
+            other.private_parameters.remove(linked);
+            other.public_parameters.0.remove(linked);
+        }
+
+        let other_witness_index = other.current_witness_index;
+        other.remap_witnesses(|witness| {
+            links
+                .get(&witness)
+                .copied()
+                .unwrap_or(Witness(witness.witness_index() + witness_offset))
+        });
+
+        let block_offset = self
+            .opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } => {
+                    Some(block_id.0 + 1)
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+        for opcode in &mut other.opcodes {
+            if let Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } = opcode
+            {
+                block_id.0 += block_offset;
+            }
+        }
+
+        let opcode_offset = self.opcodes.len();
+        self.assert_messages.extend(other.assert_messages.into_iter().map(
+            |(location, message)| {
+                let location = match location {
+                    OpcodeLocation::Acir(index) => OpcodeLocation::Acir(index + opcode_offset),
+                    OpcodeLocation::Brillig { acir_index, brillig_index } => {
+                        OpcodeLocation::Brillig {
+                            acir_index: acir_index + opcode_offset,
+                            brillig_index,
+                        }
+                    }
+                };
+                (location, message)
+            },
+        ));
+
+        self.opcodes.extend(other.opcodes);
+        self.private_parameters.extend(other.private_parameters);
+        self.public_parameters.0.extend(other.public_parameters.0);
+        self.return_values.0.extend(other.return_values.0);
+        self.current_witness_index =
+            self.current_witness_index.max(other_witness_index + witness_offset);
+    }
+
+    /// Returns the largest witness index referenced by the circuit's opcodes, parameters or return values.
+    fn max_witness_index(&self) -> u32 {
+        let opcode_witnesses =
+            self.opcodes.iter().filter_map(|opcode| opcode.witnesses().last().copied());
+        self.private_parameters
+            .iter()
+            .chain(&self.public_parameters.0)
+            .chain(&self.return_values.0)
+            .copied()
+            .chain(opcode_witnesses)
+            .map(|witness| witness.witness_index())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Moves each assert message from the opcode at index `i` to the opcode at `new_indices[i]`.
+    ///
+    /// Messages are dropped if their opcode has no new index, including those which refer to opcodes beyond the end
+    /// of `new_indices`.
+    fn remap_assert_messages(&mut self, new_indices: &[Option<usize>]) {
+        self.assert_messages.retain_mut(|(location, _)| {
+            let (OpcodeLocation::Acir(index) | OpcodeLocation::Brillig { acir_index: index, .. }) =
+                location;
+            match new_indices.get(*index).copied().flatten() {
+                Some(new_index) => {
+                    *index = new_index;
+                    true
+                }
+                None => false,
+            }
+        });
     }
 }
 
@@ -199,10 +411,23 @@ impl std::fmt::Debug for Circuit {
     }
 }
 
+/// A set of public inputs to a circuit.
+///
+/// Public inputs are always ordered by ascending witness index, regardless of the order in which they were declared.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct PublicInputs(pub BTreeSet<Witness>);
 
 impl PublicInputs {
+    /// Returns the public inputs in the order in which their values must be provided to a verifier.
+    ///
+    /// This is in ascending order of witness index rather than in the order in which the inputs were declared,
+    /// so backends which lay out public inputs in declaration order must sort them to match.
+    /// The order is the same as that of [`PublicInputs::indices`].
+    pub fn as_ordered_vec(&self) -> Vec<Witness> {
+        self.0.iter().copied().collect()
+    }
+
     /// Returns the witness index of each public input
     pub fn indices(&self) -> Vec<u32> {
         self.0.iter().map(|witness| witness.witness_index()).collect()
@@ -219,10 +444,12 @@ mod tests {
 
     use super::{
         opcodes::{BlackBoxFuncCall, FunctionInput},
-        Circuit, Opcode, PublicInputs,
+        test_utils::addition_circuit,
+        Circuit, Opcode, OpcodeLocation, PublicInputs,
     };
-    use crate::native_types::Witness;
+    use crate::native_types::{Witness, WitnessMap};
     use acir_field::FieldElement;
+    use std::collections::BTreeMap;
 
     fn and_opcode() -> Opcode {
         Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
@@ -283,4 +510,278 @@ mod tests {
         let deserialized = serde_json::from_str(&json).unwrap();
         assert_eq!(circuit, deserialized);
     }
+
+    #[test]
+    fn shift_witnesses() {
+        use super::opcodes::{BlockId, MemOp};
+
+        let mut circuit = Circuit {
+            current_witness_index: 5,
+            opcodes: vec![
+                Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::write_to_mem_index(FieldElement::one().into(), Witness(3).into()),
+                    predicate: None,
+                },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(4)),
+                    predicate: None,
+                },
+            ],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(3)]),
+            return_values: PublicInputs([Witness(4)].into()),
+            ..Circuit::default()
+        };
+        circuit.shift_witnesses(100);
+
+        assert_eq!(circuit.current_witness_index, 104);
+        assert_eq!(
+            circuit.private_parameters,
+            BTreeSet::from([Witness(101), Witness(102), Witness(103)])
+        );
+        assert_eq!(circuit.return_values, PublicInputs([Witness(104)].into()));
+        assert_eq!(
+            circuit.opcodes[0],
+            Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(101), Witness(102)] }
+        );
+        assert_eq!(
+            circuit.opcodes[2],
+            Opcode::MemoryOp {
+                block_id: BlockId(0),
+                op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(104)),
+                predicate: None,
+            }
+        );
+
+        let mut bytes = Vec::new();
+        circuit.write(&mut bytes).unwrap();
+        assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
+    }
+
+    #[test]
+    fn schnorr_verify_required_inputs() {
+        let signature = (3..(3 + 64)).map(|i| FunctionInput { witness: Witness(i), num_bits: 8 });
+        let message =
+            ((3 + 64)..(3 + 64 + 10)).map(|i| FunctionInput { witness: Witness(i), num_bits: 8 });
+        let output = Witness(3 + 64 + 10);
+        let circuit = Circuit {
+            current_witness_index: 100,
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SchnorrVerify {
+                public_key_x: FunctionInput { witness: Witness(1), num_bits: 254 },
+                public_key_y: FunctionInput { witness: Witness(2), num_bits: 254 },
+                signature: signature.collect(),
+                message: message.collect(),
+                output,
+            })],
+            private_parameters: BTreeSet::from_iter((1..output.witness_index()).map(Witness)),
+            return_values: PublicInputs(BTreeSet::from([output])),
+            ..Circuit::default()
+        };
+
+        let required = circuit.required_inputs();
+        assert_eq!(required.len(), 76);
+        assert_eq!(required, circuit.private_parameters);
+        assert!(!required.contains(&output));
+    }
+
+    #[test]
+    fn required_inputs_exclude_computed_public_inputs() {
+        // w1 + w2 - w3 == 0, where `w3` is a public parameter which is also returned.
+        // `w4` is returned without being constrained by any opcode and so must be provided.
+        let circuit = Circuit {
+            current_witness_index: 5,
+            opcodes: vec![
+                Opcode::Arithmetic(crate::native_types::Expression {
+                    mul_terms: Vec::new(),
+                    linear_combinations: vec![
+                        (FieldElement::one(), Witness(1)),
+                        (FieldElement::one(), Witness(2)),
+                        (-FieldElement::one(), Witness(3)),
+                    ],
+                    q_c: FieldElement::zero(),
+                }),
+                and_opcode(),
+            ],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(2), Witness(3)])),
+            return_values: PublicInputs(BTreeSet::from([Witness(3), Witness(4)])),
+            ..Circuit::default()
+        };
+
+        // The AND opcode assigns `w3` so it is derived rather than provided, despite being a public parameter.
+        assert_eq!(circuit.required_inputs(), BTreeSet::from([Witness(1), Witness(2), Witness(4)]));
+    }
+
+    #[test]
+    fn recompute_witness_index() {
+        let mut circuit = Circuit {
+            current_witness_index: 100,
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
+                low: FunctionInput { witness: Witness(1), num_bits: 128 },
+                high: FunctionInput { witness: Witness(2), num_bits: 128 },
+                outputs: (Witness(76), Witness(77)),
+            })],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            ..Circuit::default()
+        };
+        circuit.recompute_witness_index();
+        assert_eq!(circuit.current_witness_index, 77);
+
+        // An under-counted index is reported by validation and corrected.
+        circuit.current_witness_index = 2;
+        assert!(circuit.validate().is_err());
+        circuit.recompute_witness_index();
+        assert_eq!(circuit.current_witness_index, 77);
+        assert_eq!(circuit.validate(), Ok(()));
+
+        // Parameters which are not referenced by any opcode are also taken into account.
+        circuit.return_values = PublicInputs(BTreeSet::from([Witness(80)]));
+        circuit.recompute_witness_index();
+        assert_eq!(circuit.current_witness_index, 80);
+    }
+
+    #[test]
+    fn public_inputs_are_ordered_by_witness_index() {
+        // The scalar multiplication's outputs are declared in descending order and are not contiguous.
+        let circuit = Circuit {
+            current_witness_index: 8,
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
+                low: FunctionInput { witness: Witness(1), num_bits: 128 },
+                high: FunctionInput { witness: Witness(2), num_bits: 128 },
+                outputs: (Witness(7), Witness(4)),
+            })],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(2)])),
+            return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(7), Witness(4)])),
+            ..Circuit::default()
+        };
+
+        assert_eq!(circuit.return_values.as_ordered_vec(), vec![Witness(4), Witness(7)]);
+        assert_eq!(
+            circuit.public_inputs().as_ordered_vec(),
+            vec![Witness(2), Witness(4), Witness(7)]
+        );
+        assert_eq!(circuit.public_inputs().indices(), vec![2, 4, 7]);
+    }
+
+    #[test]
+    fn drops_assert_messages_of_missing_opcodes() {
+        let mut circuit = addition_circuit();
+        circuit.assert_messages = vec![
+            (OpcodeLocation::Acir(0), "sum".to_owned()),
+            (OpcodeLocation::Brillig { acir_index: 3, brillig_index: 0 }, "stale".to_owned()),
+        ];
+
+        circuit.remap_assert_messages(&[Some(1)]);
+        assert_eq!(circuit.assert_messages, vec![(OpcodeLocation::Acir(1), "sum".to_owned())]);
+    }
+
+    #[test]
+    fn producers_of_addition_output() {
+        let circuit = addition_circuit();
+        assert_eq!(circuit.producers_of(Witness(3)), vec![0]);
+        assert_eq!(circuit.producers_of(Witness(1)), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn replace_opcode() {
+        let mut circuit = addition_circuit();
+        let original = circuit.opcode(0).cloned().unwrap();
+        assert_eq!(circuit.opcode(1), None);
+
+        // w1 * w2 - w5 == 0
+        let product = Opcode::Arithmetic(crate::native_types::Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(5))],
+            q_c: FieldElement::zero(),
+        });
+        assert_eq!(circuit.replace_opcode(0, product.clone()), Some(original));
+        assert_eq!(circuit.opcodes, vec![product.clone()]);
+        assert_eq!(circuit.current_witness_index, 5);
+        assert_eq!(circuit.replace_opcode(1, product), None);
+
+        let mut bytes = Vec::new();
+        circuit.write(&mut bytes).unwrap();
+        assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
+
+        if let Some(Opcode::Arithmetic(expr)) = circuit.opcode_mut(0) {
+            expr.q_c = FieldElement::one();
+        }
+        assert_eq!(
+            circuit.opcode(0).unwrap().clone().arithmetic().unwrap().q_c,
+            FieldElement::one()
+        );
+    }
+
+    #[test]
+    fn append_circuit() {
+        let mut circuit = addition_circuit();
+        circuit.append(addition_circuit());
+
+        assert_eq!(circuit.current_witness_index, 8);
+        assert_eq!(
+            circuit.private_parameters,
+            BTreeSet::from([Witness(1), Witness(2), Witness(5), Witness(6)])
+        );
+        assert_eq!(circuit.return_values, PublicInputs([Witness(3), Witness(7)].into()));
+
+        // Both additions can be satisfied independently of each other.
+        let witnesses = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::from(1u128)),
+            (Witness(2), FieldElement::from(2u128)),
+            (Witness(3), FieldElement::from(3u128)),
+            (Witness(5), FieldElement::from(10u128)),
+            (Witness(6), FieldElement::from(20u128)),
+            (Witness(7), FieldElement::from(30u128)),
+        ]));
+        for opcode in circuit.opcodes {
+            let expr = opcode.arithmetic().unwrap();
+            assert_eq!(expr.evaluate(&witnesses), Ok(FieldElement::zero()));
+        }
+    }
+
+    #[test]
+    fn append_linked_circuit() {
+        let mut circuit = addition_circuit();
+        // Feed the sum computed by the first circuit into the first input of the second.
+        circuit.append_linked(addition_circuit(), &[(Witness(3), Witness(1))]);
+
+        assert_eq!(
+            circuit.private_parameters,
+            BTreeSet::from([Witness(1), Witness(2), Witness(6)])
+        );
+        assert_eq!(circuit.return_values, PublicInputs([Witness(3), Witness(7)].into()));
+        assert_eq!(
+            circuit.opcodes[1],
+            Opcode::Arithmetic(crate::native_types::Expression {
+                mul_terms: Vec::new(),
+                linear_combinations: vec![
+                    (FieldElement::one(), Witness(3)),
+                    (FieldElement::one(), Witness(6)),
+                    (-FieldElement::one(), Witness(7)),
+                ],
+                q_c: FieldElement::zero(),
+            })
+        );
+    }
+
+    #[test]
+    fn append_renumbers_memory_blocks() {
+        use super::opcodes::BlockId;
+
+        let memory_circuit = || Circuit {
+            current_witness_index: 2,
+            opcodes: vec![Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1)] }],
+            ..Circuit::default()
+        };
+        let mut circuit = memory_circuit();
+        circuit.append(memory_circuit());
+
+        assert_eq!(
+            circuit.opcodes[1],
+            Opcode::MemoryInit { block_id: BlockId(1), init: vec![Witness(3)] }
+        );
+    }
 }
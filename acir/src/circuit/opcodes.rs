@@ -1,8 +1,11 @@
+use std::collections::BTreeSet;
+
 use super::{
-    brillig::Brillig,
+    brillig::{Brillig, BrilligInputs, BrilligOutputs},
     directives::{Directive, QuotientDirective},
 };
 use crate::native_types::{Expression, Witness};
+use acir_field::FieldElement;
 use serde::{Deserialize, Serialize};
 
 mod black_box_function_call;
@@ -12,6 +15,7 @@ pub use black_box_function_call::{BlackBoxFuncCall, FunctionInput};
 pub use memory_operation::{BlockId, MemOp};
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Opcode {
     Arithmetic(Expression),
     /// Calls to "gadgets" which rely on backends implementing support for specialized constraints.
@@ -31,6 +35,13 @@ pub enum Opcode {
         block_id: BlockId,
         init: Vec<Witness>,
     },
+    /// Has no effect on solving and references no witnesses.
+    ///
+    /// Used to label regions of the circuit, e.g. with `tag` identifying the source location of the following
+    /// opcodes, so that tools can correlate opcodes back to the program they were compiled from.
+    Nop {
+        tag: u32,
+    },
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -59,6 +70,7 @@ impl Opcode {
             Opcode::Brillig(_) => "brillig",
             Opcode::MemoryOp { .. } => "mem",
             Opcode::MemoryInit { .. } => "init memory block",
+            Opcode::Nop { .. } => "nop",
         }
     }
 
@@ -73,6 +85,15 @@ impl Opcode {
         }
     }
 
+    /// Returns an [`Opcode::Arithmetic`] constraining `witness` to be either zero or one, i.e. `w * w - w == 0`.
+    pub fn boolean_constraint(witness: Witness) -> Opcode {
+        Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), witness, witness)],
+            linear_combinations: vec![(-FieldElement::one(), witness)],
+            q_c: FieldElement::zero(),
+        })
+    }
+
     pub fn is_arithmetic(&self) -> bool {
         matches!(self, Opcode::Arithmetic(_))
     }
@@ -83,6 +104,194 @@ impl Opcode {
             _ => None,
         }
     }
+
+    /// Returns the witness which the solver assigns using this opcode, given the witnesses which are already `known`.
+    ///
+    /// This is the single unknown witness of an [`Opcode::Arithmetic`]. Other opcodes, and arithmetic opcodes with
+    /// no unknowns or several, do not solve for a witness in this way.
+    pub(crate) fn solved_witness(&self, known: &BTreeSet<Witness>) -> Option<Witness> {
+        if !self.is_arithmetic() {
+            return None;
+        }
+        let witnesses = self.witnesses();
+        let mut unknowns = witnesses.difference(known);
+        match (unknowns.next(), unknowns.next()) {
+            (Some(witness), None) => Some(*witness),
+            _ => None,
+        }
+    }
+
+    /// Returns all witnesses which are referenced by this opcode.
+    pub fn witnesses(&self) -> BTreeSet<Witness> {
+        let mut witnesses = self.read_witnesses();
+        witnesses.extend(self.write_witnesses());
+        witnesses
+    }
+
+    /// Returns the witnesses whose values are required in order to execute this opcode.
+    ///
+    /// All witnesses of an [`Opcode::Arithmetic`] are treated as being read, even though the solver
+    /// may use the opcode to assign a value to a single unknown witness.
+    pub fn read_witnesses(&self) -> BTreeSet<Witness> {
+        let mut witnesses = BTreeSet::new();
+        match self {
+            Opcode::Arithmetic(expr) => insert_expression_witnesses(&mut witnesses, expr),
+            Opcode::BlackBoxFuncCall(bb_func) => {
+                witnesses.extend(bb_func.get_inputs_vec().into_iter().map(|input| input.witness));
+            }
+            Opcode::Directive(Directive::Quotient(QuotientDirective {
+                a, b, predicate, ..
+            })) => {
+                insert_expression_witnesses(&mut witnesses, a);
+                insert_expression_witnesses(&mut witnesses, b);
+                if let Some(predicate) = predicate {
+                    insert_expression_witnesses(&mut witnesses, predicate);
+                }
+            }
+            Opcode::Directive(Directive::ToLeRadix { a, .. }) => {
+                insert_expression_witnesses(&mut witnesses, a);
+            }
+            Opcode::Directive(Directive::PermutationSort { inputs, .. }) => {
+                for expr in inputs.iter().flatten() {
+                    insert_expression_witnesses(&mut witnesses, expr);
+                }
+            }
+            Opcode::Brillig(brillig) => {
+                for input in &brillig.inputs {
+                    match input {
+                        BrilligInputs::Single(expr) => {
+                            insert_expression_witnesses(&mut witnesses, expr);
+                        }
+                        BrilligInputs::Array(exprs) => {
+                            for expr in exprs {
+                                insert_expression_witnesses(&mut witnesses, expr);
+                            }
+                        }
+                    }
+                }
+                if let Some(predicate) = &brillig.predicate {
+                    insert_expression_witnesses(&mut witnesses, predicate);
+                }
+            }
+            Opcode::MemoryOp { op, predicate, .. } => {
+                insert_expression_witnesses(&mut witnesses, &op.operation);
+                insert_expression_witnesses(&mut witnesses, &op.index);
+                // The value of a read operation is written to rather than read from.
+                if !op.operation.is_zero() {
+                    insert_expression_witnesses(&mut witnesses, &op.value);
+                }
+                if let Some(predicate) = predicate {
+                    insert_expression_witnesses(&mut witnesses, predicate);
+                }
+            }
+            Opcode::MemoryInit { init, .. } => witnesses.extend(init.iter().copied()),
+            Opcode::Nop { .. } => (),
+        }
+        witnesses
+    }
+
+    /// Returns the witnesses which are assigned values when executing this opcode.
+    ///
+    /// If a [`MemOp`]'s operation is not known at compile time then its value is reported as both
+    /// being read and written.
+    pub fn write_witnesses(&self) -> BTreeSet<Witness> {
+        let mut witnesses = BTreeSet::new();
+        match self {
+            Opcode::Arithmetic(_) | Opcode::MemoryInit { .. } | Opcode::Nop { .. } => (),
+            Opcode::BlackBoxFuncCall(bb_func) => witnesses.extend(bb_func.get_outputs_vec()),
+            Opcode::Directive(Directive::Quotient(QuotientDirective { q, r, .. })) => {
+                witnesses.extend([*q, *r]);
+            }
+            Opcode::Directive(Directive::ToLeRadix { b, .. }) => {
+                witnesses.extend(b.iter().copied())
+            }
+            Opcode::Directive(Directive::PermutationSort { bits, .. }) => {
+                witnesses.extend(bits.iter().copied());
+            }
+            Opcode::Brillig(brillig) => {
+                for output in &brillig.outputs {
+                    match output {
+                        BrilligOutputs::Simple(witness) => {
+                            witnesses.insert(*witness);
+                        }
+                        BrilligOutputs::Array(outputs) => witnesses.extend(outputs.iter().copied()),
+                    }
+                }
+            }
+            Opcode::MemoryOp { op, .. } => {
+                if op.operation.to_const().map_or(true, |operation| operation.is_zero()) {
+                    insert_expression_witnesses(&mut witnesses, &op.value);
+                }
+            }
+        }
+        witnesses
+    }
+
+    /// Replaces each witness `w` referenced by this opcode with `f(w)`.
+    pub(crate) fn remap_witnesses(&mut self, f: &impl Fn(Witness) -> Witness) {
+        let remap_all = |witnesses: &mut Vec<Witness>| {
+            witnesses.iter_mut().for_each(|witness| *witness = f(*witness));
+        };
+
+        match self {
+            Opcode::Arithmetic(expr) => expr.remap_witnesses(f),
+            Opcode::BlackBoxFuncCall(bb_func) => bb_func.remap_witnesses(f),
+            Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, q, r, predicate })) => {
+                a.remap_witnesses(f);
+                b.remap_witnesses(f);
+                *q = f(*q);
+                *r = f(*r);
+                if let Some(predicate) = predicate {
+                    predicate.remap_witnesses(f);
+                }
+            }
+            Opcode::Directive(Directive::ToLeRadix { a, b, .. }) => {
+                a.remap_witnesses(f);
+                remap_all(b);
+            }
+            Opcode::Directive(Directive::PermutationSort { inputs, bits, .. }) => {
+                inputs.iter_mut().flatten().for_each(|expr| expr.remap_witnesses(f));
+                remap_all(bits);
+            }
+            Opcode::Brillig(brillig) => {
+                for input in &mut brillig.inputs {
+                    match input {
+                        BrilligInputs::Single(expr) => expr.remap_witnesses(f),
+                        BrilligInputs::Array(exprs) => {
+                            exprs.iter_mut().for_each(|expr| expr.remap_witnesses(f));
+                        }
+                    }
+                }
+                for output in &mut brillig.outputs {
+                    match output {
+                        BrilligOutputs::Simple(witness) => *witness = f(*witness),
+                        BrilligOutputs::Array(outputs) => remap_all(outputs),
+                    }
+                }
+                if let Some(predicate) = &mut brillig.predicate {
+                    predicate.remap_witnesses(f);
+                }
+            }
+            Opcode::MemoryOp { op, predicate, .. } => {
+                op.operation.remap_witnesses(f);
+                op.index.remap_witnesses(f);
+                op.value.remap_witnesses(f);
+                if let Some(predicate) = predicate {
+                    predicate.remap_witnesses(f);
+                }
+            }
+            Opcode::MemoryInit { init, .. } => remap_all(init),
+            Opcode::Nop { .. } => (),
+        }
+    }
+}
+
+fn insert_expression_witnesses(witnesses: &mut BTreeSet<Witness>, expr: &Expression) {
+    for (_, lhs, rhs) in &expr.mul_terms {
+        witnesses.insert(*lhs);
+        witnesses.insert(*rhs);
+    }
+    witnesses.extend(expr.linear_combinations.iter().map(|(_, witness)| *witness));
 }
 
 impl std::fmt::Display for Opcode {
@@ -168,6 +377,7 @@ impl std::fmt::Display for Opcode {
                 write!(f, "INIT ")?;
                 write!(f, "(id: {}, len: {}) ", block_id.0, init.len())
             }
+            Opcode::Nop { tag } => write!(f, "NOP (tag: {tag})"),
         }
     }
 }
@@ -177,3 +387,65 @@ impl std::fmt::Debug for Opcode {
         std::fmt::Display::fmt(self, f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use super::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp, Opcode};
+    use crate::native_types::Witness;
+
+    #[test]
+    fn boolean_constraint() {
+        let Opcode::Arithmetic(expr) = Opcode::boolean_constraint(Witness(3)) else {
+            panic!("expected an arithmetic opcode");
+        };
+        assert_eq!(expr.mul_terms, vec![(FieldElement::one(), Witness(3), Witness(3))]);
+        assert_eq!(expr.linear_combinations, vec![(-FieldElement::one(), Witness(3))]);
+        assert!(expr.q_c.is_zero());
+    }
+
+    #[test]
+    fn schnorr_verify_witnesses() {
+        let input = |i| FunctionInput { witness: Witness(i), num_bits: 8 };
+        let opcode = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SchnorrVerify {
+            public_key_x: input(1),
+            public_key_y: input(2),
+            signature: (3..(3 + 64)).map(input).collect(),
+            message: ((3 + 64)..(3 + 64 + 10)).map(input).collect(),
+            output: Witness(3 + 64 + 10),
+        });
+
+        let inputs: BTreeSet<Witness> = (1..(3 + 64 + 10)).map(Witness).collect();
+        assert_eq!(opcode.read_witnesses(), inputs);
+        assert_eq!(opcode.write_witnesses(), BTreeSet::from([Witness(3 + 64 + 10)]));
+        assert_eq!(opcode.witnesses().len(), 77);
+    }
+
+    #[test]
+    fn memory_op_witnesses() {
+        let init = Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] };
+        let write = Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::write_to_mem_index(FieldElement::one().into(), Witness(3).into()),
+            predicate: None,
+        };
+        let read = Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(Witness(5).into(), Witness(4)),
+            predicate: Some(Witness(6).into()),
+        };
+
+        assert_eq!(init.read_witnesses(), BTreeSet::from([Witness(1), Witness(2)]));
+        assert!(init.write_witnesses().is_empty());
+
+        assert_eq!(write.read_witnesses(), BTreeSet::from([Witness(3)]));
+        assert!(write.write_witnesses().is_empty());
+
+        assert_eq!(read.read_witnesses(), BTreeSet::from([Witness(5), Witness(6)]));
+        assert_eq!(read.write_witnesses(), BTreeSet::from([Witness(4)]));
+        assert_eq!(read.witnesses(), BTreeSet::from([Witness(4), Witness(5), Witness(6)]));
+    }
+}
@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 // Note: Some functions will not use all of the witness
 // So we need to supply how many bits of the witness is needed
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct FunctionInput {
     pub witness: Witness,
     pub num_bits: u32,
@@ -17,6 +18,7 @@ impl FunctionInput {
 }
 
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BlackBoxFuncCall {
     AND {
         lhs: FunctionInput,
@@ -112,6 +114,24 @@ pub enum BlackBoxFuncCall {
         /// will be the input aggregation object of the next recursive aggregation.
         output_aggregation_object: Vec<Witness>,
     },
+    Poseidon {
+        inputs: Vec<FunctionInput>,
+        output: Witness,
+    },
+    Sha512 {
+        inputs: Vec<FunctionInput>,
+        outputs: Vec<Witness>,
+    },
+    Sha384 {
+        inputs: Vec<FunctionInput>,
+        outputs: Vec<Witness>,
+    },
+    EddsaVerify {
+        public_key: Vec<FunctionInput>,
+        signature: Vec<FunctionInput>,
+        message: Vec<FunctionInput>,
+        output: Witness,
+    },
 }
 
 impl BlackBoxFuncCall {
@@ -176,6 +196,17 @@ impl BlackBoxFuncCall {
                 input_aggregation_object: None,
                 output_aggregation_object: vec![],
             },
+            BlackBoxFunc::Poseidon => {
+                BlackBoxFuncCall::Poseidon { inputs: vec![], output: Witness(0) }
+            }
+            BlackBoxFunc::Sha512 => BlackBoxFuncCall::Sha512 { inputs: vec![], outputs: vec![] },
+            BlackBoxFunc::Sha384 => BlackBoxFuncCall::Sha384 { inputs: vec![], outputs: vec![] },
+            BlackBoxFunc::EddsaVerify => BlackBoxFuncCall::EddsaVerify {
+                public_key: vec![],
+                signature: vec![],
+                message: vec![],
+                output: Witness(0),
+            },
         }
     }
 
@@ -195,6 +226,10 @@ impl BlackBoxFuncCall {
             BlackBoxFuncCall::Keccak256 { .. } => BlackBoxFunc::Keccak256,
             BlackBoxFuncCall::Keccak256VariableLength { .. } => BlackBoxFunc::Keccak256,
             BlackBoxFuncCall::RecursiveAggregation { .. } => BlackBoxFunc::RecursiveAggregation,
+            BlackBoxFuncCall::Poseidon { .. } => BlackBoxFunc::Poseidon,
+            BlackBoxFuncCall::Sha512 { .. } => BlackBoxFunc::Sha512,
+            BlackBoxFuncCall::Sha384 { .. } => BlackBoxFunc::Sha384,
+            BlackBoxFuncCall::EddsaVerify { .. } => BlackBoxFunc::EddsaVerify,
         }
     }
 
@@ -205,10 +240,13 @@ impl BlackBoxFuncCall {
     pub fn get_inputs_vec(&self) -> Vec<FunctionInput> {
         match self {
             BlackBoxFuncCall::SHA256 { inputs, .. }
+            | BlackBoxFuncCall::Sha512 { inputs, .. }
+            | BlackBoxFuncCall::Sha384 { inputs, .. }
             | BlackBoxFuncCall::Blake2s { inputs, .. }
             | BlackBoxFuncCall::Keccak256 { inputs, .. }
             | BlackBoxFuncCall::Pedersen { inputs, .. }
-            | BlackBoxFuncCall::HashToField128Security { inputs, .. } => inputs.to_vec(),
+            | BlackBoxFuncCall::HashToField128Security { inputs, .. }
+            | BlackBoxFuncCall::Poseidon { inputs, .. } => inputs.to_vec(),
             BlackBoxFuncCall::AND { lhs, rhs, .. } | BlackBoxFuncCall::XOR { lhs, rhs, .. } => {
                 vec![*lhs, *rhs]
             }
@@ -266,6 +304,14 @@ impl BlackBoxFuncCall {
                 inputs.extend(hashed_message.iter().copied());
                 inputs
             }
+            BlackBoxFuncCall::EddsaVerify { public_key, signature, message, .. } => {
+                let mut inputs =
+                    Vec::with_capacity(public_key.len() + signature.len() + message.len());
+                inputs.extend(public_key.iter().copied());
+                inputs.extend(signature.iter().copied());
+                inputs.extend(message.iter().copied());
+                inputs
+            }
             BlackBoxFuncCall::Keccak256VariableLength { inputs, var_message_size, .. } => {
                 let mut inputs = inputs.clone();
                 inputs.push(*var_message_size);
@@ -294,6 +340,8 @@ impl BlackBoxFuncCall {
     pub fn get_outputs_vec(&self) -> Vec<Witness> {
         match self {
             BlackBoxFuncCall::SHA256 { outputs, .. }
+            | BlackBoxFuncCall::Sha512 { outputs, .. }
+            | BlackBoxFuncCall::Sha384 { outputs, .. }
             | BlackBoxFuncCall::Blake2s { outputs, .. }
             | BlackBoxFuncCall::Keccak256 { outputs, .. }
             | BlackBoxFuncCall::RecursiveAggregation {
@@ -302,7 +350,9 @@ impl BlackBoxFuncCall {
             BlackBoxFuncCall::AND { output, .. }
             | BlackBoxFuncCall::XOR { output, .. }
             | BlackBoxFuncCall::HashToField128Security { output, .. }
+            | BlackBoxFuncCall::Poseidon { output, .. }
             | BlackBoxFuncCall::SchnorrVerify { output, .. }
+            | BlackBoxFuncCall::EddsaVerify { output, .. }
             | BlackBoxFuncCall::EcdsaSecp256k1 { output, .. }
             | BlackBoxFuncCall::EcdsaSecp256r1 { output, .. } => vec![*output],
             BlackBoxFuncCall::FixedBaseScalarMul { outputs, .. }
@@ -311,6 +361,109 @@ impl BlackBoxFuncCall {
             BlackBoxFuncCall::Keccak256VariableLength { outputs, .. } => outputs.to_vec(),
         }
     }
+
+    /// Replaces each witness `w` referenced by this call with `f(w)`.
+    pub(crate) fn remap_witnesses(&mut self, f: &impl Fn(Witness) -> Witness) {
+        let remap_input = |input: &mut FunctionInput| input.witness = f(input.witness);
+        let remap_inputs =
+            |inputs: &mut Vec<FunctionInput>| inputs.iter_mut().for_each(remap_input);
+        let remap_outputs = |outputs: &mut Vec<Witness>| {
+            outputs.iter_mut().for_each(|output| *output = f(*output));
+        };
+
+        match self {
+            BlackBoxFuncCall::AND { lhs, rhs, output }
+            | BlackBoxFuncCall::XOR { lhs, rhs, output } => {
+                remap_input(lhs);
+                remap_input(rhs);
+                *output = f(*output);
+            }
+            BlackBoxFuncCall::RANGE { input } => remap_input(input),
+            BlackBoxFuncCall::SHA256 { inputs, outputs }
+            | BlackBoxFuncCall::Sha512 { inputs, outputs }
+            | BlackBoxFuncCall::Sha384 { inputs, outputs }
+            | BlackBoxFuncCall::Blake2s { inputs, outputs }
+            | BlackBoxFuncCall::Keccak256 { inputs, outputs } => {
+                remap_inputs(inputs);
+                remap_outputs(outputs);
+            }
+            BlackBoxFuncCall::SchnorrVerify {
+                public_key_x,
+                public_key_y,
+                signature,
+                message,
+                output,
+            } => {
+                remap_input(public_key_x);
+                remap_input(public_key_y);
+                remap_inputs(signature);
+                remap_inputs(message);
+                *output = f(*output);
+            }
+            BlackBoxFuncCall::EddsaVerify { public_key, signature, message, output } => {
+                remap_inputs(public_key);
+                remap_inputs(signature);
+                remap_inputs(message);
+                *output = f(*output);
+            }
+            BlackBoxFuncCall::Pedersen { inputs, outputs, .. } => {
+                remap_inputs(inputs);
+                *outputs = (f(outputs.0), f(outputs.1));
+            }
+            BlackBoxFuncCall::HashToField128Security { inputs, output }
+            | BlackBoxFuncCall::Poseidon { inputs, output } => {
+                remap_inputs(inputs);
+                *output = f(*output);
+            }
+            BlackBoxFuncCall::EcdsaSecp256k1 {
+                public_key_x,
+                public_key_y,
+                signature,
+                hashed_message,
+                output,
+            }
+            | BlackBoxFuncCall::EcdsaSecp256r1 {
+                public_key_x,
+                public_key_y,
+                signature,
+                hashed_message,
+                output,
+            } => {
+                remap_inputs(public_key_x);
+                remap_inputs(public_key_y);
+                remap_inputs(signature);
+                remap_inputs(hashed_message);
+                *output = f(*output);
+            }
+            BlackBoxFuncCall::FixedBaseScalarMul { low, high, outputs } => {
+                remap_input(low);
+                remap_input(high);
+                *outputs = (f(outputs.0), f(outputs.1));
+            }
+            BlackBoxFuncCall::Keccak256VariableLength { inputs, var_message_size, outputs } => {
+                remap_inputs(inputs);
+                remap_input(var_message_size);
+                remap_outputs(outputs);
+            }
+            BlackBoxFuncCall::RecursiveAggregation {
+                verification_key,
+                proof,
+                public_inputs,
+                key_hash,
+                input_aggregation_object,
+                output_aggregation_object,
+            } => {
+                remap_inputs(verification_key);
+                remap_inputs(proof);
+                remap_inputs(public_inputs);
+                remap_input(key_hash);
+                if let Some(input_aggregation_object) = input_aggregation_object {
+                    remap_inputs(input_aggregation_object);
+                }
+                remap_outputs(output_aggregation_object);
+            }
+        }
+    }
 }
 
 const ABBREVIATION_LIMIT: usize = 5;
@@ -1,12 +1,26 @@
 use crate::native_types::{Expression, Witness};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Hash, Copy, Default)]
+#[derive(
+    Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash, Copy, Default, Debug,
+)]
 pub struct BlockId(pub u32);
 
+/// The largest block id generated by [`arbitrary::Arbitrary`].
+#[cfg(feature = "arbitrary")]
+const MAX_ARBITRARY_BLOCK_ID: u32 = 16;
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for BlockId {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(BlockId(u.int_in_range(0..=MAX_ARBITRARY_BLOCK_ID)?))
+    }
+}
+
 /// Operation on a block of memory
 /// We can either write or read at an index in memory
 #[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct MemOp {
     /// Can be 0 (read) or 1 (write)
     pub operation: Expression,
@@ -17,11 +31,16 @@ pub struct MemOp {
 impl MemOp {
     /// Creates a `MemOp` which reads from memory at `index` and inserts the read value
     /// into the [`WitnessMap`][crate::native_types::WitnessMap] at `witness`
+    ///
+    /// `index` may be any expression, e.g. `w1 + w2` for dynamic array indexing, in which case it is evaluated
+    /// using the witness assignments when the operation is solved.
     pub fn read_at_mem_index(index: Expression, witness: Witness) -> Self {
         MemOp { operation: Expression::zero(), index, value: witness.into() }
     }
 
     /// Creates a `MemOp` which writes the [`Expression`] `value` into memory at `index`.
+    ///
+    /// As with [`MemOp::read_at_mem_index`], `index` may be any expression over the circuit's witnesses.
     pub fn write_to_mem_index(index: Expression, value: Expression) -> Self {
         MemOp { operation: Expression::one(), index, value }
     }
@@ -0,0 +1,238 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use thiserror::Error;
+
+use super::{opcodes::BlockId, Circuit, Opcode, OpcodeLocation};
+use crate::native_types::Witness;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("opcodes {opcode_indices:?} cannot be ordered as they depend upon each other's outputs")]
+pub struct CyclicDependency {
+    /// The indices of the opcodes which could not be ordered, in the original circuit.
+    pub opcode_indices: Vec<usize>,
+}
+
+impl Circuit {
+    /// Reorders the circuit's opcodes so that every opcode is preceded by the opcodes which produce its inputs.
+    ///
+    /// The circuit's parameters are treated as being known from the start. An [`Opcode::Arithmetic`] is treated as
+    /// producing its single unknown witness once all of its other witnesses are known, as the solver does.
+    /// Opcodes acting on the same memory block keep their relative order, and otherwise the original order is kept
+    /// wherever possible. Assert messages are moved along with their opcodes.
+    ///
+    /// Returns an error if some opcodes can never be executed because they are waiting on each other's outputs,
+    /// in which case the circuit is left unmodified.
+    pub fn topological_sort(&mut self) -> Result<(), CyclicDependency> {
+        let order = self
+            .dependency_order(|index| index)
+            .map_err(|(_, remaining)| CyclicDependency { opcode_indices: remaining })?;
+        self.reorder_opcodes(order);
+        Ok(())
+    }
+
+    /// Returns an ordering of the opcodes' indices in which every opcode is preceded by the opcodes which produce
+    /// its inputs, choosing the ready opcode with the smallest `key` at each step.
+    ///
+    /// If some opcodes cannot be ordered then the opcodes which were ordered are returned along with the indices
+    /// of those which could not be, in their original order.
+    pub(super) fn dependency_order<K: Ord>(
+        &self,
+        key: impl Fn(usize) -> K,
+    ) -> Result<Vec<usize>, (Vec<usize>, Vec<usize>)> {
+        let mut known: BTreeSet<Witness> = self.circuit_arguments();
+
+        // The number of unknown inputs of each opcode, and the opcodes waiting on each unknown witness.
+        let mut unknown_counts = Vec::with_capacity(self.opcodes.len());
+        let mut dependents: HashMap<Witness, Vec<usize>> = HashMap::new();
+        // The opcodes acting on each memory block which have not been ordered, in their original order.
+        let mut block_opcodes: HashMap<BlockId, VecDeque<usize>> = HashMap::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            let unknowns = unknown_inputs(opcode, &known);
+            for witness in &unknowns {
+                dependents.entry(*witness).or_default().push(index);
+            }
+            unknown_counts.push(unknowns.len());
+            if let Some(block_id) = memory_block(opcode) {
+                block_opcodes.entry(block_id).or_default().push_back(index);
+            }
+        }
+
+        let is_ready = |index: usize,
+                        unknown_count: usize,
+                        block_opcodes: &HashMap<BlockId, VecDeque<usize>>| {
+            let opcode = &self.opcodes[index];
+            unknown_count <= max_unknown_inputs(opcode)
+                && memory_block(opcode)
+                    .map_or(true, |block_id| block_opcodes[&block_id].front() == Some(&index))
+        };
+
+        // Opcodes are queued once they become ready, which they then remain until they are ordered.
+        let mut queued = vec![false; self.opcodes.len()];
+        let mut ready: BTreeSet<(K, usize)> = BTreeSet::new();
+        for index in 0..self.opcodes.len() {
+            if is_ready(index, unknown_counts[index], &block_opcodes) {
+                queued[index] = true;
+                ready.insert((key(index), index));
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.opcodes.len());
+        while let Some((_, index)) = ready.pop_first() {
+            order.push(index);
+
+            let opcode = &self.opcodes[index];
+            let mut candidates = Vec::new();
+            if let Some(block_id) = memory_block(opcode) {
+                let opcodes = block_opcodes.get_mut(&block_id).expect("block was recorded above");
+                opcodes.pop_front();
+                candidates.extend(opcodes.front().copied());
+            }
+            for witness in opcode.witnesses() {
+                if known.insert(witness) {
+                    for &dependent in dependents.get(&witness).into_iter().flatten() {
+                        unknown_counts[dependent] -= 1;
+                        candidates.push(dependent);
+                    }
+                }
+            }
+
+            for candidate in candidates {
+                if !queued[candidate]
+                    && is_ready(candidate, unknown_counts[candidate], &block_opcodes)
+                {
+                    queued[candidate] = true;
+                    ready.insert((key(candidate), candidate));
+                }
+            }
+        }
+
+        if order.len() < self.opcodes.len() {
+            let remaining = (0..self.opcodes.len()).filter(|index| !queued[*index]).collect();
+            return Err((order, remaining));
+        }
+        Ok(order)
+    }
+
+    /// Rearranges the opcodes so that the opcode at `order[i]` is moved to index `i`, moving assert messages along
+    /// with their opcodes.
+    pub(super) fn reorder_opcodes(&mut self, order: Vec<usize>) {
+        let new_indices: BTreeMap<usize, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(new_index, old_index)| (*old_index, new_index))
+            .collect();
+        for (location, _) in &mut self.assert_messages {
+            match location {
+                OpcodeLocation::Acir(index) | OpcodeLocation::Brillig { acir_index: index, .. } => {
+                    *index = new_indices[index];
+                }
+            }
+        }
+
+        let mut opcodes: Vec<Option<Opcode>> =
+            std::mem::take(&mut self.opcodes).into_iter().map(Some).collect();
+        self.opcodes = order
+            .into_iter()
+            .map(|index| opcodes[index].take().expect("each opcode is ordered once"))
+            .collect();
+    }
+}
+
+/// Returns the witnesses which must be known before `opcode` can be executed, excluding those in `known`.
+fn unknown_inputs(opcode: &Opcode, known: &BTreeSet<Witness>) -> Vec<Witness> {
+    let writes = opcode.write_witnesses();
+    opcode
+        .read_witnesses()
+        .into_iter()
+        .filter(|witness| !writes.contains(witness) && !known.contains(witness))
+        .collect()
+}
+
+/// Returns the memory block which `opcode` acts on, if any.
+fn memory_block(opcode: &Opcode) -> Option<BlockId> {
+    match opcode {
+        Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } => Some(*block_id),
+        _ => None,
+    }
+}
+
+/// An arithmetic opcode can be used to solve for a single unknown witness.
+fn max_unknown_inputs(opcode: &Opcode) -> usize {
+    usize::from(opcode.is_arithmetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use super::CyclicDependency;
+    use crate::{
+        circuit::{
+            opcodes::{BlockId, MemOp},
+            test_utils::addition,
+            Circuit, Opcode, OpcodeLocation, PublicInputs,
+        },
+        native_types::Witness,
+    };
+
+    #[test]
+    fn reorders_shuffled_addition_circuit() {
+        // w3 = w1 + w2, w4 = w3 + w1, w5 = w4 + w3, listed in reverse.
+        let mut circuit = Circuit {
+            current_witness_index: 6,
+            opcodes: vec![addition(4, 3, 5), addition(3, 1, 4), addition(1, 2, 3)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs([Witness(5)].into()),
+            assert_messages: vec![(OpcodeLocation::Acir(2), "first".to_owned())],
+            ..Circuit::default()
+        };
+
+        circuit.topological_sort().unwrap();
+
+        assert_eq!(circuit.opcodes, vec![addition(1, 2, 3), addition(3, 1, 4), addition(4, 3, 5)]);
+        assert_eq!(circuit.get_assert_message(OpcodeLocation::Acir(0)), Some("first"));
+    }
+
+    #[test]
+    fn preserves_memory_order() {
+        let init = Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1)] };
+        let write = Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::write_to_mem_index(FieldElement::zero().into(), Witness(3).into()),
+            predicate: None,
+        };
+        let read = Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(FieldElement::zero().into(), Witness(4)),
+            predicate: None,
+        };
+        let mut circuit = Circuit {
+            opcodes: vec![init.clone(), write.clone(), read.clone(), addition(1, 2, 3)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            ..Circuit::default()
+        };
+
+        // The read has all of its inputs but must stay after the write, which waits on `w3`.
+        circuit.topological_sort().unwrap();
+        assert_eq!(circuit.opcodes, vec![init, addition(1, 2, 3), write, read]);
+    }
+
+    #[test]
+    fn reports_cyclic_dependencies() {
+        // Each opcode has two unknowns until the other has been solved.
+        let mut circuit = Circuit {
+            opcodes: vec![addition(1, 2, 3), addition(1, 3, 2), addition(1, 1, 4)],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+        let original = circuit.clone();
+
+        assert_eq!(
+            circuit.topological_sort(),
+            Err(CyclicDependency { opcode_indices: vec![0, 1] })
+        );
+        assert_eq!(circuit, original);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::{Circuit, Opcode, OpcodeLocation, PublicInputs};
+use crate::native_types::Witness;
+
+impl Circuit {
+    /// Splits the circuit into sub-circuits which share no witnesses and so can be solved independently.
+    ///
+    /// Two opcodes are placed in the same sub-circuit if they reference a common witness or act on the same
+    /// memory block. Sub-circuits are returned in order of their first opcode, and each keeps the relative order
+    /// of its opcodes along with their assert messages. Parameters and return values are split between the
+    /// sub-circuits whose opcodes reference them; those which are not referenced by any opcode are dropped.
+    pub fn partition_independent(&self) -> Vec<Circuit> {
+        let mut components = DisjointSet::new(self.opcodes.len());
+        let mut witness_owners: HashMap<Witness, usize> = HashMap::new();
+        let mut block_owners = HashMap::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            for witness in opcode.witnesses() {
+                let owner = *witness_owners.entry(witness).or_insert(index);
+                components.union(owner, index);
+            }
+            if let Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } = opcode
+            {
+                let owner = *block_owners.entry(*block_id).or_insert(index);
+                components.union(owner, index);
+            }
+        }
+
+        // Maps the representative of each component to its position in the returned vector.
+        let mut partition_indices: HashMap<usize, usize> = HashMap::new();
+        // The new index of each opcode within its partition, alongside the partition's position.
+        let mut opcode_locations = Vec::with_capacity(self.opcodes.len());
+        let mut partitions: Vec<Circuit> = Vec::new();
+        for (index, opcode) in self.opcodes.iter().enumerate() {
+            let partition_index =
+                *partition_indices.entry(components.find(index)).or_insert_with(|| {
+                    partitions.push(Circuit {
+                        current_witness_index: self.current_witness_index,
+                        ..Circuit::default()
+                    });
+                    partitions.len() - 1
+                });
+            let partition = &mut partitions[partition_index];
+            opcode_locations.push((partition_index, partition.opcodes.len()));
+            partition.opcodes.push(opcode.clone());
+        }
+
+        for partition in &mut partitions {
+            let witnesses: BTreeSet<Witness> =
+                partition.opcodes.iter().flat_map(Opcode::witnesses).collect();
+            let restrict = |parameters: &BTreeSet<Witness>| -> BTreeSet<Witness> {
+                parameters.intersection(&witnesses).copied().collect()
+            };
+            partition.private_parameters = restrict(&self.private_parameters);
+            partition.public_parameters = PublicInputs(restrict(&self.public_parameters.0));
+            partition.return_values = PublicInputs(restrict(&self.return_values.0));
+        }
+
+        for (location, message) in &self.assert_messages {
+            let (OpcodeLocation::Acir(index) | OpcodeLocation::Brillig { acir_index: index, .. }) =
+                location;
+            let Some(&(partition_index, new_index)) = opcode_locations.get(*index) else {
+                continue;
+            };
+            let new_location = match *location {
+                OpcodeLocation::Acir(_) => OpcodeLocation::Acir(new_index),
+                OpcodeLocation::Brillig { brillig_index, .. } => {
+                    OpcodeLocation::Brillig { acir_index: new_index, brillig_index }
+                }
+            };
+            partitions[partition_index].assert_messages.push((new_location, message.clone()));
+        }
+
+        partitions
+    }
+}
+
+/// A union-find structure over the indices `0..len`.
+struct DisjointSet {
+    parents: Vec<usize>,
+}
+
+impl DisjointSet {
+    fn new(len: usize) -> Self {
+        DisjointSet { parents: (0..len).collect() }
+    }
+
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parents[a.max(b)] = a.min(b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use crate::{
+        circuit::{test_utils::addition, Circuit, OpcodeLocation, PublicInputs},
+        native_types::Witness,
+    };
+
+    #[test]
+    fn splits_disjoint_additions() {
+        let circuit = Circuit {
+            current_witness_index: 6,
+            opcodes: vec![addition(1, 2, 3), addition(4, 5, 6)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(4), Witness(5)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(3), Witness(6)])),
+            assert_messages: vec![(OpcodeLocation::Acir(1), "second".to_owned())],
+            ..Circuit::default()
+        };
+
+        let partitions = circuit.partition_independent();
+
+        assert_eq!(
+            partitions,
+            vec![
+                Circuit {
+                    current_witness_index: 6,
+                    opcodes: vec![addition(1, 2, 3)],
+                    private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+                    return_values: PublicInputs(BTreeSet::from([Witness(3)])),
+                    ..Circuit::default()
+                },
+                Circuit {
+                    current_witness_index: 6,
+                    opcodes: vec![addition(4, 5, 6)],
+                    private_parameters: BTreeSet::from([Witness(4), Witness(5)]),
+                    return_values: PublicInputs(BTreeSet::from([Witness(6)])),
+                    assert_messages: vec![(OpcodeLocation::Acir(0), "second".to_owned())],
+                    ..Circuit::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_connected_opcodes_together() {
+        // The second addition consumes the output of the first.
+        let circuit = Circuit {
+            opcodes: vec![addition(1, 2, 3), addition(4, 5, 6), addition(3, 1, 7)],
+            ..Circuit::default()
+        };
+
+        let partitions = circuit.partition_independent();
+        let opcodes: Vec<_> = partitions.into_iter().map(|partition| partition.opcodes).collect();
+        assert_eq!(
+            opcodes,
+            vec![vec![addition(1, 2, 3), addition(3, 1, 7)], vec![addition(4, 5, 6)]]
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Renders circuits in an assembly-like syntax which is easier to read than the [`Debug`] output.
+
+use std::fmt::{Result, Write};
+
+use acir_field::FieldElement;
+
+use super::{
+    brillig::{Brillig, BrilligInputs, BrilligOutputs},
+    directives::{Directive, QuotientDirective},
+    opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+    Circuit, Opcode,
+};
+use crate::native_types::{Expression, Witness};
+
+impl Circuit {
+    /// Renders the circuit with each opcode on its own line in an assembly-like syntax.
+    ///
+    /// Witnesses are written as `w<index>`, e.g. an addition gate is rendered as `w3 = w1 + w2`.
+    pub fn to_pretty_string(&self) -> String {
+        let mut output = String::new();
+        self.write_pretty(&mut output).expect("writing to a `String` cannot fail");
+        output
+    }
+
+    fn write_pretty(&self, f: &mut String) -> Result {
+        writeln!(f, "current witness index: {}", witness(Witness(self.current_witness_index)))?;
+        writeln!(f, "private parameters: [{}]", witnesses(&self.private_parameters))?;
+        writeln!(f, "public parameters: [{}]", witnesses(&self.public_parameters.0))?;
+        writeln!(f, "return values: [{}]", witnesses(&self.return_values.0))?;
+
+        for opcode in &self.opcodes {
+            write_opcode(f, opcode)?;
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn witness(witness: Witness) -> String {
+    format!("w{}", witness.witness_index())
+}
+
+fn witnesses<'a>(witnesses: impl IntoIterator<Item = &'a Witness>) -> String {
+    witnesses.into_iter().map(|w| witness(*w)).collect::<Vec<_>>().join(", ")
+}
+
+fn expressions<'a>(exprs: impl IntoIterator<Item = &'a Expression>) -> String {
+    exprs.into_iter().map(expression).collect::<Vec<_>>().join(", ")
+}
+
+/// Renders `expr` as a sum of terms, e.g. `2*w1*w2 + w3 - 5`.
+fn expression(expr: &Expression) -> String {
+    let mut terms: Vec<(FieldElement, String)> = Vec::new();
+    for (q_m, w_l, w_r) in &expr.mul_terms {
+        terms.push((*q_m, format!("{}*{}", witness(*w_l), witness(*w_r))));
+    }
+    for (q_l, w_l) in &expr.linear_combinations {
+        terms.push((*q_l, witness(*w_l)));
+    }
+
+    let mut output = String::new();
+    for (coefficient, term) in terms {
+        let (is_negative, magnitude) =
+            if coefficient.is_negative() { (true, -coefficient) } else { (false, coefficient) };
+        match (output.is_empty(), is_negative) {
+            (true, true) => output.push('-'),
+            (true, false) => (),
+            (false, true) => output.push_str(" - "),
+            (false, false) => output.push_str(" + "),
+        }
+        if !magnitude.is_one() {
+            output.push_str(&format!("{magnitude}*"));
+        }
+        output.push_str(&term);
+    }
+
+    if output.is_empty() {
+        output = expr.q_c.to_string();
+    } else if !expr.q_c.is_zero() {
+        let q_c = expr.q_c.to_string();
+        match q_c.strip_prefix('-') {
+            Some(magnitude) => output.push_str(&format!(" - {magnitude}")),
+            None => output.push_str(&format!(" + {q_c}")),
+        }
+    }
+    output
+}
+
+fn function_input(input: &FunctionInput) -> String {
+    if input.num_bits < FieldElement::max_num_bits() {
+        format!("{}:u{}", witness(input.witness), input.num_bits)
+    } else {
+        witness(input.witness)
+    }
+}
+
+fn block(block_id: &BlockId) -> String {
+    format!("MEM[block{}]", block_id.0)
+}
+
+fn write_opcode(f: &mut String, opcode: &Opcode) -> Result {
+    match opcode {
+        Opcode::Arithmetic(expr) => write_arithmetic(f, expr),
+        Opcode::BlackBoxFuncCall(bb_func) => write_black_box_func_call(f, bb_func),
+        Opcode::Directive(directive) => write_directive(f, directive),
+        Opcode::Brillig(brillig) => write_brillig(f, brillig),
+        Opcode::MemoryOp { block_id, op, predicate } => {
+            let MemOp { operation, index, value } = op;
+            let location = format!("{}[{}]", block(block_id), expression(index));
+            if operation.is_zero() {
+                write!(f, "{} = {location}", expression(value))?;
+            } else if operation.to_const().is_some() {
+                write!(f, "{location} = {}", expression(value))?;
+            } else {
+                write!(
+                    f,
+                    "{location} <-> {} (write if {})",
+                    expression(value),
+                    expression(operation)
+                )?;
+            }
+            write_predicate(f, predicate)
+        }
+        Opcode::MemoryInit { block_id, init } => {
+            write!(f, "INIT {} = [{}]", block(block_id), witnesses(init))
+        }
+        Opcode::Nop { tag } => write!(f, "NOP {tag}"),
+    }
+}
+
+/// Writes `expr == 0` as an assignment to one of its witnesses where possible.
+fn write_arithmetic(f: &mut String, expr: &Expression) -> Result {
+    let assigned = expr.linear_combinations.iter().rev().find(|(coefficient, witness)| {
+        (coefficient.is_one() || (-*coefficient).is_one())
+            && !expr.mul_terms.iter().any(|(_, w_l, w_r)| w_l == witness || w_r == witness)
+    });
+
+    match assigned {
+        Some((coefficient, assigned)) => {
+            let rest = expr - &(*coefficient * &Expression::from(*assigned));
+            // `rest + coefficient * assigned == 0` implies `assigned == -rest / coefficient`.
+            let value = if coefficient.is_one() { -&rest } else { rest };
+            write!(f, "{} = {}", witness(*assigned), expression(&value))
+        }
+        None => write!(f, "0 = {}", expression(expr)),
+    }
+}
+
+fn write_black_box_func_call(f: &mut String, bb_func: &BlackBoxFuncCall) -> Result {
+    let inputs: Vec<_> = bb_func.get_inputs_vec().iter().map(function_input).collect();
+    write!(f, "{}({})", bb_func.name().to_uppercase(), inputs.join(", "))?;
+
+    let outputs = bb_func.get_outputs_vec();
+    match outputs.as_slice() {
+        [] => Ok(()),
+        [output] => write!(f, " -> {}", witness(*output)),
+        outputs => write!(f, " -> [{}]", witnesses(outputs)),
+    }
+}
+
+fn write_directive(f: &mut String, directive: &Directive) -> Result {
+    match directive {
+        Directive::Quotient(QuotientDirective { a, b, q, r, predicate }) => {
+            write!(
+                f,
+                "{}, {} = QUOTIENT({}, {})",
+                witness(*q),
+                witness(*r),
+                expression(a),
+                expression(b)
+            )?;
+            write_predicate(f, predicate)
+        }
+        Directive::ToLeRadix { a, b, radix } => {
+            write!(f, "[{}] = TO_LE_RADIX({}, {radix})", witnesses(b), expression(a))
+        }
+        Directive::PermutationSort { inputs, tuple, bits, sort_by } => {
+            let inputs: Vec<_> =
+                inputs.iter().map(|tuple| format!("[{}]", expressions(tuple))).collect();
+            write!(
+                f,
+                "[{}] = PERMUTATION_SORT([{}], tuple: {tuple}, sort_by: {sort_by:?})",
+                witnesses(bits),
+                inputs.join(", ")
+            )
+        }
+    }
+}
+
+fn write_brillig(f: &mut String, brillig: &Brillig) -> Result {
+    let outputs: Vec<_> = brillig
+        .outputs
+        .iter()
+        .map(|output| match output {
+            BrilligOutputs::Simple(output) => witness(*output),
+            BrilligOutputs::Array(outputs) => format!("[{}]", witnesses(outputs)),
+        })
+        .collect();
+    let inputs: Vec<_> = brillig
+        .inputs
+        .iter()
+        .map(|input| match input {
+            BrilligInputs::Single(input) => expression(input),
+            BrilligInputs::Array(inputs) => format!("[{}]", expressions(inputs)),
+        })
+        .collect();
+
+    write!(
+        f,
+        "[{}] = BRILLIG({}) {{ {} opcodes }}",
+        outputs.join(", "),
+        inputs.join(", "),
+        brillig.bytecode.len()
+    )?;
+    write_predicate(f, &brillig.predicate)
+}
+
+fn write_predicate(f: &mut String, predicate: &Option<Expression>) -> Result {
+    match predicate {
+        Some(predicate) => write!(f, " if {}", expression(predicate)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    #[test]
+    fn addition_circuit() {
+        let addition = Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(3)),
+            ],
+            q_c: FieldElement::zero(),
+        });
+
+        let circuit = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![addition],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs([Witness(3)].into()),
+            ..Circuit::default()
+        };
+
+        let expected = "\
+current witness index: w4
+private parameters: [w1, w2]
+public parameters: []
+return values: [w3]
+w3 = w1 + w2
+";
+        assert_eq!(circuit.to_pretty_string(), expected);
+    }
+
+    #[test]
+    fn renders_each_opcode_kind() {
+        let circuit = Circuit {
+            current_witness_index: 5,
+            opcodes: vec![
+                Opcode::Arithmetic(Expression {
+                    mul_terms: vec![(FieldElement::from(2u128), Witness(1), Witness(2))],
+                    linear_combinations: vec![(FieldElement::one(), Witness(3))],
+                    q_c: -FieldElement::from(5u128),
+                }),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+                    lhs: FunctionInput { witness: Witness(1), num_bits: 8 },
+                    rhs: FunctionInput { witness: Witness(2), num_bits: 8 },
+                    output: Witness(3),
+                }),
+                Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::write_to_mem_index(FieldElement::one().into(), Witness(3).into()),
+                    predicate: None,
+                },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(Witness(5).into(), Witness(4)),
+                    predicate: None,
+                },
+            ],
+            ..Circuit::default()
+        };
+
+        let opcodes: Vec<_> =
+            circuit.to_pretty_string().lines().skip(4).map(String::from).collect();
+        assert_eq!(
+            opcodes,
+            vec![
+                "w3 = -2*w1*w2 + 5",
+                "AND(w1:u8, w2:u8) -> w3",
+                "INIT MEM[block0] = [w1, w2]",
+                "MEM[block0][1] = w3",
+                "w4 = MEM[block0][w5]",
+            ]
+        );
+    }
+}
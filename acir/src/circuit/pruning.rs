@@ -0,0 +1,171 @@
+use std::collections::BTreeSet;
+
+use super::{Circuit, Opcode};
+use crate::native_types::Witness;
+
+impl Circuit {
+    /// Removes opcodes which do not contribute to the circuit's public inputs or return values.
+    ///
+    /// Liveness is propagated backwards from the public parameters and return values: an opcode is live if it
+    /// writes to a live witness, in which case all of the witnesses it references become live.
+    /// An [`Opcode::Arithmetic`] writes to its single unknown witness when it is reached in order, as in the solver,
+    /// and is only removed if that witness is dead. Arithmetic opcodes which do not solve for a witness are assertions
+    /// and are always kept.
+    /// Black box calls, Brillig calls (which may make foreign calls) and memory opcodes are conservatively
+    /// treated as always being live. [`Opcode::Nop`]s are kept as they may label the opcodes around them.
+    ///
+    /// Assert messages of removed opcodes are dropped.
+    pub fn prune_dead_opcodes(&mut self) {
+        let mut live_witnesses: BTreeSet<Witness> =
+            self.public_parameters.0.union(&self.return_values.0).copied().collect();
+        let mut live_opcodes = vec![false; self.opcodes.len()];
+
+        let mut known_witnesses = self.circuit_arguments();
+        let arithmetic_outputs: Vec<Option<Witness>> = self
+            .opcodes
+            .iter()
+            .map(|opcode| {
+                let output = opcode.solved_witness(&known_witnesses);
+                known_witnesses.extend(opcode.witnesses());
+                output
+            })
+            .collect();
+
+        // Opcodes are not necessarily in dependency order so iterate until no new opcodes become live.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, opcode) in self.opcodes.iter().enumerate().rev() {
+                if live_opcodes[index]
+                    || !is_live(opcode, arithmetic_outputs[index], &live_witnesses)
+                {
+                    continue;
+                }
+                live_opcodes[index] = true;
+                live_witnesses.extend(opcode.witnesses());
+                changed = true;
+            }
+        }
+
+        let mut new_indices = Vec::with_capacity(self.opcodes.len());
+        let mut next_index = 0;
+        for is_live in &live_opcodes {
+            new_indices.push(is_live.then_some(next_index));
+            next_index += usize::from(*is_live);
+        }
+
+        self.remap_assert_messages(&new_indices);
+
+        let mut live_opcodes = live_opcodes.into_iter();
+        self.opcodes.retain(|_| live_opcodes.next().expect("one flag per opcode"));
+    }
+}
+
+fn is_live(
+    opcode: &Opcode,
+    arithmetic_output: Option<Witness>,
+    live_witnesses: &BTreeSet<Witness>,
+) -> bool {
+    match opcode {
+        Opcode::BlackBoxFuncCall(_)
+        | Opcode::Brillig(_)
+        | Opcode::MemoryOp { .. }
+        | Opcode::MemoryInit { .. }
+        | Opcode::Nop { .. } => true,
+        Opcode::Arithmetic(_) => match arithmetic_output {
+            Some(output) => live_witnesses.contains(&output),
+            None => true,
+        },
+        Opcode::Directive(_) => {
+            opcode.write_witnesses().iter().any(|witness| live_witnesses.contains(witness))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+
+    use crate::{
+        circuit::{
+            directives::{Directive, QuotientDirective},
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            test_utils::addition,
+            Circuit, Opcode, OpcodeLocation, PublicInputs,
+        },
+        native_types::{Expression, Witness},
+    };
+
+    #[test]
+    fn removes_dangling_arithmetic_opcode() {
+        let mut circuit = Circuit {
+            current_witness_index: 5,
+            // `w4` is never used by anything which reaches the return value `w5`.
+            opcodes: vec![addition(1, 2, 3), addition(1, 2, 4), addition(3, 1, 5)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs([Witness(5)].into()),
+            assert_messages: vec![
+                (OpcodeLocation::Acir(1), "dead".to_owned()),
+                (OpcodeLocation::Acir(2), "live".to_owned()),
+            ],
+            ..Circuit::default()
+        };
+
+        circuit.prune_dead_opcodes();
+
+        assert_eq!(circuit.opcodes, vec![addition(1, 2, 3), addition(3, 1, 5)]);
+        assert_eq!(circuit.assert_messages, vec![(OpcodeLocation::Acir(1), "live".to_owned())]);
+    }
+
+    #[test]
+    fn keeps_assertions_on_private_witnesses() {
+        // w1 * w2 - 15 == 0 constrains the private inputs without producing any witness.
+        let assertion = Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: Vec::new(),
+            q_c: -FieldElement::from(15_u128),
+        });
+        let mut circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![assertion.clone()],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            ..Circuit::default()
+        };
+
+        circuit.prune_dead_opcodes();
+        assert_eq!(circuit.opcodes, vec![assertion]);
+    }
+
+    #[test]
+    fn keeps_opcodes_with_side_effects() {
+        let range = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(4), num_bits: 8 },
+        });
+        let quotient = Opcode::Directive(Directive::Quotient(QuotientDirective {
+            a: Witness(1).into(),
+            b: Witness(2).into(),
+            q: Witness(3),
+            r: Witness(4),
+            predicate: None,
+        }));
+        let dead_quotient = Opcode::Directive(Directive::Quotient(QuotientDirective {
+            a: Witness(1).into(),
+            b: Witness(2).into(),
+            q: Witness(5),
+            r: Witness(6),
+            predicate: None,
+        }));
+        let mut circuit = Circuit {
+            current_witness_index: 6,
+            opcodes: vec![quotient.clone(), dead_quotient, range.clone()],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            ..Circuit::default()
+        };
+
+        // The range constraint is always live and so keeps alive the directive computing its input.
+        circuit.prune_dead_opcodes();
+        assert_eq!(circuit.opcodes, vec![quotient, range]);
+    }
+}
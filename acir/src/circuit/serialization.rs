@@ -0,0 +1,747 @@
+use std::collections::BTreeSet;
+use std::io::{Read, Write};
+
+use base64::Engine;
+use flate2::Compression;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::{Circuit, Opcode, PublicInputs};
+use crate::native_types::Witness;
+
+/// Magic bytes which prefix every [`Circuit`] serialized by [`Circuit::write`].
+pub const ACIR_MAGIC: [u8; 4] = *b"ACIR";
+
+/// Magic bytes which prefix every [`Circuit`] serialized by [`Circuit::write_uncompressed`].
+pub const ACIR_UNCOMPRESSED_MAGIC: [u8; 4] = *b"ACIU";
+
+/// Version of the serialization format which is written by [`Circuit::write`].
+///
+/// This must be bumped whenever the serialized representation of a [`Circuit`] changes.
+pub const ACIR_SERIALIZATION_VERSION: u32 = 7;
+
+#[derive(Debug, Error)]
+pub enum SerializationError {
+    #[error("serialized circuit does not start with an ACIR header, it was likely produced by an older version of ACIR")]
+    MissingHeader,
+    #[error("unsupported ACIR serialization version: found {found}, expected {expected}")]
+    UnsupportedVersion { found: u32, expected: u32 },
+    #[error(
+        "serialized circuit is uncompressed, it must be read with `Circuit::read_uncompressed`"
+    )]
+    Uncompressed,
+    #[error("serialized circuit is compressed, it must be read with `Circuit::read`")]
+    Compressed,
+}
+
+/// Number of bytes of the checksum appended by [`Circuit::write_with_checksum`].
+pub const CHECKSUM_LENGTH: usize = 8;
+
+/// Returns the checksum of `bytes` used by [`Circuit::write_with_checksum`], a truncated SHA-256 hash.
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let hash = Sha256::digest(bytes);
+    hash[..CHECKSUM_LENGTH].try_into().expect("SHA-256 hashes are longer than the checksum")
+}
+
+#[cfg(feature = "serialize-messagepack")]
+type DecodeError = rmp_serde::decode::Error;
+#[cfg(not(feature = "serialize-messagepack"))]
+type DecodeError = bincode::Error;
+
+#[derive(Debug, Error)]
+pub enum CircuitReadError {
+    #[error(transparent)]
+    Header(#[from] SerializationError),
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[error("failed to decompress circuit: {0}")]
+    Gzip(std::io::Error),
+    #[error("failed to decode circuit after consuming {consumed_bytes} bytes: {source}")]
+    Bincode { consumed_bytes: usize, source: DecodeError },
+    #[error("serialized circuit has {remaining} bytes of trailing data")]
+    TrailingData { remaining: usize },
+    #[error("serialized circuit does not match its checksum, it is likely truncated or corrupted")]
+    ChecksumMismatch,
+    #[error("invalid base64 encoding: {0}")]
+    Base64(base64::DecodeError),
+    #[error("invalid hex encoding: {0}")]
+    Hex(hex::FromHexError),
+}
+
+/// Writes the uncompressed header which precedes the serialized circuit.
+fn write_header<W: Write>(writer: &mut W, magic: [u8; 4]) -> std::io::Result<()> {
+    writer.write_all(&magic)?;
+    writer.write_all(&ACIR_SERIALIZATION_VERSION.to_le_bytes())
+}
+
+/// Reads and validates the header which precedes the serialized circuit,
+/// leaving `reader` positioned at the start of the serialized circuit.
+fn read_header<R: Read>(reader: &mut R, expected_magic: [u8; 4]) -> Result<(), CircuitReadError> {
+    let mut magic = [0u8; 4];
+    match reader.read_exact(&mut magic) {
+        Ok(()) => {}
+        Err(err) if err.kind() == std::io::ErrorKind::UnexpectedEof => {
+            return Err(SerializationError::MissingHeader.into())
+        }
+        Err(err) => return Err(CircuitReadError::Io(err)),
+    }
+    if magic != expected_magic {
+        return Err(match magic {
+            ACIR_MAGIC => SerializationError::Compressed,
+            ACIR_UNCOMPRESSED_MAGIC => SerializationError::Uncompressed,
+            _ => SerializationError::MissingHeader,
+        }
+        .into());
+    }
+
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version).map_err(CircuitReadError::Io)?;
+    let found = u32::from_le_bytes(version);
+    if found != ACIR_SERIALIZATION_VERSION {
+        return Err(SerializationError::UnsupportedVersion {
+            found,
+            expected: ACIR_SERIALIZATION_VERSION,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// A [`Write`] sink which counts the bytes written to it.
+#[derive(Default)]
+struct ByteCounter(usize);
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0 += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A [`Read`] adapter which counts the bytes read through it.
+struct ReadCounter<R> {
+    inner: R,
+    count: usize,
+}
+
+impl<R: Read> Read for ReadCounter<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read;
+        Ok(read)
+    }
+}
+
+/// Highest gzip compression level accepted by [`Circuit::write_with_level`].
+pub const MAX_COMPRESSION_LEVEL: u32 = 9;
+
+#[cfg(feature = "serialize-messagepack")]
+const DEFAULT_COMPRESSION_LEVEL: u32 = 9;
+#[cfg(not(feature = "serialize-messagepack"))]
+const DEFAULT_COMPRESSION_LEVEL: u32 = 6;
+
+fn compression_from_level(level: u32) -> std::io::Result<Compression> {
+    if level > MAX_COMPRESSION_LEVEL {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid compression level {level}, expected a value between 0 and {MAX_COMPRESSION_LEVEL}"),
+        ));
+    }
+    Ok(Compression::new(level))
+}
+
+#[cfg(feature = "serialize-messagepack")]
+fn encode(circuit: &Circuit) -> Vec<u8> {
+    rmp_serde::to_vec(circuit).unwrap()
+}
+#[cfg(feature = "serialize-messagepack")]
+fn decode_from<R: Read>(reader: R) -> Result<Circuit, DecodeError> {
+    rmp_serde::from_read(reader)
+}
+/// Extracts the underlying I/O error from `err` unless it was caused by the stream ending early.
+#[cfg(feature = "serialize-messagepack")]
+fn into_io_error(err: DecodeError) -> Result<std::io::Error, DecodeError> {
+    match err {
+        rmp_serde::decode::Error::InvalidMarkerRead(err)
+        | rmp_serde::decode::Error::InvalidDataRead(err)
+            if err.kind() != std::io::ErrorKind::UnexpectedEof =>
+        {
+            Ok(err)
+        }
+        err => Err(err),
+    }
+}
+
+#[cfg(not(feature = "serialize-messagepack"))]
+fn encode(circuit: &Circuit) -> Vec<u8> {
+    bincode::serialize(circuit).unwrap()
+}
+#[cfg(not(feature = "serialize-messagepack"))]
+fn decode_from<R: Read>(reader: R) -> Result<Circuit, DecodeError> {
+    bincode::deserialize_from(reader)
+}
+/// Extracts the underlying I/O error from `err` unless it was caused by the stream ending early.
+#[cfg(not(feature = "serialize-messagepack"))]
+fn into_io_error(err: DecodeError) -> Result<std::io::Error, DecodeError> {
+    match *err {
+        bincode::ErrorKind::Io(err) if err.kind() != std::io::ErrorKind::UnexpectedEof => Ok(err),
+        _ => Err(err),
+    }
+}
+
+/// Decodes a circuit from `reader`, which must contain nothing after the encoded circuit.
+///
+/// Failures of `reader` itself are reported using `io_error`, as opposed to the stream ending
+/// before a full circuit could be decoded.
+fn decode_circuit<R: Read>(
+    reader: R,
+    io_error: fn(std::io::Error) -> CircuitReadError,
+) -> Result<Circuit, CircuitReadError> {
+    let mut reader = ReadCounter { inner: reader, count: 0 };
+    let circuit = decode_from(&mut reader).map_err(|err| match into_io_error(err) {
+        Ok(err) => io_error(err),
+        Err(source) => CircuitReadError::Bincode { consumed_bytes: reader.count, source },
+    })?;
+
+    let mut trailing_data = Vec::new();
+    reader.inner.read_to_end(&mut trailing_data).map_err(io_error)?;
+    if !trailing_data.is_empty() {
+        return Err(CircuitReadError::TrailingData { remaining: trailing_data.len() });
+    }
+    Ok(circuit)
+}
+
+impl Circuit {
+    pub fn write<W: Write>(&self, writer: W) -> std::io::Result<()> {
+        self.write_with_level(writer, DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    /// Serializes the circuit as [`Circuit::write`] does, but compressing with the provided level.
+    ///
+    /// `level` must be between 0 (no compression) and [`MAX_COMPRESSION_LEVEL`] (best compression).
+    /// No matching option is needed when reading the circuit back with [`Circuit::read`].
+    #[cfg(feature = "serialize-messagepack")]
+    pub fn write_with_level<W: Write>(&self, mut writer: W, level: u32) -> std::io::Result<()> {
+        let compression = compression_from_level(level)?;
+        write_header(&mut writer, ACIR_MAGIC)?;
+        let buf = encode(self);
+        let mut deflater = flate2::write::DeflateEncoder::new(writer, compression);
+        deflater.write_all(&buf)?;
+        deflater.finish()?;
+        Ok(())
+    }
+    #[cfg(feature = "serialize-messagepack")]
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, CircuitReadError> {
+        read_header(&mut reader, ACIR_MAGIC)?;
+        decode_circuit(flate2::read::DeflateDecoder::new(reader), CircuitReadError::Gzip)
+    }
+
+    /// Serializes the circuit as [`Circuit::write`] does, but compressing with the provided level.
+    ///
+    /// `level` must be between 0 (no compression) and [`MAX_COMPRESSION_LEVEL`] (best compression).
+    /// No matching option is needed when reading the circuit back with [`Circuit::read`].
+    #[cfg(not(feature = "serialize-messagepack"))]
+    pub fn write_with_level<W: Write>(&self, mut writer: W, level: u32) -> std::io::Result<()> {
+        let compression = compression_from_level(level)?;
+        write_header(&mut writer, ACIR_MAGIC)?;
+        let buf = encode(self);
+        let mut encoder = flate2::write::GzEncoder::new(writer, compression);
+        encoder.write_all(&buf)?;
+        encoder.finish()?;
+        Ok(())
+    }
+
+    /// Deserializes a circuit which was serialized using [`Circuit::write`].
+    ///
+    /// The circuit is decompressed and decoded directly from `reader` so any [`Read`] implementation, such as a
+    /// file or socket, may be used without first buffering its contents. Passing a `&[u8]` reads from a slice.
+    #[cfg(not(feature = "serialize-messagepack"))]
+    pub fn read<R: Read>(mut reader: R) -> Result<Self, CircuitReadError> {
+        read_header(&mut reader, ACIR_MAGIC)?;
+        decode_circuit(flate2::read::GzDecoder::new(reader), CircuitReadError::Gzip)
+    }
+
+    /// Serializes the circuit without compressing it.
+    ///
+    /// This avoids the cost of compression when the output is stored in an already compressed container
+    /// or memory-mapped. The output uses a distinct header from [`Circuit::write`] and must be read back
+    /// with [`Circuit::read_uncompressed`].
+    pub fn write_uncompressed<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        write_header(&mut writer, ACIR_UNCOMPRESSED_MAGIC)?;
+        writer.write_all(&encode(self))
+    }
+
+    /// Deserializes a circuit which was serialized using [`Circuit::write_uncompressed`].
+    pub fn read_uncompressed<R: Read>(mut reader: R) -> Result<Self, CircuitReadError> {
+        read_header(&mut reader, ACIR_UNCOMPRESSED_MAGIC)?;
+        decode_circuit(reader, CircuitReadError::Io)
+    }
+
+    /// Serializes the circuit as [`Circuit::write`] does, followed by a [`CHECKSUM_LENGTH`] byte checksum of
+    /// the serialized circuit.
+    ///
+    /// This allows corrupted or truncated artifacts to be detected by [`Circuit::read_with_checksum`] before
+    /// attempting to decompress and decode them.
+    pub fn write_with_checksum<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        let bytes = self.to_bytes();
+        writer.write_all(&bytes)?;
+        writer.write_all(&checksum(&bytes))
+    }
+
+    /// Deserializes a circuit which was serialized using [`Circuit::write_with_checksum`].
+    ///
+    /// The checksum is verified before the circuit is decoded, returning [`CircuitReadError::ChecksumMismatch`]
+    /// if it does not match. As the checksum follows the circuit, `reader` is read to its end.
+    ///
+    /// The mismatch is a top-level [`CircuitReadError`] rather than a [`SerializationError`], as the latter only
+    /// describes invalid headers and is wrapped in [`CircuitReadError::Header`]. Matching on
+    /// `CircuitReadError::Header(_)` therefore does not catch corrupted payloads.
+    pub fn read_with_checksum<R: Read>(mut reader: R) -> Result<Self, CircuitReadError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).map_err(CircuitReadError::Io)?;
+        let Some(payload_length) = bytes.len().checked_sub(CHECKSUM_LENGTH) else {
+            return Err(CircuitReadError::ChecksumMismatch);
+        };
+        let (payload, expected) = bytes.split_at(payload_length);
+        if checksum(payload) != expected {
+            return Err(CircuitReadError::ChecksumMismatch);
+        }
+        Self::read(payload)
+    }
+
+    /// Returns the exact number of bytes which [`Circuit::write`] would output for this circuit.
+    ///
+    /// The circuit is encoded through the same pipeline as [`Circuit::write`] but the output is
+    /// discarded rather than being buffered.
+    pub fn serialized_size(&self) -> std::io::Result<usize> {
+        let mut counter = ByteCounter::default();
+        self.write(&mut counter)?;
+        Ok(counter.0)
+    }
+
+    /// Serializes the circuit using [`Circuit::write`] and encodes the result as standard base64.
+    pub fn to_base64(&self) -> String {
+        base64::engine::general_purpose::STANDARD.encode(self.to_bytes())
+    }
+
+    /// Deserializes a circuit using [`Circuit::read`] from the standard base64 encoding produced by [`Circuit::to_base64`].
+    pub fn from_base64(encoded: &str) -> Result<Self, CircuitReadError> {
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_err(CircuitReadError::Base64)?;
+        Self::read(bytes.as_slice())
+    }
+
+    /// Serializes the circuit using [`Circuit::write`] and encodes the result as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.to_bytes())
+    }
+
+    /// Deserializes a circuit using [`Circuit::read`] from the hex encoding produced by [`Circuit::to_hex`].
+    pub fn from_hex(encoded: &str) -> Result<Self, CircuitReadError> {
+        let bytes = hex::decode(encoded).map_err(CircuitReadError::Hex)?;
+        Self::read(bytes.as_slice())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write(&mut bytes).expect("writing to a `Vec` cannot fail");
+        bytes
+    }
+
+    /// Serializes the circuit into a human-readable JSON value.
+    ///
+    /// Unlike [`Circuit::write`], the output is neither compressed nor binary encoded which makes it
+    /// suitable for inspecting and diffing circuits. As with [`Circuit::write`], assert messages are not included.
+    #[cfg(feature = "serde_json")]
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::to_value(self).expect("circuit should always be representable as JSON")
+    }
+
+    /// Deserializes a circuit from a JSON value produced by [`Circuit::to_json`].
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    /// Serializes the circuit as [CBOR](https://www.rfc-editor.org/rfc/rfc8949), a self-describing binary format
+    /// which can be parsed from other languages without knowledge of the bincode layout used by [`Circuit::write`].
+    ///
+    /// Field elements are encoded as 32 byte big-endian byte strings. As with [`Circuit::write`], assert messages
+    /// are not included.
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::ser::into_writer(self, &mut bytes).expect("writing to a `Vec` cannot fail");
+        bytes
+    }
+
+    /// Deserializes a circuit from the CBOR produced by [`Circuit::to_cbor`].
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, ciborium::de::Error<std::io::Error>> {
+        ciborium::de::from_reader(bytes)
+    }
+}
+
+/// The parameters and return values of a circuit read by a [`CircuitStreamReader`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CircuitParameters {
+    pub private_parameters: BTreeSet<Witness>,
+    pub public_parameters: PublicInputs,
+    pub return_values: PublicInputs,
+}
+
+/// Reads a circuit serialized by [`Circuit::write`] one opcode at a time, without holding all of its opcodes in memory.
+///
+/// The current witness index, the circuit's parameters and the number of opcodes are available as soon as the
+/// reader is created, after which the opcodes are yielded in order by iterating over the reader.
+///
+/// ```
+/// # use acir::circuit::{Circuit, CircuitStreamReader};
+/// # let mut bytes = Vec::new();
+/// # Circuit::default().write(&mut bytes).unwrap();
+/// let mut reader = CircuitStreamReader::new(bytes.as_slice()).unwrap();
+/// println!("{:?}", reader.parameters().private_parameters);
+/// for opcode in &mut reader {
+///     println!("{}", opcode.unwrap());
+/// }
+/// reader.finish().unwrap();
+/// ```
+#[cfg(not(feature = "serialize-messagepack"))]
+pub struct CircuitStreamReader<R: Read> {
+    reader: ReadCounter<flate2::read::GzDecoder<R>>,
+    current_witness_index: u32,
+    parameters: CircuitParameters,
+    num_opcodes: u64,
+    remaining_opcodes: u64,
+}
+
+#[cfg(not(feature = "serialize-messagepack"))]
+impl<R: Read> CircuitStreamReader<R> {
+    /// Reads the header and parameters of a circuit serialized by [`Circuit::write`], leaving its opcodes to be
+    /// streamed.
+    pub fn new(mut reader: R) -> Result<Self, CircuitReadError> {
+        read_header(&mut reader, ACIR_MAGIC)?;
+        let reader = ReadCounter { inner: flate2::read::GzDecoder::new(reader), count: 0 };
+        let mut stream = CircuitStreamReader {
+            reader,
+            current_witness_index: 0,
+            parameters: CircuitParameters::default(),
+            num_opcodes: 0,
+            remaining_opcodes: 0,
+        };
+
+        // These are the leading fields of the encoded `Circuit`, with its opcodes being prefixed by their number.
+        stream.current_witness_index = stream.decode()?;
+        stream.parameters = CircuitParameters {
+            private_parameters: stream.decode()?,
+            public_parameters: stream.decode()?,
+            return_values: stream.decode()?,
+        };
+        stream.num_opcodes = stream.decode()?;
+        stream.remaining_opcodes = stream.num_opcodes;
+        Ok(stream)
+    }
+
+    pub fn current_witness_index(&self) -> u32 {
+        self.current_witness_index
+    }
+
+    /// Returns the circuit's parameters and return values.
+    pub fn parameters(&self) -> &CircuitParameters {
+        &self.parameters
+    }
+
+    /// Returns the total number of opcodes in the circuit, including those which have already been read.
+    pub fn num_opcodes(&self) -> u64 {
+        self.num_opcodes
+    }
+
+    /// Skips over any opcodes which have not been read, returning the circuit's parameters and return values.
+    ///
+    /// Returns an error if the stream contains any data after the encoded circuit.
+    pub fn finish(mut self) -> Result<CircuitParameters, CircuitReadError> {
+        while self.remaining_opcodes > 0 {
+            self.remaining_opcodes -= 1;
+            self.decode::<Opcode>()?;
+        }
+
+        let mut trailing_data = Vec::new();
+        self.reader.inner.read_to_end(&mut trailing_data).map_err(CircuitReadError::Gzip)?;
+        if !trailing_data.is_empty() {
+            return Err(CircuitReadError::TrailingData { remaining: trailing_data.len() });
+        }
+        Ok(self.parameters)
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&mut self) -> Result<T, CircuitReadError> {
+        bincode::deserialize_from(&mut self.reader).map_err(|err| match into_io_error(err) {
+            Ok(err) => CircuitReadError::Gzip(err),
+            Err(source) => CircuitReadError::Bincode { consumed_bytes: self.reader.count, source },
+        })
+    }
+}
+
+#[cfg(not(feature = "serialize-messagepack"))]
+impl<R: Read> Iterator for CircuitStreamReader<R> {
+    type Item = Result<Opcode, CircuitReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining_opcodes == 0 {
+            return None;
+        }
+        self.remaining_opcodes -= 1;
+
+        let opcode = self.decode();
+        if opcode.is_err() {
+            // The position of the following opcode is unknown so no more opcodes can be read.
+            self.remaining_opcodes = 0;
+        }
+        Some(opcode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::{
+        CircuitParameters, CircuitReadError, CircuitStreamReader, SerializationError, ACIR_MAGIC,
+        ACIR_SERIALIZATION_VERSION,
+    };
+    use crate::circuit::{Circuit, Opcode, PublicInputs};
+    use crate::native_types::{Expression, Witness};
+    use acir_field::FieldElement;
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor_encodes_field_elements_as_bytes() {
+        let circuit = Circuit {
+            opcodes: vec![Opcode::Arithmetic(Expression::from(FieldElement::from(0x0102_u128)))],
+            ..Circuit::default()
+        };
+
+        let cbor = circuit.to_cbor();
+        let mut expected_bytes = [0u8; 32];
+        expected_bytes[30..].copy_from_slice(&[1, 2]);
+        // A 32 byte string is prefixed by the header bytes 0x58 0x20.
+        assert!(cbor
+            .windows(34)
+            .any(|window| window[..2] == [0x58, 0x20] && window[2..] == expected_bytes));
+        assert_eq!(Circuit::from_cbor(&cbor).unwrap(), circuit);
+    }
+
+    #[test]
+    fn header_is_written_uncompressed() {
+        let mut bytes = Vec::new();
+        Circuit::default().write(&mut bytes).unwrap();
+
+        assert_eq!(bytes[..4], ACIR_MAGIC);
+        assert_eq!(bytes[4..8], ACIR_SERIALIZATION_VERSION.to_le_bytes());
+    }
+
+    #[test]
+    fn reads_circuits_written_at_any_compression_level() {
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Arithmetic(Expression {
+                mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+                linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+                q_c: FieldElement::zero(),
+            })],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(3)])),
+            ..Circuit::default()
+        };
+
+        let mut fast = Vec::new();
+        circuit.write_with_level(&mut fast, 1).unwrap();
+        let mut best = Vec::new();
+        circuit.write_with_level(&mut best, 9).unwrap();
+
+        assert_eq!(Circuit::read(fast.as_slice()).unwrap(), circuit);
+        assert_eq!(Circuit::read(best.as_slice()).unwrap(), circuit);
+    }
+
+    #[test]
+    fn rejects_invalid_compression_level() {
+        let err = Circuit::default().write_with_level(Vec::new(), 10).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn rejects_unsupported_version() {
+        let mut bytes = Vec::new();
+        Circuit::default().write(&mut bytes).unwrap();
+        bytes[4..8].copy_from_slice(&(ACIR_SERIALIZATION_VERSION + 1).to_le_bytes());
+
+        let err = Circuit::read(bytes.as_slice()).unwrap_err();
+        assert!(matches!(
+            err,
+            CircuitReadError::Header(SerializationError::UnsupportedVersion { found, expected })
+                if found == ACIR_SERIALIZATION_VERSION + 1 && expected == ACIR_SERIALIZATION_VERSION
+        ));
+    }
+
+    #[test]
+    fn rejects_mismatched_compression() {
+        let mut compressed = Vec::new();
+        Circuit::default().write(&mut compressed).unwrap();
+        let mut uncompressed = Vec::new();
+        Circuit::default().write_uncompressed(&mut uncompressed).unwrap();
+
+        let err = Circuit::read(uncompressed.as_slice()).unwrap_err();
+        assert!(matches!(err, CircuitReadError::Header(SerializationError::Uncompressed)));
+        let err = Circuit::read_uncompressed(compressed.as_slice()).unwrap_err();
+        assert!(matches!(err, CircuitReadError::Header(SerializationError::Compressed)));
+    }
+
+    #[test]
+    fn checksum_detects_corruption() {
+        let circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![Opcode::Arithmetic(Expression::from(Witness(1)))],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+        let mut bytes = Vec::new();
+        circuit.write_with_checksum(&mut bytes).unwrap();
+        assert_eq!(Circuit::read_with_checksum(bytes.as_slice()).unwrap(), circuit);
+
+        for index in [0, bytes.len() / 2, bytes.len() - 1] {
+            let mut corrupted = bytes.clone();
+            corrupted[index] ^= 1;
+            let err = Circuit::read_with_checksum(corrupted.as_slice()).unwrap_err();
+            assert!(matches!(err, CircuitReadError::ChecksumMismatch));
+        }
+        let err = Circuit::read_with_checksum(&bytes[..bytes.len() - 1]).unwrap_err();
+        assert!(matches!(err, CircuitReadError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn rejects_trailing_data() {
+        let mut bytes = Vec::new();
+        Circuit::default().write_uncompressed(&mut bytes).unwrap();
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        let err = Circuit::read_uncompressed(bytes.as_slice()).unwrap_err();
+        assert!(matches!(err, CircuitReadError::TrailingData { remaining: 3 }));
+    }
+
+    #[test]
+    fn stream_reader_skips_unread_opcodes() {
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![
+                Opcode::Arithmetic(Expression::from(Witness(1))),
+                Opcode::Arithmetic(Expression::from(Witness(2))),
+            ],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            public_parameters: PublicInputs(BTreeSet::from([Witness(2)])),
+            return_values: PublicInputs(BTreeSet::from([Witness(3)])),
+            ..Circuit::default()
+        };
+        let mut bytes = Vec::new();
+        circuit.write(&mut bytes).unwrap();
+
+        let mut reader = CircuitStreamReader::new(bytes.as_slice()).unwrap();
+        let parameters = CircuitParameters {
+            private_parameters: circuit.private_parameters,
+            public_parameters: circuit.public_parameters,
+            return_values: circuit.return_values,
+        };
+        assert_eq!(reader.current_witness_index(), 3);
+        assert_eq!(reader.parameters(), &parameters);
+        assert_eq!(reader.num_opcodes(), 2);
+        assert_eq!(reader.next().unwrap().unwrap(), circuit.opcodes[0]);
+
+        assert_eq!(reader.finish().unwrap(), parameters);
+    }
+
+    #[test]
+    fn text_encodings_round_trip() {
+        let circuit = Circuit {
+            current_witness_index: 2,
+            opcodes: vec![Opcode::Arithmetic(Expression::from(Witness(1)))],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+
+        assert_eq!(Circuit::from_base64(&circuit.to_base64()).unwrap(), circuit);
+        assert_eq!(Circuit::from_hex(&circuit.to_hex()).unwrap(), circuit);
+
+        assert!(matches!(Circuit::from_base64("not base64!"), Err(CircuitReadError::Base64(_))));
+        assert!(matches!(Circuit::from_hex("abc"), Err(CircuitReadError::Hex(_))));
+    }
+
+    #[test]
+    fn rejects_missing_header() {
+        let mut bytes = Vec::new();
+        Circuit::default().write(&mut bytes).unwrap();
+
+        // Circuits serialized before the header was introduced consist of only the gzip stream.
+        let err = Circuit::read(&bytes[8..]).unwrap_err();
+        assert!(matches!(err, CircuitReadError::Header(SerializationError::MissingHeader)));
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn json_roundtrip() {
+        use crate::circuit::{
+            brillig::{Brillig, BrilligInputs, BrilligOutputs},
+            directives::{Directive, QuotientDirective},
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+        };
+
+        let circuit = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![
+                Opcode::Arithmetic(Expression {
+                    mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+                    linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+                    q_c: FieldElement::from(8u128),
+                }),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+                    lhs: FunctionInput { witness: Witness(1), num_bits: 4 },
+                    rhs: FunctionInput { witness: Witness(2), num_bits: 4 },
+                    output: Witness(3),
+                }),
+                Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                    input: FunctionInput { witness: Witness(1), num_bits: 8 },
+                }),
+                Opcode::Directive(Directive::Quotient(QuotientDirective {
+                    a: Witness(1).into(),
+                    b: Witness(2).into(),
+                    q: Witness(3),
+                    r: Witness(4),
+                    predicate: None,
+                })),
+                Opcode::Brillig(Brillig {
+                    inputs: vec![BrilligInputs::Single(Witness(1).into())],
+                    outputs: vec![BrilligOutputs::Simple(Witness(2))],
+                    foreign_call_results: vec![],
+                    bytecode: vec![brillig::Opcode::Stop],
+                    trap_messages: vec![],
+                    predicate: None,
+                }),
+                Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(FieldElement::zero().into(), Witness(3)),
+                    predicate: None,
+                },
+            ],
+            private_parameters: BTreeSet::from_iter(vec![Witness(1), Witness(2)]),
+            public_parameters: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+            return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(4)])),
+            assert_messages: Default::default(),
+        };
+
+        let json = circuit.to_json();
+        assert_eq!(Circuit::from_json(json).unwrap(), circuit);
+    }
+}
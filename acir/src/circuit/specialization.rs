@@ -0,0 +1,179 @@
+use std::collections::BTreeSet;
+
+use acir_field::FieldElement;
+
+use super::{
+    brillig::BrilligInputs,
+    directives::{Directive, QuotientDirective},
+    Circuit, Opcode,
+};
+use crate::native_types::{Expression, Witness, WitnessMap};
+
+impl Circuit {
+    /// Specializes the circuit to the constant values of the witnesses in `assignments`.
+    ///
+    /// Each assigned witness is substituted into every expression which the circuit's opcodes read from, and
+    /// the resulting constants are folded. Arithmetic opcodes which are reduced to `0 == 0` are removed along
+    /// with their assert messages. The assigned witnesses are then removed from the private parameters.
+    ///
+    /// Some opcodes reference witnesses directly rather than through an expression, such as black box function
+    /// inputs and memory initializations. If an assigned witness is still referenced after substitution, or is a
+    /// public input, then its value is baked into the circuit by an arithmetic opcode constraining it to that
+    /// value, which is placed at the start of the circuit so that it is solved before it is used.
+    pub fn specialize(&mut self, assignments: &WitnessMap) {
+        for opcode in &mut self.opcodes {
+            substitute_expressions(opcode, assignments);
+        }
+
+        let mut referenced: BTreeSet<Witness> =
+            self.opcodes.iter().flat_map(Opcode::witnesses).collect();
+        referenced.extend(self.public_inputs().0);
+        let pinned: Vec<Opcode> = assignments
+            .iter_sorted()
+            .filter(|(witness, _)| referenced.contains(witness))
+            .map(|(witness, value)| {
+                let mut pin = Expression::from_field(-*value);
+                pin.push_addition_term(FieldElement::one(), witness);
+                Opcode::Arithmetic(pin)
+            })
+            .collect();
+
+        let is_trivial =
+            |opcode: &Opcode| matches!(opcode, Opcode::Arithmetic(expr) if expr.is_zero());
+        let mut new_indices = Vec::with_capacity(self.opcodes.len());
+        let mut next_index = pinned.len();
+        for opcode in &self.opcodes {
+            let keep = !is_trivial(opcode);
+            new_indices.push(keep.then_some(next_index));
+            next_index += usize::from(keep);
+        }
+        self.remap_assert_messages(&new_indices);
+
+        self.opcodes.retain(|opcode| !is_trivial(opcode));
+        self.opcodes.splice(0..0, pinned);
+        self.private_parameters.retain(|witness| !assignments.contains_key(witness));
+    }
+}
+
+/// Substitutes `assignments` into the expressions which `opcode` reads from.
+///
+/// The value of a memory operation is only substituted for writes, as a read assigns it.
+fn substitute_expressions(opcode: &mut Opcode, assignments: &WitnessMap) {
+    let substitute = |expr: &mut Expression| expr.substitute(assignments);
+    match opcode {
+        Opcode::Arithmetic(expr) => substitute(expr),
+        Opcode::Directive(Directive::Quotient(QuotientDirective { a, b, predicate, .. })) => {
+            substitute(a);
+            substitute(b);
+            predicate.iter_mut().for_each(substitute);
+        }
+        Opcode::Directive(Directive::ToLeRadix { a, .. }) => substitute(a),
+        Opcode::Directive(Directive::PermutationSort { inputs, .. }) => {
+            inputs.iter_mut().flatten().for_each(substitute);
+        }
+        Opcode::Brillig(brillig) => {
+            for input in &mut brillig.inputs {
+                match input {
+                    BrilligInputs::Single(expr) => substitute(expr),
+                    BrilligInputs::Array(exprs) => exprs.iter_mut().for_each(substitute),
+                }
+            }
+            brillig.predicate.iter_mut().for_each(substitute);
+        }
+        Opcode::MemoryOp { op, predicate, .. } => {
+            substitute(&mut op.operation);
+            substitute(&mut op.index);
+            if op.operation.to_const() == Some(FieldElement::one()) {
+                substitute(&mut op.value);
+            }
+            predicate.iter_mut().for_each(substitute);
+        }
+        Opcode::BlackBoxFuncCall(_) | Opcode::MemoryInit { .. } | Opcode::Nop { .. } => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use acir_field::FieldElement;
+
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, FunctionInput},
+            test_utils::addition_circuit,
+            Circuit, Opcode, OpcodeLocation,
+        },
+        native_types::{Expression, Witness, WitnessMap},
+    };
+
+    #[test]
+    fn folds_constants_and_drops_trivial_opcodes() {
+        // w1 * w2 - w3 == 0, w1 - 2 == 0
+        let mut product = Expression::default();
+        product.push_multiplication_term(FieldElement::one(), Witness(1), Witness(2));
+        product.push_addition_term(-FieldElement::one(), Witness(3));
+        let check = Expression::from(Witness(1)) - FieldElement::from(2u128);
+        let mut circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![Opcode::Arithmetic(check), Opcode::Arithmetic(product)],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            assert_messages: vec![
+                (OpcodeLocation::Acir(0), "check".to_owned()),
+                (OpcodeLocation::Acir(1), "product".to_owned()),
+            ],
+            ..Circuit::default()
+        };
+
+        circuit.specialize(&WitnessMap::from(BTreeMap::from([(
+            Witness(1),
+            FieldElement::from(2u128),
+        )])));
+
+        let mut expected = Expression::default();
+        expected.push_addition_term(FieldElement::from(2u128), Witness(2));
+        expected.push_addition_term(-FieldElement::one(), Witness(3));
+        assert_eq!(circuit.opcodes, vec![Opcode::Arithmetic(expected)]);
+        assert_eq!(circuit.private_parameters, BTreeSet::from([Witness(2)]));
+        assert_eq!(circuit.assert_messages, vec![(OpcodeLocation::Acir(0), "product".to_owned())]);
+    }
+
+    #[test]
+    fn pins_witnesses_referenced_by_black_box_calls() {
+        let range = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(1), num_bits: 8 },
+        });
+        let mut circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![range.clone()],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+
+        circuit.specialize(&WitnessMap::from(BTreeMap::from([(
+            Witness(1),
+            FieldElement::from(5u128),
+        )])));
+
+        let pin = Expression::from(Witness(1)) - FieldElement::from(5u128);
+        assert_eq!(circuit.opcodes, vec![Opcode::Arithmetic(pin), range]);
+        assert!(circuit.private_parameters.is_empty());
+    }
+
+    #[test]
+    fn specialize_addition_input() {
+        let mut circuit = addition_circuit();
+        circuit.specialize(&WitnessMap::from(BTreeMap::from([(
+            Witness(1),
+            FieldElement::from(2u128),
+        )])));
+
+        assert_eq!(circuit.required_inputs(), BTreeSet::from([Witness(2)]));
+        assert_eq!(circuit.producers_of(Witness(3)), vec![0]);
+        let solution = WitnessMap::from(BTreeMap::from([
+            (Witness(2), FieldElement::from(3u128)),
+            (Witness(3), FieldElement::from(5u128)),
+        ]));
+        assert_eq!(circuit.check_witness(&solution), Vec::new());
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::{opcodes::BlockId, Circuit, Opcode};
+use crate::BlackBoxFunc;
+
+/// Breakdown of the opcodes which make up a [`Circuit`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeStats {
+    pub arithmetic: usize,
+    pub black_box_func_calls: usize,
+    pub directives: usize,
+    pub brillig: usize,
+    pub memory_ops: usize,
+    pub memory_inits: usize,
+    pub nops: usize,
+    /// Number of calls to each black box function.
+    ///
+    /// Black box functions vary greatly in cost so these are counted separately.
+    pub black_box_funcs: BTreeMap<BlackBoxFunc, usize>,
+    /// Number of distinct witnesses referenced by the circuit's opcodes, parameters and return values.
+    pub num_witnesses: usize,
+}
+
+impl OpcodeStats {
+    /// Returns the total number of opcodes.
+    pub fn num_opcodes(&self) -> usize {
+        self.arithmetic
+            + self.black_box_func_calls
+            + self.directives
+            + self.brillig
+            + self.memory_ops
+            + self.memory_inits
+            + self.nops
+    }
+}
+
+/// Summary of the blocks of memory initialized by a [`Circuit`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of distinct memory blocks.
+    pub num_blocks: usize,
+    /// Number of cells in the largest memory block.
+    pub max_block_size: usize,
+    /// Total number of cells across all memory blocks.
+    pub total_cells: usize,
+}
+
+/// Summary of the use of a single block of memory, as returned by [`Circuit::memory_blocks`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BlockInfo {
+    /// Number of cells in the block's largest initialization, or `None` if the block is never initialized.
+    pub size: Option<usize>,
+    /// Number of [`Opcode::MemoryOp`] reads from the block.
+    pub num_reads: usize,
+    /// Number of [`Opcode::MemoryOp`] writes to the block.
+    pub num_writes: usize,
+}
+
+/// Estimates the cost of proving each opcode, allowing backends to supply their own heuristics to
+/// [`Circuit::total_cost`].
+///
+/// Costs are in arbitrary units, such as a backend's gate count.
+pub trait CostModel {
+    fn cost(&self, opcode: &Opcode) -> u64;
+}
+
+/// A [`CostModel`] which assigns every opcode a cost of one, so that the total cost of a circuit is
+/// its [number of opcodes][OpcodeStats::num_opcodes].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultCostModel;
+
+impl CostModel for DefaultCostModel {
+    fn cost(&self, _opcode: &Opcode) -> u64 {
+        1
+    }
+}
+
+impl Circuit {
+    /// Sums the cost of each of the circuit's opcodes under `model`.
+    pub fn total_cost(&self, model: &dyn CostModel) -> u64 {
+        self.opcodes.iter().map(|opcode| model.cost(opcode)).sum()
+    }
+
+    /// Counts the opcodes of each kind in the circuit.
+    pub fn opcode_stats(&self) -> OpcodeStats {
+        let mut stats = OpcodeStats::default();
+        let mut witnesses: BTreeSet<_> = self.circuit_arguments();
+        witnesses.extend(self.return_values.0.iter().copied());
+
+        for opcode in &self.opcodes {
+            match opcode {
+                Opcode::Arithmetic(_) => stats.arithmetic += 1,
+                Opcode::BlackBoxFuncCall(bb_func) => {
+                    stats.black_box_func_calls += 1;
+                    *stats.black_box_funcs.entry(bb_func.get_black_box_func()).or_default() += 1;
+                }
+                Opcode::Directive(_) => stats.directives += 1,
+                Opcode::Brillig(_) => stats.brillig += 1,
+                Opcode::MemoryOp { .. } => stats.memory_ops += 1,
+                Opcode::MemoryInit { .. } => stats.memory_inits += 1,
+                Opcode::Nop { .. } => stats.nops += 1,
+            }
+            witnesses.extend(opcode.witnesses());
+        }
+
+        stats.num_witnesses = witnesses.len();
+        stats
+    }
+
+    /// Summarizes the sizes of the memory blocks initialized by the circuit's [`Opcode::MemoryInit`] opcodes.
+    ///
+    /// If a block is initialized more than once then its largest initialization is used.
+    pub fn memory_stats(&self) -> MemoryStats {
+        let mut block_sizes: HashMap<BlockId, usize> = HashMap::new();
+        for opcode in &self.opcodes {
+            if let Opcode::MemoryInit { block_id, init } = opcode {
+                let size = block_sizes.entry(*block_id).or_default();
+                *size = (*size).max(init.len());
+            }
+        }
+
+        MemoryStats {
+            num_blocks: block_sizes.len(),
+            max_block_size: block_sizes.values().copied().max().unwrap_or(0),
+            total_cells: block_sizes.values().sum(),
+        }
+    }
+
+    /// Summarizes each block of memory referenced by the circuit's memory opcodes.
+    ///
+    /// Blocks which are accessed without being initialized are included with a `size` of `None`.
+    /// Memory operations are counted as reads or writes if their operation is the constant 0 or 1 respectively;
+    /// operations whose kind is only known at solving time are not counted.
+    pub fn memory_blocks(&self) -> BTreeMap<BlockId, BlockInfo> {
+        let mut blocks: BTreeMap<BlockId, BlockInfo> = BTreeMap::new();
+        for opcode in &self.opcodes {
+            match opcode {
+                Opcode::MemoryInit { block_id, init } => {
+                    let info = blocks.entry(*block_id).or_default();
+                    info.size = Some(info.size.unwrap_or(0).max(init.len()));
+                }
+                Opcode::MemoryOp { block_id, op, .. } => {
+                    let info = blocks.entry(*block_id).or_default();
+                    match op.operation.to_const() {
+                        Some(operation) if operation.is_zero() => info.num_reads += 1,
+                        Some(operation) if operation.is_one() => info.num_writes += 1,
+                        _ => (),
+                    }
+                }
+                _ => (),
+            }
+        }
+        blocks
+    }
+
+    /// Returns the distinct black box functions which are called by the circuit.
+    ///
+    /// A backend can use this to check that it supports every function required by the circuit before solving it.
+    pub fn blackbox_functions(&self) -> BTreeSet<BlackBoxFunc> {
+        self.opcodes
+            .iter()
+            .filter_map(|opcode| match opcode {
+                Opcode::BlackBoxFuncCall(bb_func) => Some(bb_func.get_black_box_func()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use acir_field::FieldElement;
+
+    use super::{BlockInfo, CostModel, DefaultCostModel, OpcodeStats};
+    use crate::{
+        circuit::{
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::Witness,
+        BlackBoxFunc,
+    };
+
+    /// Returns a circuit verifying a Schnorr signature over a single-byte message, with the signature truncated to a
+    /// single byte as only the shape of the circuit matters.
+    fn schnorr_verify_circuit() -> Circuit {
+        let field_input = |index| FunctionInput { witness: Witness(index), num_bits: 254 };
+        let byte_input = |index| FunctionInput { witness: Witness(index), num_bits: 8 };
+        Circuit {
+            current_witness_index: 5,
+            opcodes: vec![Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SchnorrVerify {
+                public_key_x: field_input(1),
+                public_key_y: field_input(2),
+                signature: vec![byte_input(3)],
+                message: vec![byte_input(4)],
+                output: Witness(5),
+            })],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(3), Witness(4)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(5)])),
+            ..Circuit::default()
+        }
+    }
+
+    #[test]
+    fn schnorr_verify_circuit_stats() {
+        let stats = schnorr_verify_circuit().opcode_stats();
+        assert_eq!(
+            stats,
+            OpcodeStats {
+                black_box_func_calls: 1,
+                black_box_funcs: BTreeMap::from([(BlackBoxFunc::SchnorrVerify, 1)]),
+                num_witnesses: 5,
+                ..OpcodeStats::default()
+            }
+        );
+        assert_eq!(stats.num_opcodes(), 1);
+    }
+
+    #[test]
+    fn total_cost_uses_cost_model() {
+        struct SchnorrHeavy;
+
+        impl CostModel for SchnorrHeavy {
+            fn cost(&self, opcode: &Opcode) -> u64 {
+                match opcode {
+                    Opcode::BlackBoxFuncCall(BlackBoxFuncCall::SchnorrVerify { .. }) => 10_000,
+                    _ => 1,
+                }
+            }
+        }
+
+        let mut circuit = schnorr_verify_circuit();
+        assert_eq!(circuit.total_cost(&DefaultCostModel), 1);
+        assert_eq!(circuit.total_cost(&SchnorrHeavy), 10_000);
+
+        circuit.opcodes.push(Opcode::Arithmetic(Witness(1).into()));
+        assert_eq!(circuit.total_cost(&SchnorrHeavy), 10_001);
+    }
+
+    #[test]
+    fn memory_blocks_reports_uninitialized_blocks() {
+        let read = |block_id| Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(FieldElement::zero().into(), Witness(2)),
+            predicate: None,
+        };
+        let circuit = Circuit {
+            opcodes: vec![
+                Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1)] },
+                read(BlockId(0)),
+                read(BlockId(1)),
+            ],
+            ..Circuit::default()
+        };
+
+        assert_eq!(
+            circuit.memory_blocks(),
+            BTreeMap::from([
+                (BlockId(0), BlockInfo { size: Some(1), num_reads: 1, num_writes: 0 }),
+                (BlockId(1), BlockInfo { size: None, num_reads: 1, num_writes: 0 }),
+            ])
+        );
+    }
+}
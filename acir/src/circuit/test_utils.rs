@@ -0,0 +1,32 @@
+//! Fixtures shared between the unit tests of the circuit module.
+
+use std::collections::BTreeSet;
+
+use acir_field::FieldElement;
+
+use super::{Circuit, Opcode, PublicInputs};
+use crate::native_types::{Expression, Witness};
+
+/// Returns an opcode constraining `lhs + rhs - output == 0`.
+pub(super) fn addition(lhs: u32, rhs: u32, output: u32) -> Opcode {
+    Opcode::Arithmetic(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::one(), Witness(lhs)),
+            (FieldElement::one(), Witness(rhs)),
+            (-FieldElement::one(), Witness(output)),
+        ],
+        q_c: FieldElement::zero(),
+    })
+}
+
+/// Returns a circuit which returns `w3 = w1 + w2` for the private parameters `w1` and `w2`.
+pub(super) fn addition_circuit() -> Circuit {
+    Circuit {
+        current_witness_index: 4,
+        opcodes: vec![addition(1, 2, 3)],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        return_values: PublicInputs([Witness(3)].into()),
+        ..Circuit::default()
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::{BTreeSet, HashSet};
+
+use acir_field::FieldElement;
+use thiserror::Error;
+
+use super::{opcodes::BlockId, Circuit, Opcode};
+use crate::native_types::Witness;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum CircuitValidationError {
+    #[error("opcode {opcode_index} references witness _{} which is above the current witness index", witness.witness_index())]
+    WitnessOutOfRange { opcode_index: usize, witness: Witness },
+    #[error("circuit parameter _{} is above the current witness index", witness.witness_index())]
+    ParameterOutOfRange { witness: Witness },
+    #[error("witness _{} is both a private parameter and a return value", witness.witness_index())]
+    PrivateReturnValue { witness: Witness },
+    #[error("opcode {opcode_index} references memory block {} before it is initialized", block_id.0)]
+    UninitializedMemoryBlock { opcode_index: usize, block_id: BlockId },
+    #[error("opcode {opcode_index} declares witness _{} as {num_bits} bits wide, which exceeds the field size", witness.witness_index())]
+    InputTooWide { opcode_index: usize, witness: Witness, num_bits: u32 },
+    #[error("brillig opcode {brillig_index} of opcode {opcode_index} declares {types} foreign call types for {operands} operands")]
+    ForeignCallTypeCountMismatch {
+        opcode_index: usize,
+        brillig_index: usize,
+        types: usize,
+        operands: usize,
+    },
+}
+
+impl Circuit {
+    /// Checks that the circuit is well-formed, returning all violations which were found.
+    ///
+    /// A circuit is well-formed if:
+    /// - every witness referenced by its opcodes and parameters is at most `current_witness_index`
+    /// - no private parameter is also a return value
+    /// - every [`Opcode::MemoryOp`] acts on a block which was initialized by a preceding [`Opcode::MemoryInit`]
+    /// - no black box function input is wider than [`FieldElement::max_num_bits`], as range checks on it would be vacuous
+    /// - every typed Brillig foreign call declares exactly one type per input and per destination
+    pub fn validate(&self) -> Result<(), Vec<CircuitValidationError>> {
+        let mut errors = Vec::new();
+        let in_range = |witness: &Witness| witness.witness_index() <= self.current_witness_index;
+
+        let parameters = self
+            .private_parameters
+            .iter()
+            .chain(&self.public_parameters.0)
+            .chain(&self.return_values.0)
+            .collect::<BTreeSet<_>>();
+        for witness in parameters {
+            if !in_range(witness) {
+                errors.push(CircuitValidationError::ParameterOutOfRange { witness: *witness });
+            }
+        }
+
+        for witness in self.private_parameters.intersection(&self.return_values.0) {
+            errors.push(CircuitValidationError::PrivateReturnValue { witness: *witness });
+        }
+
+        let mut initialized_blocks = HashSet::new();
+        for (opcode_index, opcode) in self.opcodes.iter().enumerate() {
+            for witness in opcode.witnesses() {
+                if !in_range(&witness) {
+                    errors
+                        .push(CircuitValidationError::WitnessOutOfRange { opcode_index, witness });
+                }
+            }
+
+            match opcode {
+                Opcode::MemoryInit { block_id, .. } => {
+                    initialized_blocks.insert(*block_id);
+                }
+                Opcode::MemoryOp { block_id, .. } if !initialized_blocks.contains(block_id) => {
+                    errors.push(CircuitValidationError::UninitializedMemoryBlock {
+                        opcode_index,
+                        block_id: *block_id,
+                    });
+                }
+                Opcode::BlackBoxFuncCall(call) => {
+                    for input in call.get_inputs_vec() {
+                        if input.num_bits > FieldElement::max_num_bits() {
+                            errors.push(CircuitValidationError::InputTooWide {
+                                opcode_index,
+                                witness: input.witness,
+                                num_bits: input.num_bits,
+                            });
+                        }
+                    }
+                }
+                Opcode::Brillig(brillig) => {
+                    for (brillig_index, brillig_opcode) in brillig.bytecode.iter().enumerate() {
+                        let brillig::Opcode::ForeignCall {
+                            destinations,
+                            destination_types,
+                            inputs,
+                            input_types,
+                            ..
+                        } = brillig_opcode
+                        else {
+                            continue;
+                        };
+                        for (types, operands) in
+                            [(destination_types, destinations), (input_types, inputs)]
+                        {
+                            match types {
+                                Some(types) if types.len() != operands.len() => {
+                                    errors.push(
+                                        CircuitValidationError::ForeignCallTypeCountMismatch {
+                                            opcode_index,
+                                            brillig_index,
+                                            types: types.len(),
+                                            operands: operands.len(),
+                                        },
+                                    );
+                                }
+                                _ => (),
+                            }
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use acir_field::FieldElement;
+    use brillig::{ForeignCallParamType, RegisterIndex, RegisterOrMemory, Typ};
+
+    use super::CircuitValidationError;
+    use crate::{
+        circuit::{
+            brillig::Brillig,
+            opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+            test_utils::addition,
+            Circuit, Opcode, PublicInputs,
+        },
+        native_types::Witness,
+    };
+
+    #[test]
+    fn accepts_well_formed_circuit() {
+        let circuit = Circuit {
+            current_witness_index: 4,
+            opcodes: vec![
+                Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+                Opcode::MemoryOp {
+                    block_id: BlockId(0),
+                    op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(3)),
+                    predicate: None,
+                },
+                addition(1, 3, 4),
+            ],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(4)])),
+            ..Circuit::default()
+        };
+
+        assert_eq!(circuit.validate(), Ok(()));
+    }
+
+    #[test]
+    fn reports_all_violations() {
+        let circuit = Circuit {
+            current_witness_index: 3,
+            opcodes: vec![
+                addition(1, 2, 7),
+                Opcode::MemoryOp {
+                    block_id: BlockId(1),
+                    op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(3)),
+                    predicate: None,
+                },
+            ],
+            private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+            return_values: PublicInputs(BTreeSet::from([Witness(2)])),
+            ..Circuit::default()
+        };
+
+        assert_eq!(
+            circuit.validate(),
+            Err(vec![
+                CircuitValidationError::PrivateReturnValue { witness: Witness(2) },
+                CircuitValidationError::WitnessOutOfRange { opcode_index: 0, witness: Witness(7) },
+                CircuitValidationError::UninitializedMemoryBlock {
+                    opcode_index: 1,
+                    block_id: BlockId(1)
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_inputs_wider_than_field() {
+        let range = |num_bits| {
+            Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+                input: FunctionInput { witness: Witness(1), num_bits },
+            })
+        };
+        let circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![
+                range(FieldElement::max_num_bits()),
+                range(FieldElement::max_num_bits() + 1),
+            ],
+            private_parameters: BTreeSet::from([Witness(1)]),
+            ..Circuit::default()
+        };
+
+        assert_eq!(
+            circuit.validate(),
+            Err(vec![CircuitValidationError::InputTooWide {
+                opcode_index: 1,
+                witness: Witness(1),
+                num_bits: FieldElement::max_num_bits() + 1
+            }])
+        );
+    }
+
+    #[test]
+    fn rejects_foreign_calls_with_wrong_number_of_types() {
+        let field_type = ForeignCallParamType::Simple(Typ::Field);
+        let circuit = Circuit {
+            current_witness_index: 1,
+            opcodes: vec![Opcode::Brillig(Brillig {
+                inputs: Vec::new(),
+                outputs: Vec::new(),
+                foreign_call_results: Vec::new(),
+                bytecode: vec![brillig::Opcode::ForeignCall {
+                    function: "oracle".into(),
+                    destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                    destination_types: Some(Vec::new()),
+                    inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                    input_types: Some(vec![field_type]),
+                }],
+                trap_messages: vec![],
+                predicate: None,
+            })],
+            ..Circuit::default()
+        };
+
+        assert_eq!(
+            circuit.validate(),
+            Err(vec![CircuitValidationError::ForeignCallTypeCountMismatch {
+                opcode_index: 0,
+                brillig_index: 0,
+                types: 0,
+                operands: 1
+            }])
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use acir_field::FieldElement;
+
+use super::{Witness, WitnessMap};
+use crate::circuit::opcodes::FunctionInput;
+
+/// The order in which the bytes of a value are assigned to consecutive witnesses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// The first witness holds the most significant byte.
+    Big,
+    /// The first witness holds the least significant byte.
+    Little,
+}
+
+/// Decomposes `value` into `num_bytes` bytes assigned to consecutive witnesses starting from `start`.
+///
+/// Returns the assignment of each byte witness along with an 8-bit [`FunctionInput`] for each of them,
+/// as expected by black box functions which take byte arrays such as [`SchnorrVerify`][sv]'s signature.
+///
+/// # Panics
+///
+/// Panics if `value` does not fit within `num_bytes` bytes.
+///
+/// [sv]: crate::circuit::opcodes::BlackBoxFuncCall::SchnorrVerify
+pub fn to_byte_witnesses(
+    value: FieldElement,
+    start: Witness,
+    num_bytes: usize,
+    endianness: Endianness,
+) -> (Vec<(Witness, FieldElement)>, Vec<FunctionInput>) {
+    assert!(
+        value.num_bits() as usize <= num_bytes * 8,
+        "{value} does not fit within {num_bytes} bytes"
+    );
+
+    let mut bytes = value.to_le_bytes();
+    bytes.resize(num_bytes, 0);
+    if endianness == Endianness::Big {
+        bytes.reverse();
+    }
+
+    bytes
+        .into_iter()
+        .zip(start.witness_index()..)
+        .map(|(byte, index)| {
+            let witness = Witness(index);
+            ((witness, FieldElement::from(byte as u128)), FunctionInput { witness, num_bits: 8 })
+        })
+        .unzip()
+}
+
+/// Reconstructs a value from the bytes assigned to `inputs` in `witness_map`, the inverse of [`to_byte_witnesses`].
+///
+/// Returns `None` if any of the witnesses is unassigned or is not assigned a byte.
+/// Values which do not fit within the field are reduced modulo the field's modulus.
+pub fn from_byte_witnesses(
+    witness_map: &WitnessMap,
+    inputs: &[FunctionInput],
+    endianness: Endianness,
+) -> Option<FieldElement> {
+    let mut bytes = inputs
+        .iter()
+        .map(|input| {
+            let value = witness_map.get(&input.witness)?;
+            u8::try_from(value.try_to_u64()?).ok()
+        })
+        .collect::<Option<Vec<u8>>>()?;
+    if endianness == Endianness::Little {
+        bytes.reverse();
+    }
+    Some(FieldElement::from_be_bytes_reduce(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir_field::FieldElement;
+
+    use super::{from_byte_witnesses, to_byte_witnesses, Endianness};
+    use crate::{
+        circuit::opcodes::FunctionInput,
+        native_types::{Witness, WitnessMap},
+    };
+
+    #[test]
+    fn reconstructs_value_from_byte_witnesses() {
+        let value = -FieldElement::from(12345u128);
+        for endianness in [Endianness::Big, Endianness::Little] {
+            let (assignments, inputs) = to_byte_witnesses(value, Witness(3), 32, endianness);
+            assert_eq!(inputs.len(), 32);
+            assert_eq!(inputs[0], FunctionInput { witness: Witness(3), num_bits: 8 });
+            assert_eq!(inputs[31], FunctionInput { witness: Witness(34), num_bits: 8 });
+
+            let witness_map = WitnessMap::from(assignments.into_iter().collect::<BTreeMap<_, _>>());
+            assert_eq!(from_byte_witnesses(&witness_map, &inputs, endianness), Some(value));
+        }
+    }
+
+    #[test]
+    fn byte_order() {
+        let value = FieldElement::from(0x0102u128);
+        let (big_endian, _) = to_byte_witnesses(value, Witness(1), 3, Endianness::Big);
+        let byte = |byte: u128| FieldElement::from(byte);
+        assert_eq!(
+            big_endian,
+            vec![(Witness(1), byte(0)), (Witness(2), byte(1)), (Witness(3), byte(2))]
+        );
+
+        let (little_endian, inputs) = to_byte_witnesses(value, Witness(1), 3, Endianness::Little);
+        assert_eq!(
+            little_endian,
+            vec![(Witness(1), byte(2)), (Witness(2), byte(1)), (Witness(3), byte(0))]
+        );
+
+        // Witnesses which are not assigned bytes cannot be reconstructed.
+        let mut witness_map =
+            WitnessMap::from(little_endian.into_iter().collect::<BTreeMap<_, _>>());
+        witness_map.insert(Witness(2), byte(256));
+        assert_eq!(from_byte_witnesses(&witness_map, &inputs, Endianness::Little), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_values_which_do_not_fit() {
+        to_byte_witnesses(FieldElement::from(256u128), Witness(1), 1, Endianness::Big);
+    }
+}
@@ -1,7 +1,7 @@
-use crate::native_types::Witness;
+use crate::native_types::{Witness, WitnessMap};
 use acir_field::FieldElement;
 use serde::{Deserialize, Serialize};
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::BTreeMap};
 
 mod operators;
 mod ordering;
@@ -14,6 +14,7 @@ mod ordering;
 // In the multiplication polynomial
 // XXX: If we allow the degree of the quotient polynomial to be arbitrary, then we will need a vector of wire values
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Expression {
     // To avoid having to create intermediate variables pre-optimization
     // We collect all of the multiplication terms in the arithmetic opcode
@@ -136,6 +137,31 @@ impl Expression {
         *self == Self::zero()
     }
 
+    /// Returns the degree of the expression as given by its terms.
+    ///
+    /// Examples:
+    /// -  f(x,y) = xy + x would return 2
+    /// -  f(x,y) = x + y would return 1
+    /// -  f(x,y) = 5 would return 0
+    pub fn degree(&self) -> usize {
+        if !self.mul_terms.is_empty() {
+            2
+        } else if !self.linear_combinations.is_empty() {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Returns `true` if the expression can be used as an arithmetic gate.
+    ///
+    /// Expressions are at most quadratic by construction, so the only requirement is that every multiplication term
+    /// has a non-zero coefficient, as otherwise the expression's [degree][Expression::degree] would not reflect its
+    /// effective degree.
+    pub fn is_valid_gate(&self) -> bool {
+        self.mul_terms.iter().all(|(q_m, _, _)| !q_m.is_zero())
+    }
+
     /// Returns a `FieldElement` if the expression represents a constant polynomial.
     /// Otherwise returns `None`.
     ///
@@ -340,6 +366,95 @@ impl Expression {
 
         Expression { mul_terms, linear_combinations, q_c }
     }
+
+    /// Replaces each witness `w` referenced by this expression with `f(w)`, keeping the terms sorted.
+    pub(crate) fn remap_witnesses(&mut self, f: &impl Fn(Witness) -> Witness) {
+        for (_, w_l, w_r) in &mut self.mul_terms {
+            let (l, r) = (f(*w_l), f(*w_r));
+            (*w_l, *w_r) = if l <= r { (l, r) } else { (r, l) };
+        }
+        for (_, witness) in &mut self.linear_combinations {
+            *witness = f(*witness);
+        }
+        self.sort();
+    }
+
+    /// Replaces each witness which is assigned a value in `assignments` with that value, folding the resulting
+    /// constants into the remaining terms.
+    ///
+    /// If any witness is replaced then the expression is left in canonical form.
+    pub(crate) fn substitute(&mut self, assignments: &WitnessMap) {
+        let value_of = |witness: &Witness| assignments.get(witness).copied();
+        if !self.linear_combinations.iter().any(|(_, witness)| value_of(witness).is_some())
+            && !self
+                .mul_terms
+                .iter()
+                .any(|(_, w_l, w_r)| value_of(w_l).is_some() || value_of(w_r).is_some())
+        {
+            return;
+        }
+
+        let mut substituted = Expression::from_field(self.q_c);
+        for (q_m, w_l, w_r) in &self.mul_terms {
+            match (value_of(w_l), value_of(w_r)) {
+                (Some(l), Some(r)) => substituted.q_c += *q_m * l * r,
+                (Some(l), None) => substituted.push_addition_term(*q_m * l, *w_r),
+                (None, Some(r)) => substituted.push_addition_term(*q_m * r, *w_l),
+                (None, None) => substituted.push_multiplication_term(*q_m, *w_l, *w_r),
+            }
+        }
+        for (q_l, witness) in &self.linear_combinations {
+            match value_of(witness) {
+                Some(value) => substituted.q_c += *q_l * value,
+                None => substituted.push_addition_term(*q_l, *witness),
+            }
+        }
+        substituted.canonicalize();
+        *self = substituted;
+    }
+
+    /// Rewrites the expression into a canonical form, without changing the polynomial which it represents.
+    ///
+    /// Terms referencing the same witnesses are merged, terms with a zero coefficient are removed,
+    /// the witnesses of each multiplication term are ordered and the terms are sorted.
+    pub(crate) fn canonicalize(&mut self) {
+        let mut mul_terms: BTreeMap<(Witness, Witness), FieldElement> = BTreeMap::new();
+        for (q_m, w_l, w_r) in &self.mul_terms {
+            let key = if w_l <= w_r { (*w_l, *w_r) } else { (*w_r, *w_l) };
+            *mul_terms.entry(key).or_insert_with(FieldElement::zero) += *q_m;
+        }
+        let mut linear_combinations: BTreeMap<Witness, FieldElement> = BTreeMap::new();
+        for (q_l, witness) in &self.linear_combinations {
+            *linear_combinations.entry(*witness).or_insert_with(FieldElement::zero) += *q_l;
+        }
+
+        self.mul_terms = mul_terms
+            .into_iter()
+            .filter(|(_, q_m)| !q_m.is_zero())
+            .map(|((w_l, w_r), q_m)| (q_m, w_l, w_r))
+            .collect();
+        self.linear_combinations = linear_combinations
+            .into_iter()
+            .filter(|(_, q_l)| !q_l.is_zero())
+            .map(|(witness, q_l)| (q_l, witness))
+            .collect();
+    }
+
+    /// Evaluates the expression using the values assigned in `witnesses`.
+    ///
+    /// Returns the first witness referenced by the expression which has no assigned value, if any.
+    pub fn evaluate(&self, witnesses: &WitnessMap) -> Result<FieldElement, Witness> {
+        let value_of = |witness: &Witness| witnesses.get(witness).copied().ok_or(*witness);
+
+        let mut result = self.q_c;
+        for (q_m, w_l, w_r) in &self.mul_terms {
+            result += *q_m * value_of(w_l)? * value_of(w_r)?;
+        }
+        for (q_l, w_l) in &self.linear_combinations {
+            result += *q_l * value_of(w_l)?;
+        }
+        Ok(result)
+    }
 }
 
 impl From<FieldElement> for Expression {
@@ -396,3 +511,63 @@ fn add_mul_smoketest() {
         }
     )
 }
+
+#[test]
+fn degree_and_gate_validity() {
+    let addition = Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::one(), Witness(1)),
+            (FieldElement::one(), Witness(2)),
+            (-FieldElement::one(), Witness(3)),
+        ],
+        q_c: FieldElement::zero(),
+    };
+    assert_eq!(addition.degree(), 1);
+    assert!(addition.is_valid_gate());
+
+    let multiplication = Expression {
+        mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+        linear_combinations: vec![(-FieldElement::one(), Witness(3))],
+        q_c: FieldElement::zero(),
+    };
+    assert_eq!(multiplication.degree(), 2);
+    assert!(multiplication.is_valid_gate());
+
+    assert_eq!(Expression::one().degree(), 0);
+
+    let zero_product = Expression {
+        mul_terms: vec![(FieldElement::zero(), Witness(1), Witness(2))],
+        ..Default::default()
+    };
+    assert!(!zero_product.is_valid_gate());
+}
+
+#[test]
+fn evaluate_smoketest() {
+    let addition = Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::one(), Witness(1)),
+            (FieldElement::one(), Witness(2)),
+            (-FieldElement::one(), Witness(3)),
+        ],
+        q_c: FieldElement::zero(),
+    };
+
+    let mut witnesses = WitnessMap::from(std::collections::BTreeMap::from([
+        (Witness(1), FieldElement::from(2u128)),
+        (Witness(2), FieldElement::from(3u128)),
+    ]));
+    assert_eq!(addition.evaluate(&witnesses), Err(Witness(3)));
+
+    witnesses.insert(Witness(3), FieldElement::from(5u128));
+    assert_eq!(addition.evaluate(&witnesses), Ok(FieldElement::zero()));
+
+    let quadratic = Expression {
+        mul_terms: vec![(FieldElement::from(2u128), Witness(1), Witness(2))],
+        linear_combinations: vec![(FieldElement::one(), Witness(3))],
+        q_c: FieldElement::one(),
+    };
+    assert_eq!(quadratic.evaluate(&witnesses), Ok(FieldElement::from(18u128)));
+}
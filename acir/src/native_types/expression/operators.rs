@@ -25,6 +25,14 @@ impl Neg for &Expression {
     }
 }
 
+impl Neg for Expression {
+    type Output = Expression;
+    #[inline]
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
 // FieldElement
 
 impl Add<FieldElement> for Expression {
@@ -89,6 +97,22 @@ impl Mul<&Expression> for FieldElement {
     }
 }
 
+impl Mul<FieldElement> for Expression {
+    type Output = Expression;
+    #[inline]
+    fn mul(self, rhs: FieldElement) -> Self::Output {
+        &self * rhs
+    }
+}
+
+impl Mul<Expression> for FieldElement {
+    type Output = Expression;
+    #[inline]
+    fn mul(self, rhs: Expression) -> Self::Output {
+        &rhs * self
+    }
+}
+
 // Witness
 
 impl Add<Witness> for &Expression {
@@ -121,6 +145,38 @@ impl Sub<&Expression> for Witness {
     }
 }
 
+impl Add<Witness> for Expression {
+    type Output = Expression;
+    #[inline]
+    fn add(self, rhs: Witness) -> Expression {
+        &self + rhs
+    }
+}
+
+impl Add<Expression> for Witness {
+    type Output = Expression;
+    #[inline]
+    fn add(self, rhs: Expression) -> Expression {
+        &rhs + self
+    }
+}
+
+impl Sub<Witness> for Expression {
+    type Output = Expression;
+    #[inline]
+    fn sub(self, rhs: Witness) -> Expression {
+        &self - rhs
+    }
+}
+
+impl Sub<Expression> for Witness {
+    type Output = Expression;
+    #[inline]
+    fn sub(self, rhs: Expression) -> Expression {
+        self - &rhs
+    }
+}
+
 // Mul<Witness> is not implemented as this could result in degree 3 terms.
 
 // Expression
@@ -214,6 +270,30 @@ impl Mul<&Expression> for &Expression {
     }
 }
 
+impl Add<Expression> for Expression {
+    type Output = Expression;
+    #[inline]
+    fn add(self, rhs: Expression) -> Expression {
+        &self + &rhs
+    }
+}
+
+impl Sub<Expression> for Expression {
+    type Output = Expression;
+    #[inline]
+    fn sub(self, rhs: Expression) -> Expression {
+        &self - &rhs
+    }
+}
+
+impl Mul<Expression> for Expression {
+    type Output = Option<Expression>;
+    #[inline]
+    fn mul(self, rhs: Expression) -> Option<Expression> {
+        &self * &rhs
+    }
+}
+
 /// Returns `w*b.linear_combinations`
 fn single_mul(w: Witness, b: &Expression) -> Expression {
     Expression {
@@ -288,3 +368,46 @@ fn mul_smoketest() {
     // Enforce commutativity
     assert_eq!(&a * &b, &b * &a);
 }
+
+#[test]
+fn owned_operators_build_addition_expression() {
+    let addition =
+        Expression::from(Witness(1)) + Expression::from(Witness(2)) - Expression::from(Witness(3));
+
+    assert_eq!(
+        addition,
+        Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(3)),
+            ],
+            q_c: FieldElement::zero(),
+        }
+    );
+    assert_eq!(Expression::from(Witness(1)) + Witness(2) - Witness(3), addition);
+}
+
+#[test]
+fn owned_mul_rejects_degree_three_terms() {
+    let quadratic = (Expression::from(Witness(1)) * Expression::from(Witness(2))).unwrap();
+    assert_eq!(
+        quadratic,
+        Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+            linear_combinations: Vec::new(),
+            q_c: FieldElement::zero(),
+        }
+    );
+
+    assert_eq!(quadratic.clone() * Expression::from(Witness(3)), None);
+    assert_eq!(
+        quadratic.clone() * FieldElement::from(2_u128),
+        Expression {
+            mul_terms: vec![(FieldElement::from(2_u128), Witness(1), Witness(2))],
+            linear_combinations: Vec::new(),
+            q_c: FieldElement::zero(),
+        }
+    );
+}
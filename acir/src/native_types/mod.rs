@@ -1,8 +1,13 @@
+mod byte_witnesses;
 mod expression;
 mod witness;
 mod witness_map;
 
+pub use byte_witnesses::{from_byte_witnesses, to_byte_witnesses, Endianness};
 pub use expression::Expression;
-pub use witness::Witness;
+pub use witness::{Witness, WitnessFromStrError};
+pub use witness_map::MergeConflict;
+pub use witness_map::MissingWitness;
+pub use witness_map::WitnessDiff;
 pub use witness_map::WitnessMap;
 pub use witness_map::WitnessMapError;
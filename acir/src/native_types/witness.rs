@@ -1,7 +1,8 @@
-use std::ops::Add;
+use std::{fmt, ops::Add, str::FromStr};
 
 use acir_field::FieldElement;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use super::Expression;
 
@@ -11,6 +12,17 @@ use super::Expression;
 )]
 pub struct Witness(pub u32);
 
+/// The largest witness index generated by [`arbitrary::Arbitrary`], to avoid circuits of pathological size.
+#[cfg(feature = "arbitrary")]
+const MAX_ARBITRARY_WITNESS_INDEX: u32 = 1 << 16;
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Witness {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Witness(u.int_in_range(0..=MAX_ARBITRARY_WITNESS_INDEX)?))
+    }
+}
+
 impl Witness {
     pub fn new(witness_index: u32) -> Witness {
         Witness(witness_index)
@@ -34,6 +46,31 @@ impl From<u32> for Witness {
     }
 }
 
+/// Formats the witness as `w<index>`, e.g. `w42`.
+impl fmt::Display for Witness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "w{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum WitnessFromStrError {
+    #[error("Invalid witness string {0:?}, expected `w` followed by a witness index")]
+    InvalidWitnessString(String),
+}
+
+/// Parses a witness from the `w<index>` form produced by its [`Display`][fmt::Display] implementation.
+impl FromStr for Witness {
+    type Err = WitnessFromStrError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix('w')
+            .filter(|index| index.bytes().all(|byte| byte.is_ascii_digit()))
+            .and_then(|index| index.parse().ok())
+            .map(Witness)
+            .ok_or_else(|| WitnessFromStrError::InvalidWitnessString(s.to_string()))
+    }
+}
+
 impl Add<Witness> for Witness {
     type Output = Expression;
 
@@ -41,3 +78,22 @@ impl Add<Witness> for Witness {
         Expression::from(self).add_mul(FieldElement::one(), &Expression::from(rhs))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Witness, WitnessFromStrError};
+
+    #[test]
+    fn display_and_from_str_roundtrip() {
+        let witness = Witness(42);
+        assert_eq!(witness.to_string(), "w42");
+        assert_eq!("w42".parse::<Witness>(), Ok(witness));
+
+        for invalid in ["x42", "w", "w+42", "42", "w4294967296"] {
+            assert_eq!(
+                invalid.parse::<Witness>(),
+                Err(WitnessFromStrError::InvalidWitnessString(invalid.to_string()))
+            );
+        }
+    }
+}
@@ -37,6 +37,40 @@ enum SerializationError {
 #[error(transparent)]
 pub struct WitnessMapError(#[from] SerializationError);
 
+/// Error returned by [`WitnessMap::merge`] when both maps assign different values to the same witness.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("witness {} is assigned conflicting values: {existing} and {incoming}", witness.witness_index())]
+pub struct MergeConflict {
+    pub witness: Witness,
+    pub existing: FieldElement,
+    pub incoming: FieldElement,
+}
+
+/// Error returned by [`WitnessMap::try_get`] when the witness has not been assigned a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("witness {} has no assigned value", witness.witness_index())]
+pub struct MissingWitness {
+    pub witness: Witness,
+}
+
+/// The differences between two [`WitnessMap`]s, as returned by [`WitnessMap::diff`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WitnessDiff {
+    /// Assignments to witnesses which are only present in the first map.
+    pub only_in_self: BTreeMap<Witness, FieldElement>,
+    /// Assignments to witnesses which are only present in the second map.
+    pub only_in_other: BTreeMap<Witness, FieldElement>,
+    /// Witnesses which are present in both maps with different values, given as `(self_value, other_value)`.
+    pub mismatched: BTreeMap<Witness, (FieldElement, FieldElement)>,
+}
+
+impl WitnessDiff {
+    /// Returns `true` if the two maps being compared were equal.
+    pub fn is_empty(&self) -> bool {
+        self.only_in_self.is_empty() && self.only_in_other.is_empty() && self.mismatched.is_empty()
+    }
+}
+
 /// A map from the witnesses in a constraint system to the field element values
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
 pub struct WitnessMap(BTreeMap<Witness, FieldElement>);
@@ -48,6 +82,10 @@ impl WitnessMap {
     pub fn get(&self, witness: &Witness) -> Option<&FieldElement> {
         self.0.get(witness)
     }
+    /// Returns the value assigned to `witness`, or a [`MissingWitness`] error naming it if it has no value.
+    pub fn try_get(&self, witness: Witness) -> Result<&FieldElement, MissingWitness> {
+        self.0.get(&witness).ok_or(MissingWitness { witness })
+    }
     pub fn get_index(&self, index: u32) -> Option<&FieldElement> {
         self.0.get(&index.into())
     }
@@ -57,6 +95,56 @@ impl WitnessMap {
     pub fn insert(&mut self, key: Witness, value: FieldElement) -> Option<FieldElement> {
         self.0.insert(key, value)
     }
+
+    /// Returns an iterator over the witness assignments in ascending order of witness index.
+    ///
+    /// The map is backed by a [`BTreeMap`] so this order is guaranteed, as it is when consuming the map with [`IntoIterator`].
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (Witness, &FieldElement)> {
+        self.0.iter().map(|(witness, value)| (*witness, value))
+    }
+
+    /// Inserts all assignments from `other` into this map.
+    ///
+    /// Witnesses which are assigned the same value in both maps are merged silently. If any witness is
+    /// assigned different values then a [`MergeConflict`] is returned and this map is left unchanged.
+    pub fn merge(&mut self, other: WitnessMap) -> Result<(), MergeConflict> {
+        for (witness, incoming) in &other.0 {
+            match self.0.get(witness) {
+                Some(existing) if existing != incoming => {
+                    return Err(MergeConflict {
+                        witness: *witness,
+                        existing: *existing,
+                        incoming: *incoming,
+                    })
+                }
+                _ => (),
+            }
+        }
+        self.0.extend(other.0);
+        Ok(())
+    }
+
+    /// Compares this map against `other`, returning the witnesses at which they differ.
+    pub fn diff(&self, other: &WitnessMap) -> WitnessDiff {
+        let mut diff = WitnessDiff::default();
+        for (witness, value) in &self.0 {
+            match other.0.get(witness) {
+                None => {
+                    diff.only_in_self.insert(*witness, *value);
+                }
+                Some(other_value) if other_value != value => {
+                    diff.mismatched.insert(*witness, (*value, *other_value));
+                }
+                Some(_) => (),
+            }
+        }
+        for (witness, value) in &other.0 {
+            if !self.0.contains_key(witness) {
+                diff.only_in_other.insert(*witness, *value);
+            }
+        }
+        diff
+    }
 }
 
 impl Index<&Witness> for WitnessMap {
@@ -86,6 +174,13 @@ impl IntoIterator for WitnessMap {
     }
 }
 
+/// Inserts each assignment in turn, as with [`WitnessMap::insert`], so later values overwrite earlier ones.
+impl Extend<(Witness, FieldElement)> for WitnessMap {
+    fn extend<T: IntoIterator<Item = (Witness, FieldElement)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
 impl From<BTreeMap<Witness, FieldElement>> for WitnessMap {
     fn from(value: BTreeMap<Witness, FieldElement>) -> Self {
         Self(value)
@@ -144,3 +239,136 @@ impl TryFrom<&[u8]> for WitnessMap {
         Ok(Self(witness_map))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acir_field::FieldElement;
+
+    use super::{MergeConflict, MissingWitness, WitnessDiff, WitnessMap};
+    use crate::native_types::Witness;
+
+    #[test]
+    fn extend_matches_individual_inserts() {
+        let inputs =
+            [(Witness(1), FieldElement::from(2u128)), (Witness(2), FieldElement::from(3u128))];
+
+        let mut inserted = WitnessMap::new();
+        for (witness, value) in inputs {
+            inserted.insert(witness, value);
+        }
+        let mut extended = WitnessMap::new();
+        extended.extend(inputs);
+
+        assert_eq!(extended, inserted);
+    }
+
+    #[test]
+    fn try_get_names_missing_witness() {
+        let witness_map = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+
+        assert_eq!(witness_map.try_get(Witness(1)), Ok(&FieldElement::one()));
+
+        let error = witness_map.try_get(Witness(7)).unwrap_err();
+        assert_eq!(error, MissingWitness { witness: Witness(7) });
+        assert_eq!(error.to_string(), "witness 7 has no assigned value");
+    }
+
+    #[test]
+    fn merge_combines_consistent_assignments() {
+        let mut witness_map = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::one()),
+            (Witness(2), FieldElement::from(2_u128)),
+        ]));
+        let other = WitnessMap::from(BTreeMap::from([
+            (Witness(2), FieldElement::from(2_u128)),
+            (Witness(3), FieldElement::from(3_u128)),
+        ]));
+
+        witness_map.merge(other).unwrap();
+
+        let expected = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::one()),
+            (Witness(2), FieldElement::from(2_u128)),
+            (Witness(3), FieldElement::from(3_u128)),
+        ]));
+        assert_eq!(witness_map, expected);
+    }
+
+    #[test]
+    fn merge_rejects_conflicting_assignments() {
+        let original = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+        let mut witness_map = original.clone();
+        let other = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::zero()),
+            (Witness(2), FieldElement::one()),
+        ]));
+
+        let err = witness_map.merge(other).unwrap_err();
+
+        assert_eq!(
+            err,
+            MergeConflict {
+                witness: Witness(1),
+                existing: FieldElement::one(),
+                incoming: FieldElement::zero()
+            }
+        );
+        assert_eq!(witness_map, original);
+    }
+
+    #[test]
+    fn diff_isolates_differing_witnesses() {
+        let expected = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::one()),
+            (Witness(2), FieldElement::from(2_u128)),
+            (Witness(3), FieldElement::from(3_u128)),
+        ]));
+        let mut actual = expected.clone();
+        assert!(expected.diff(&actual).is_empty());
+
+        actual.insert(Witness(2), FieldElement::from(5_u128));
+        assert_eq!(
+            expected.diff(&actual),
+            WitnessDiff {
+                mismatched: BTreeMap::from([(
+                    Witness(2),
+                    (FieldElement::from(2_u128), FieldElement::from(5_u128))
+                )]),
+                ..WitnessDiff::default()
+            }
+        );
+
+        let partial = WitnessMap::from(BTreeMap::from([
+            (Witness(1), FieldElement::one()),
+            (Witness(4), FieldElement::from(4_u128)),
+        ]));
+        let diff = expected.diff(&partial);
+        assert_eq!(
+            diff.only_in_self,
+            BTreeMap::from([
+                (Witness(2), FieldElement::from(2_u128)),
+                (Witness(3), FieldElement::from(3_u128)),
+            ])
+        );
+        assert_eq!(diff.only_in_other, BTreeMap::from([(Witness(4), FieldElement::from(4_u128))]));
+        assert!(diff.mismatched.is_empty());
+    }
+
+    #[test]
+    fn iter_sorted_yields_ascending_witnesses() {
+        let mut witness_map = WitnessMap::new();
+        for index in [5, 1, 3, 2, 4] {
+            witness_map.insert(Witness(index), FieldElement::from(index as u128));
+        }
+
+        let entries: Vec<_> = witness_map.iter_sorted().collect();
+        let expected: Vec<_> =
+            (1..=5).map(|index| (Witness(index), FieldElement::from(index as u128))).collect();
+        assert_eq!(
+            entries,
+            expected.iter().map(|(witness, value)| (*witness, value)).collect::<Vec<_>>()
+        );
+    }
+}
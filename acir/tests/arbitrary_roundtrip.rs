@@ -0,0 +1,68 @@
+//! Property tests checking that randomly generated circuits survive a serialization round trip.
+//!
+//! These tests require the `arbitrary` feature: `cargo test -p acir --features arbitrary`.
+//! The CBOR round trip additionally requires the `cbor` feature.
+#![cfg(feature = "arbitrary")]
+
+use acir::{
+    circuit::{Circuit, Opcode},
+    native_types::Expression,
+    FieldElement,
+};
+use arbitrary::{Arbitrary, Unstructured};
+
+const NUM_CASES: u64 = 256;
+
+/// Returns a deterministic pseudorandom buffer from which values can be generated.
+fn random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 56) as u8
+        })
+        .collect()
+}
+
+/// Calls `check` with a value generated from each of [`NUM_CASES`] random buffers of varying sizes.
+fn for_each_arbitrary<T: for<'a> Arbitrary<'a>>(check: impl Fn(T)) {
+    for seed in 0..NUM_CASES {
+        let bytes = random_bytes(seed, 256 << (seed % 8));
+        let value = T::arbitrary(&mut Unstructured::new(&bytes)).expect("buffer is large enough");
+        check(value);
+    }
+}
+
+#[test]
+fn circuit_serialization_roundtrip() {
+    for_each_arbitrary(|circuit: Circuit| {
+        let mut bytes = Vec::new();
+        circuit.write(&mut bytes).unwrap();
+        assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
+    });
+}
+
+#[test]
+fn opcode_serialization_roundtrip() {
+    for_each_arbitrary(|opcode: Opcode| {
+        let bytes = bincode::serialize(&opcode).unwrap();
+        assert_eq!(bincode::deserialize::<Opcode>(&bytes).unwrap(), opcode);
+    });
+}
+
+#[test]
+fn expression_and_field_serialization_roundtrip() {
+    for_each_arbitrary(|(expr, field): (Expression, FieldElement)| {
+        let bytes = bincode::serialize(&expr).unwrap();
+        assert_eq!(bincode::deserialize::<Expression>(&bytes).unwrap(), expr);
+        assert_eq!(FieldElement::from_be_bytes_reduce(&field.to_be_bytes()), field);
+    });
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn circuit_cbor_roundtrip() {
+    for_each_arbitrary(|circuit: Circuit| {
+        assert_eq!(Circuit::from_cbor(&circuit.to_cbor()).unwrap(), circuit);
+    });
+}
@@ -9,15 +9,18 @@
 //! Generally in this situation we just need to refresh the `expected_serialization` variables to match the
 //! actual output, **HOWEVER** note that this results in a breaking change to the ACIR format.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use acir::{
     circuit::{
         brillig::{Brillig, BrilligInputs, BrilligOutputs},
         opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
-        Circuit, Opcode, PublicInputs,
+        serialization::ACIR_SERIALIZATION_VERSION,
+        BlockInfo, Circuit, CircuitBuilder, CircuitReadError, CircuitStreamReader, MemoryStats,
+        Opcode, PublicInputs, SerializationError,
     },
     native_types::{Expression, Witness},
+    BlackBoxFunc,
 };
 use acir_field::FieldElement;
 use base64::Engine;
@@ -47,17 +50,58 @@ fn addition_circuit() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 173, 144, 187, 13, 192, 32, 12, 68, 249, 100, 32, 27,
-        219, 96, 119, 89, 37, 40, 176, 255, 8, 81, 36, 23, 72, 41, 195, 53, 215, 61, 221, 189, 35,
-        132, 16, 195, 55, 217, 251, 244, 134, 127, 193, 184, 145, 149, 22, 22, 65, 101, 30, 173,
-        12, 36, 188, 160, 88, 87, 1, 150, 94, 21, 21, 69, 229, 46, 74, 52, 148, 181, 89, 183, 6,
-        134, 76, 3, 167, 24, 77, 135, 229, 125, 187, 32, 57, 231, 253, 154, 22, 151, 113, 113, 250,
-        0, 123, 50, 20, 220, 112, 1, 0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 149, 142, 177, 9, 128, 48,
+        20, 68, 239, 39, 14, 98, 169, 157, 226, 8, 34, 88, 137, 165, 141, 157, 3, 136, 157, 101,
+        70, 16, 23, 112, 10, 209, 113, 210, 89, 218, 216, 75, 72, 2, 191, 76, 94, 115, 119, 112,
+        28, 151, 0, 16, 176, 16, 243, 62, 27, 36, 243, 28, 233, 52, 69, 16, 68, 17, 93, 193, 186,
+        197, 212, 45, 186, 60, 242, 171, 111, 78, 165, 134, 49, 171, 158, 118, 189, 231, 173, 214,
+        223, 254, 186, 31, 129, 187, 248, 1, 52, 85, 70, 137, 240, 0, 0, 0,
     ];
 
     assert_eq!(bytes, expected_serialization)
 }
 
+#[test]
+fn addition_circuit_from_builder() {
+    let manual_circuit = Circuit {
+        current_witness_index: 3,
+        opcodes: vec![Opcode::Arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(3)),
+            ],
+            q_c: FieldElement::zero(),
+        })],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        return_values: PublicInputs([Witness(3)].into()),
+        ..Circuit::default()
+    };
+
+    let built_circuit = CircuitBuilder::new()
+        .arithmetic(Expression {
+            mul_terms: Vec::new(),
+            linear_combinations: vec![
+                (FieldElement::one(), Witness(1)),
+                (FieldElement::one(), Witness(2)),
+                (-FieldElement::one(), Witness(3)),
+            ],
+            q_c: FieldElement::zero(),
+        })
+        .private(Witness(1))
+        .private(Witness(2))
+        .public_return(Witness(3))
+        .build();
+
+    let mut manual_bytes = Vec::new();
+    manual_circuit.write(&mut manual_bytes).unwrap();
+    let mut built_bytes = Vec::new();
+    built_circuit.write(&mut built_bytes).unwrap();
+
+    assert_eq!(built_bytes, manual_bytes);
+}
+
 #[test]
 fn fixed_base_scalar_mul_circuit() {
     let fixed_base_scalar_mul = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
@@ -78,9 +122,9 @@ fn fixed_base_scalar_mul_circuit() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 77, 138, 91, 10, 0, 48, 12, 194, 178, 215, 207, 78, 189,
-        163, 175, 165, 10, 21, 36, 10, 57, 192, 160, 146, 188, 226, 139, 78, 113, 69, 183, 190, 61,
-        111, 218, 182, 247, 1, 253, 52, 57, 128, 84, 0, 0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 77, 138, 73, 10, 0, 64, 12,
+        194, 50, 219, 101, 94, 221, 167, 183, 80, 11, 10, 98, 192, 60, 96, 211, 89, 198, 24, 159,
+        234, 213, 63, 222, 215, 134, 188, 48, 47, 1, 127, 21, 211, 72, 84, 0, 0, 0,
     ];
 
     assert_eq!(bytes, expected_serialization)
@@ -106,12 +150,129 @@ fn pedersen_circuit() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 93, 138, 65, 10, 0, 64, 8, 2, 103, 183, 232, 255, 47,
-        142, 138, 58, 68, 130, 168, 140, 10, 60, 90, 149, 118, 182, 79, 255, 105, 57, 140, 197,
-        246, 39, 0, 246, 174, 71, 87, 84, 0, 0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 93, 138, 65, 10, 0, 64, 8,
+        132, 220, 45, 250, 255, 139, 35, 34, 130, 24, 47, 34, 232, 192, 99, 88, 55, 255, 216, 228,
+        9, 233, 148, 183, 0, 237, 79, 78, 207, 84, 0, 0, 0,
     ];
 
-    assert_eq!(bytes, expected_serialization)
+    assert_eq!(bytes, expected_serialization);
+
+    assert_eq!(circuit.blackbox_functions(), BTreeSet::from([BlackBoxFunc::Pedersen]));
+}
+
+#[test]
+fn poseidon_circuit() {
+    let poseidon = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Poseidon {
+        inputs: vec![
+            FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() },
+            FunctionInput { witness: Witness(2), num_bits: FieldElement::max_num_bits() },
+        ],
+        output: Witness(3),
+    });
+
+    let circuit = Circuit {
+        current_witness_index: 4,
+        opcodes: vec![poseidon],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        return_values: PublicInputs(BTreeSet::from_iter(vec![Witness(3)])),
+        ..Circuit::default()
+    };
+
+    let mut bytes = Vec::new();
+    circuit.write(&mut bytes).unwrap();
+
+    let expected_serialization: Vec<u8> = vec![
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 93, 139, 73, 10, 0, 0, 8, 2,
+        109, 57, 247, 255, 215, 70, 68, 5, 146, 23, 103, 64, 29, 128, 98, 34, 196, 231, 29, 35,
+        238, 142, 247, 201, 245, 220, 109, 1, 237, 139, 178, 4, 84, 0, 0, 0,
+    ];
+
+    assert_eq!(bytes, expected_serialization);
+    assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
+}
+
+#[test]
+fn sha512_circuit() {
+    let inputs: Vec<_> =
+        (1..=3).map(|i| FunctionInput { witness: Witness(i), num_bits: 8 }).collect();
+    let outputs: Vec<_> = (4..68).map(Witness).collect();
+    let sha512 =
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Sha512 { inputs, outputs: outputs.clone() });
+
+    let circuit = Circuit {
+        current_witness_index: 67,
+        opcodes: vec![sha512],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(3)]),
+        return_values: PublicInputs(BTreeSet::from_iter(outputs)),
+        ..Circuit::default()
+    };
+
+    let mut bytes = Vec::new();
+    circuit.write(&mut bytes).unwrap();
+
+    let expected_serialization: Vec<u8> = vec![
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 237, 208, 85, 142, 66, 65,
+        16, 64, 209, 55, 254, 70, 25, 119, 55, 220, 221, 109, 255, 123, 226, 16, 26, 66, 88, 3,
+        149, 156, 116, 186, 254, 234, 78, 163, 40, 218, 139, 22, 179, 195, 238, 218, 127, 62, 163,
+        240, 238, 115, 192, 33, 71, 196, 28, 115, 194, 41, 103, 156, 115, 65, 130, 75, 174, 184,
+        230, 134, 91, 238, 184, 231, 129, 71, 158, 120, 230, 133, 87, 222, 120, 231, 131, 79, 190,
+        248, 230, 135, 95, 254, 248, 39, 73, 138, 52, 25, 178, 228, 200, 83, 160, 72, 137, 50, 21,
+        170, 212, 168, 211, 160, 73, 139, 54, 29, 186, 244, 232, 51, 96, 24, 238, 30, 51, 97, 26,
+        218, 44, 27, 37, 54, 154, 197, 161, 91, 28, 246, 241, 182, 219, 170, 219, 12, 115, 97, 143,
+        130, 96, 2, 0, 0,
+    ];
+
+    assert_eq!(bytes, expected_serialization);
+    assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
+}
+
+#[test]
+fn eddsa_verify_circuit() {
+    let inputs: Vec<_> =
+        (1..=99).map(|i| FunctionInput { witness: Witness(i), num_bits: 8 }).collect();
+    let eddsa = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::EddsaVerify {
+        public_key: inputs[..32].to_vec(),
+        signature: inputs[32..96].to_vec(),
+        message: inputs[96..].to_vec(),
+        output: Witness(100),
+    });
+
+    let circuit = Circuit {
+        current_witness_index: 100,
+        opcodes: vec![eddsa],
+        private_parameters: BTreeSet::from_iter((1..=99).map(Witness)),
+        return_values: PublicInputs(BTreeSet::from([Witness(100)])),
+        ..Circuit::default()
+    };
+
+    let mut bytes = Vec::new();
+    circuit.write(&mut bytes).unwrap();
+
+    let expected_serialization: Vec<u8> = vec![
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 77, 211, 85, 110, 196, 64,
+        16, 0, 81, 135, 153, 153, 55, 204, 204, 204, 204, 156, 101, 188, 255, 25, 82, 86, 106, 149,
+        29, 233, 169, 220, 159, 158, 182, 11, 65, 16, 228, 131, 191, 83, 134, 114, 84, 160, 18, 85,
+        168, 70, 13, 106, 81, 135, 122, 52, 160, 17, 77, 104, 70, 11, 90, 209, 134, 118, 116, 160,
+        19, 93, 232, 70, 15, 122, 209, 135, 126, 12, 96, 16, 67, 24, 198, 8, 34, 24, 197, 24, 198,
+        49, 129, 73, 76, 97, 26, 51, 152, 197, 28, 230, 177, 128, 69, 44, 97, 25, 43, 88, 197, 26,
+        214, 177, 129, 77, 108, 97, 27, 59, 216, 197, 30, 246, 113, 128, 67, 28, 225, 24, 39, 56,
+        197, 25, 206, 113, 129, 75, 92, 225, 26, 55, 184, 197, 29, 238, 241, 128, 71, 60, 225, 25,
+        47, 120, 197, 27, 222, 241, 129, 79, 124, 225, 27, 63, 136, 34, 134, 56, 18, 72, 34, 133,
+        52, 50, 200, 34, 87, 178, 143, 226, 78, 194, 83, 40, 121, 46, 243, 174, 35, 37, 115, 184,
+        159, 114, 91, 97, 43, 109, 149, 173, 182, 197, 125, 22, 213, 217, 122, 219, 96, 27, 109,
+        147, 109, 182, 45, 182, 213, 182, 217, 118, 219, 97, 59, 109, 151, 237, 182, 61, 182, 215,
+        246, 217, 126, 59, 96, 7, 237, 144, 29, 182, 35, 54, 98, 79, 188, 135, 81, 231, 49, 59,
+        110, 39, 236, 164, 157, 178, 211, 118, 198, 206, 218, 57, 59, 111, 23, 236, 162, 93, 178,
+        203, 118, 197, 174, 218, 53, 187, 110, 55, 236, 166, 221, 178, 219, 118, 199, 238, 218, 61,
+        187, 111, 15, 236, 161, 61, 178, 199, 37, 239, 31, 246, 212, 158, 217, 115, 123, 97, 47,
+        237, 149, 189, 182, 55, 246, 214, 222, 217, 123, 251, 96, 31, 237, 147, 125, 182, 47, 246,
+        213, 190, 217, 119, 251, 97, 63, 237, 151, 253, 182, 63, 54, 106, 99, 54, 110, 19, 54, 105,
+        83, 54, 109, 51, 193, 255, 119, 31, 158, 172, 115, 206, 230, 109, 248, 15, 253, 2, 254, 60,
+        229, 217, 240, 4, 0, 0,
+    ];
+
+    assert_eq!(bytes, expected_serialization);
+    assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
 }
 
 #[test]
@@ -148,25 +309,29 @@ fn schnorr_verify_circuit() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 77, 210, 233, 50, 66, 1, 24, 199, 225, 99, 223, 247,
-        125, 15, 73, 146, 36, 73, 146, 36, 73, 194, 93, 184, 255, 75, 48, 122, 167, 167, 25, 103,
-        230, 204, 83, 211, 151, 230, 253, 255, 126, 146, 36, 25, 73, 6, 79, 56, 193, 223, 254, 59,
-        202, 166, 223, 199, 250, 239, 116, 255, 29, 231, 4, 39, 57, 197, 225, 59, 195, 89, 206,
-        113, 158, 11, 92, 228, 18, 151, 185, 194, 85, 174, 113, 157, 27, 220, 228, 22, 183, 185,
-        195, 93, 238, 113, 159, 7, 60, 228, 17, 83, 60, 230, 9, 79, 153, 230, 25, 51, 60, 103, 150,
-        23, 204, 241, 146, 121, 94, 177, 192, 107, 22, 121, 195, 18, 111, 89, 230, 29, 43, 188,
-        103, 149, 15, 172, 241, 145, 117, 62, 177, 193, 103, 54, 249, 194, 214, 191, 29, 227, 121,
-        245, 189, 205, 55, 118, 248, 206, 46, 63, 216, 227, 39, 191, 248, 237, 115, 60, 209, 94,
-        116, 23, 173, 69, 103, 209, 88, 244, 53, 108, 107, 198, 255, 136, 150, 162, 163, 104, 40,
-        250, 137, 118, 162, 155, 104, 38, 122, 137, 86, 162, 147, 104, 36, 250, 136, 54, 162, 139,
-        104, 34, 122, 136, 22, 162, 131, 104, 32, 246, 143, 237, 83, 201, 96, 243, 216, 59, 182,
-        78, 219, 56, 99, 219, 172, 77, 115, 182, 204, 219, 176, 96, 187, 162, 205, 74, 182, 42,
-        219, 168, 98, 155, 170, 77, 106, 182, 168, 219, 160, 225, 246, 77, 55, 111, 185, 113, 219,
-        109, 59, 110, 218, 117, 203, 158, 27, 14, 111, 54, 188, 91, 226, 150, 127, 214, 93, 14,
-        165, 212, 3, 0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 77, 210, 5, 106, 3, 1, 20,
+        6, 225, 173, 191, 186, 187, 187, 187, 55, 238, 218, 91, 244, 254, 71, 8, 157, 165, 83, 216,
+        133, 143, 73, 32, 16, 120, 255, 254, 36, 73, 210, 79, 254, 158, 33, 12, 99, 4, 163, 24,
+        195, 56, 38, 16, 152, 196, 20, 166, 49, 131, 89, 204, 97, 30, 11, 88, 196, 18, 150, 177,
+        130, 85, 172, 97, 29, 27, 216, 196, 22, 182, 177, 131, 93, 236, 97, 31, 7, 56, 196, 17,
+        142, 113, 130, 83, 156, 225, 28, 23, 184, 196, 21, 174, 113, 131, 91, 220, 225, 30, 15,
+        120, 196, 19, 158, 241, 130, 87, 188, 225, 29, 31, 248, 196, 23, 114, 200, 163, 128, 34,
+        74, 40, 163, 130, 42, 106, 168, 163, 129, 38, 90, 104, 163, 131, 46, 122, 153, 155, 253,
+        223, 45, 125, 190, 51, 159, 135, 188, 95, 218, 129, 119, 29, 248, 63, 137, 55, 14, 239, 28,
+        254, 54, 188, 119, 100, 110, 30, 153, 219, 135, 247, 15, 55, 8, 119, 8, 183, 8, 247, 8, 55,
+        9, 119, 9, 183, 9, 247, 9, 55, 10, 119, 10, 183, 10, 247, 10, 55, 11, 119, 11, 183, 75,
+        187, 101, 183, 237, 142, 221, 181, 123, 118, 223, 30, 216, 67, 123, 100, 143, 237, 137, 61,
+        181, 103, 246, 220, 94, 216, 75, 123, 101, 175, 237, 141, 189, 181, 119, 246, 222, 62, 216,
+        71, 251, 100, 159, 237, 139, 125, 181, 111, 246, 221, 126, 216, 79, 251, 101, 115, 54, 111,
+        11, 182, 104, 75, 182, 108, 43, 182, 154, 217, 49, 125, 106, 126, 175, 219, 134, 109, 218,
+        150, 109, 219, 142, 237, 218, 158, 237, 219, 244, 29, 252, 5, 110, 27, 118, 9, 212, 3, 0,
+        0,
     ];
 
-    assert_eq!(bytes, expected_serialization)
+    assert_eq!(bytes, expected_serialization);
+
+    assert_eq!(circuit.blackbox_functions(), BTreeSet::from([BlackBoxFunc::SchnorrVerify]));
+    assert_eq!(circuit.serialized_size().unwrap(), bytes.len());
 }
 
 #[test]
@@ -188,7 +353,10 @@ fn simple_brillig_foreign_call() {
             function: "invert".into(),
             destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
             inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            destination_types: None,
+            input_types: None,
         }],
+        trap_messages: vec![],
         predicate: None,
     };
 
@@ -204,14 +372,19 @@ fn simple_brillig_foreign_call() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 173, 143, 65, 10, 0, 32, 8, 4, 205, 32, 122, 142, 253,
-        160, 207, 116, 232, 210, 33, 162, 247, 23, 100, 96, 32, 93, 106, 64, 92, 92, 144, 93, 15,
-        0, 6, 22, 86, 104, 201, 190, 69, 222, 244, 70, 48, 255, 126, 145, 204, 139, 74, 102, 63,
-        199, 177, 206, 165, 167, 218, 110, 13, 15, 80, 152, 168, 248, 3, 190, 43, 105, 200, 59, 1,
-        0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 141, 141, 65, 10, 0, 32, 8,
+        4, 119, 11, 162, 103, 244, 185, 14, 93, 58, 68, 244, 254, 130, 58, 24, 9, 57, 32, 14, 42,
+        110, 4, 224, 176, 161, 112, 9, 79, 247, 194, 181, 125, 130, 9, 210, 126, 123, 229, 57, 101,
+        30, 87, 133, 227, 165, 142, 220, 58, 127, 111, 94, 38, 255, 228, 232, 105, 5, 1, 0, 0,
     ];
 
-    assert_eq!(bytes, expected_serialization)
+    assert_eq!(bytes, expected_serialization);
+
+    let Opcode::Brillig(brillig_data) = &circuit.opcodes[0] else { unreachable!() };
+    assert_eq!(
+        brillig::disassemble(&brillig_data.bytecode),
+        "0: FOREIGN_CALL invert (r0) -> (r0)\n"
+    );
 }
 
 #[test]
@@ -264,8 +437,11 @@ fn complex_brillig_foreign_call() {
                     RegisterOrMemory::RegisterIndex(RegisterIndex::from(1)),
                     RegisterOrMemory::RegisterIndex(RegisterIndex::from(2)),
                 ],
+                destination_types: None,
+                input_types: None,
             },
         ],
+        trap_messages: vec![],
         predicate: None,
     };
 
@@ -281,16 +457,24 @@ fn complex_brillig_foreign_call() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 213, 83, 219, 10, 128, 48, 8, 245, 210, 101, 159, 179,
-        254, 160, 127, 137, 222, 138, 122, 236, 243, 91, 228, 64, 44, 232, 33, 7, 117, 64, 156,
-        206, 201, 193, 51, 3, 0, 32, 156, 224, 100, 36, 103, 148, 88, 35, 215, 245, 226, 227, 59,
-        116, 232, 215, 43, 150, 226, 72, 63, 224, 200, 5, 56, 230, 255, 240, 81, 189, 61, 117, 113,
-        157, 31, 223, 236, 79, 149, 172, 78, 214, 72, 220, 138, 15, 106, 214, 168, 114, 249, 126,
-        88, 230, 117, 26, 55, 54, 37, 90, 26, 155, 39, 227, 31, 223, 232, 230, 4, 215, 157, 63,
-        176, 3, 89, 64, 134, 157, 36, 4, 0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 181, 82, 91, 10, 128, 48,
+        12, 75, 87, 31, 59, 134, 119, 19, 255, 20, 253, 244, 248, 78, 214, 65, 41, 8, 173, 176, 64,
+        104, 155, 101, 100, 27, 203, 0, 24, 21, 84, 152, 212, 172, 65, 82, 89, 60, 77, 227, 15,
+        223, 2, 23, 136, 252, 222, 223, 25, 9, 253, 51, 24, 241, 140, 23, 28, 204, 9, 188, 87, 228,
+        222, 161, 243, 235, 255, 210, 250, 161, 112, 44, 156, 100, 158, 165, 102, 29, 162, 180,
+        182, 190, 158, 199, 181, 111, 55, 27, 139, 142, 177, 122, 178, 141, 103, 83, 197, 3, 51,
+        216, 108, 69, 238, 2, 0, 0,
     ];
 
-    assert_eq!(bytes, expected_serialization)
+    assert_eq!(bytes, expected_serialization);
+
+    let mut uncompressed_bytes = Vec::new();
+    circuit.write_uncompressed(&mut uncompressed_bytes).unwrap();
+    assert!(uncompressed_bytes.len() > bytes.len());
+    assert_eq!(Circuit::read_uncompressed(uncompressed_bytes.as_slice()).unwrap(), circuit);
+    assert_eq!(Circuit::read(bytes.as_slice()).unwrap(), circuit);
+    #[cfg(feature = "cbor")]
+    assert_eq!(Circuit::from_cbor(&circuit.to_cbor()).unwrap(), circuit);
 }
 
 #[test]
@@ -320,21 +504,152 @@ fn memory_op_circuit() {
     circuit.write(&mut bytes).unwrap();
 
     let expected_serialization: Vec<u8> = vec![
-        31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 213, 146, 49, 14, 0, 32, 8, 3, 171, 192, 127, 240, 7,
-        254, 255, 85, 134, 136, 9, 131, 155, 48, 216, 165, 76, 165, 92, 16, 0, 132, 45, 113, 239,
-        238, 205, 103, 198, 93, 211, 93, 223, 52, 170, 115, 91, 114, 46, 229, 101, 105, 53, 92,
-        253, 13, 46, 103, 222, 78, 161, 164, 125, 50, 5, 16, 167, 184, 45, 92, 160, 252, 96, 232,
-        6, 3, 0, 0,
+        65, 67, 73, 82, 7, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 165, 143, 91, 10, 0, 32, 8,
+        4, 215, 212, 123, 116, 255, 83, 86, 84, 32, 209, 135, 143, 1, 17, 197, 1, 87, 1, 48, 54,
+        52, 171, 153, 249, 238, 22, 98, 246, 122, 122, 123, 60, 193, 159, 14, 23, 84, 245, 40, 232,
+        177, 255, 182, 28, 46, 235, 165, 195, 9, 2, 191, 13, 167, 74, 95, 143, 6, 2, 0, 0,
     ];
 
-    assert_eq!(bytes, expected_serialization)
+    assert_eq!(bytes, expected_serialization);
+
+    assert_eq!(
+        circuit.memory_stats(),
+        MemoryStats { num_blocks: 1, max_block_size: 2, total_cells: 2 }
+    );
+    assert_eq!(
+        circuit.memory_blocks(),
+        BTreeMap::from([(BlockId(0), BlockInfo { size: Some(2), num_reads: 1, num_writes: 1 })])
+    );
+
+    // Without its initialization, the contents of the block become an input to the memory operations.
+    let memory_ops = circuit.extract_range(1..3);
+    let block_init =
+        Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(6), Witness(7)] };
+    assert_eq!(
+        memory_ops,
+        Circuit {
+            current_witness_index: 7,
+            opcodes: vec![block_init, circuit.opcodes[1].clone(), circuit.opcodes[2].clone()],
+            private_parameters: BTreeSet::from([Witness(3), Witness(6), Witness(7)]),
+            return_values: PublicInputs([Witness(4)].into()),
+            ..Circuit::default()
+        }
+    );
 }
 
+#[test]
+fn nop_circuit() {
+    let addition = Opcode::Arithmetic(&(Witness(1) + Witness(2)) - Witness(3));
+
+    let circuit = Circuit {
+        current_witness_index: 3,
+        opcodes: vec![Opcode::Nop { tag: 1 }, addition, Opcode::Nop { tag: u32::MAX }],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        return_values: PublicInputs([Witness(3)].into()),
+        ..Circuit::default()
+    };
+    let mut bytes = Vec::new();
+    circuit.write(&mut bytes).unwrap();
+
+    assert_eq!(Circuit::read(&*bytes).unwrap(), circuit);
+}
+
+/// Bytecode of a circuit serialized by nargo before the serialization header was introduced.
+const NARGO_BYTECODE: &str = "H4sIAAAAAAAA/7WTMRLEIAhFMYkp9ywgGrHbq6yz5v5H2JkdCyaxC9LgWDw+H9gBwMM91p7fPeOzIKdYjEeMLYdGTB8MpUrCmOohJJQkfYMwN4mSSy0ZC0VudKbCZ4cthqzVrsc/yw28dMZeWmrWerfBexnsxD6hJ7jUufr4GvyZFp8xpG0C14Pd8s/q29vPCBXypvmpDx7sD8opnfqIfsM1RNtxBQAA";
+
 #[test]
 fn deserialize_from_nargo() {
-    const BYTECODE: &str = "H4sIAAAAAAAA/7WTMRLEIAhFMYkp9ywgGrHbq6yz5v5H2JkdCyaxC9LgWDw+H9gBwMM91p7fPeOzIKdYjEeMLYdGTB8MpUrCmOohJJQkfYMwN4mSSy0ZC0VudKbCZ4cthqzVrsc/yw28dMZeWmrWerfBexnsxD6hJ7jUufr4GvyZFp8xpG0C14Pd8s/q29vPCBXypvmpDx7sD8opnfqIfsM1RNtxBQAA";
-    let circuit_bytes_compressed =
-        base64::engine::general_purpose::STANDARD.decode(BYTECODE).unwrap();
-    let circuit = Circuit::read(circuit_bytes_compressed.as_slice()).unwrap();
-    println!("{:?}", circuit)
+    // This bytecode predates the serialization header so must be rejected as-is.
+    let err = Circuit::from_base64(NARGO_BYTECODE).unwrap_err();
+    assert!(matches!(err, CircuitReadError::Header(SerializationError::MissingHeader)));
+
+    let circuit = Circuit::read(nargo_bytecode().as_slice()).unwrap();
+    println!("{}", circuit.to_pretty_string())
+}
+
+/// The circuit of [`NARGO_BYTECODE`] serialized at the current version of the format.
+const NARGO_CIRCUIT: &str = "QUNJUgcAAAAfiwgAAAAAAAD/nVI7DoJAEH0jn9IzbKmdxiMYEytjaWPnAYydJUcgXIBTEDgOHSUNLSHhO1k2YXZfsTvZzOz7ZEIAhAHE6h0WBFjDG28FEYhY7+nz+JXn9Jg/b1kUvd6HS3X/F9/4WjZJPXJLez25hr6XTOIsvdhwcj7fUPOcp0xDB02t9mDyuucD+g8KMqL5gF1gvsNcAPkiuOriy61gD51zSyf3tIUOWuTu0J0DAAA=";
+
+/// Returns the bytecode of [`deserialize_from_nargo`] serialized at the current version of the format.
+fn nargo_bytecode() -> Vec<u8> {
+    base64::engine::general_purpose::STANDARD.decode(NARGO_CIRCUIT).unwrap()
+}
+
+#[test]
+fn read_from_generic_reader() {
+    fn read_generic(reader: impl std::io::Read) -> Circuit {
+        Circuit::read(reader).unwrap()
+    }
+
+    let bytes = nargo_bytecode();
+    let circuit = read_generic(std::io::Cursor::new(bytes.clone()));
+    assert_eq!(circuit, Circuit::read(bytes.as_slice()).unwrap());
+}
+
+#[test]
+fn from_base64_matches_read() {
+    let bytes = nargo_bytecode();
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+
+    let circuit = Circuit::from_base64(&encoded).unwrap();
+    assert_eq!(circuit, Circuit::read(bytes.as_slice()).unwrap());
+    assert_eq!(Circuit::from_hex(&hex::encode(&bytes)).unwrap(), circuit);
+}
+
+#[test]
+fn stream_opcodes_from_nargo() {
+    let circuit = Circuit::read(nargo_bytecode().as_slice()).unwrap();
+
+    let bytes = nargo_bytecode();
+    let mut reader = CircuitStreamReader::new(bytes.as_slice()).unwrap();
+    assert_eq!(reader.current_witness_index(), circuit.current_witness_index);
+    assert_eq!(reader.num_opcodes(), circuit.opcodes.len() as u64);
+
+    // The parameters are available before any opcodes have been read.
+    let parameters = reader.parameters().clone();
+    assert_eq!(parameters.private_parameters, circuit.private_parameters);
+    assert_eq!(parameters.public_parameters, circuit.public_parameters);
+    assert_eq!(parameters.return_values, circuit.return_values);
+
+    let opcodes: Vec<Opcode> = (&mut reader).collect::<Result<_, _>>().unwrap();
+    assert_eq!(opcodes, circuit.opcodes);
+    assert_eq!(reader.finish().unwrap(), parameters);
+}
+
+#[test]
+fn read_truncated_bytecode() {
+    let bytes = nargo_bytecode();
+
+    // Truncating the compressed stream leaves the decoder without enough data to decode the circuit.
+    let err = Circuit::read(&bytes[..bytes.len() / 2]).unwrap_err();
+    assert!(
+        matches!(err, CircuitReadError::Bincode { consumed_bytes, .. } if consumed_bytes > 0),
+        "unexpected error: {err:?}"
+    );
+
+    // Truncating the gzip footer leaves the circuit intact but the stream incomplete.
+    let err = Circuit::read(&bytes[..bytes.len() - 4]).unwrap_err();
+    assert!(matches!(err, CircuitReadError::Gzip(_)), "unexpected error: {err:?}");
+}
+
+#[test]
+fn read_bytecode_from_previous_version() {
+    // `simple_brillig_foreign_call` as serialized at version 1 of the format.
+    let bytes: Vec<u8> = vec![
+        65, 67, 73, 82, 1, 0, 0, 0, 31, 139, 8, 0, 0, 0, 0, 0, 0, 255, 173, 143, 193, 10, 0, 16,
+        16, 68, 103, 41, 249, 28, 254, 192, 207, 56, 184, 56, 72, 190, 159, 66, 86, 237, 141, 87,
+        219, 78, 115, 152, 102, 44, 0, 194, 68, 51, 205, 217, 94, 88, 223, 189, 225, 233, 95, 150,
+        227, 125, 149, 208, 217, 142, 51, 75, 167, 220, 98, 169, 210, 66, 113, 246, 9, 36, 220,
+        225, 155, 14, 131, 105, 226, 71, 61, 1, 0, 0,
+    ];
+
+    let err = Circuit::read(bytes.as_slice()).unwrap_err();
+    assert!(
+        matches!(
+            err,
+            CircuitReadError::Header(SerializationError::UnsupportedVersion { found: 1, expected })
+                if expected == ACIR_SERIALIZATION_VERSION
+        ),
+        "unexpected error: {err:?}"
+    );
 }
@@ -2,6 +2,25 @@ use ark_ff::PrimeField;
 use ark_ff::Zero;
 use num_bigint::BigUint;
 use serde::{Deserialize, Serialize};
+use subtle::ConstantTimeEq;
+use thiserror::Error;
+
+/// Error returned when converting bytes into a [`FieldElement`] fails.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FromBytesError {
+    #[error("expected {expected} bytes but got {actual}")]
+    InvalidLength { expected: usize, actual: usize },
+    #[error("bytes do not represent a value less than the field modulus")]
+    NotReduced,
+}
+
+/// Error returned when attempting to invert a zero [`FieldElement`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("cannot invert the zero element at index {index}")]
+pub struct ZeroInverse {
+    /// The index of the first zero element in the slice being inverted.
+    pub index: usize,
+}
 
 // XXX: Switch out for a trait and proper implementations
 // This implementation is in-efficient, can definitely remove hex usage and Iterator instances for trivial functionality
@@ -91,44 +110,121 @@ impl<F: PrimeField> PartialEq for FieldElement<F> {
     }
 }
 
+/// Negative values are mapped to `p - |a|`, the inverse of [`FieldElement::to_i128`].
 impl<F: PrimeField> From<i128> for FieldElement<F> {
-    fn from(mut a: i128) -> FieldElement<F> {
-        let mut negative = false;
+    fn from(a: i128) -> FieldElement<F> {
+        // `unsigned_abs` avoids overflowing on `i128::MIN`.
+        let magnitude = FieldElement::from(a.unsigned_abs());
         if a < 0 {
-            a = -a;
-            negative = true;
-        }
-
-        let mut result = match F::from_str(&a.to_string()) {
-            Ok(result) => result,
-            Err(_) => panic!("Cannot convert i128 as a string to a field element"),
-        };
-
-        if negative {
-            result = -result;
+            -magnitude
+        } else {
+            magnitude
         }
-        FieldElement(result)
     }
 }
 
+/// Field elements are serialized as their canonical decimal representation in human-readable formats such as JSON,
+/// so that they can be consumed by non-Rust clients. Binary formats encode them as byte strings holding their
+/// [big-endian representation][FieldElement::to_be_bytes].
+///
+/// Earlier versions wrote unprefixed fixed-width hex strings in every format, so a decimal string of that same width
+/// would be ambiguous. The few values with such a decimal representation are written as `0x` prefixed hex instead.
 impl<T: ark_ff::PrimeField> Serialize for FieldElement<T> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        self.to_hex().serialize(serializer)
+        if !serializer.is_human_readable() {
+            return serializer.serialize_bytes(&self.to_be_bytes());
+        }
+        let hex = self.to_hex();
+        let decimal = self.to_decimal_string();
+        if decimal.len() == hex.len() {
+            format!("0x{hex}").serialize(serializer)
+        } else {
+            decimal.serialize(serializer)
+        }
     }
 }
 
+/// `0x` prefixed strings are always read as hex. Unprefixed strings are read as hex if they have the fixed width of
+/// [`FieldElement::to_hex`], as written by earlier versions, and otherwise as decimal. Binary formats are read as
+/// big-endian byte strings. Values which are not less than the field modulus are rejected.
 impl<'de, T: ark_ff::PrimeField> Deserialize<'de> for FieldElement<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let s = <&str>::deserialize(deserializer)?;
-        match Self::from_hex(s) {
-            Some(value) => Ok(value),
-            None => Err(serde::de::Error::custom(format!("Invalid hex for FieldElement: {s}",))),
+        if !deserializer.is_human_readable() {
+            return deserializer.deserialize_bytes(BytesVisitor(std::marker::PhantomData));
+        }
+        let s = String::deserialize(deserializer)?;
+        let is_fixed_width_hex = s.len() == Self::zero().to_hex().len();
+        let value = match s.strip_prefix("0x") {
+            Some(hex) => Self::from_canonical_str_radix(hex, 16),
+            None if !is_fixed_width_hex => Self::from_canonical_str_radix(&s, 10),
+            None => Self::from_canonical_str_radix(&s, 16),
+        };
+        value.ok_or_else(|| serde::de::Error::custom(format!("Invalid FieldElement: {s}")))
+    }
+}
+
+/// Reads a [`FieldElement`] from the byte string written by binary formats.
+struct BytesVisitor<F>(std::marker::PhantomData<F>);
+
+impl<'de, F: PrimeField> serde::de::Visitor<'de> for BytesVisitor<F> {
+    type Value = FieldElement<F>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("the big-endian bytes of a field element")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        FieldElement::from_be_bytes(bytes)
+            .map_err(|err| E::custom(format!("Invalid FieldElement: {err}")))
+    }
+}
+
+/// Serializes [`FieldElement`]s as `0x` prefixed hex strings regardless of the format being used.
+///
+/// This can be used as an alternative to the default decimal representation in human-readable formats
+/// with `#[serde(with = "acir_field::serde_hex")]`.
+pub mod serde_hex {
+    use ark_ff::PrimeField;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::FieldElement;
+
+    pub fn serialize<F: PrimeField, S: Serializer>(
+        value: &FieldElement<F>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        format!("0x{}", value.to_hex()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, F: PrimeField, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FieldElement<F>, D::Error> {
+        if deserializer.is_human_readable() {
+            return FieldElement::deserialize(deserializer);
+        }
+        // Binary formats read field elements as bytes by default, rather than the string written above.
+        let s = String::deserialize(deserializer)?;
+        s.strip_prefix("0x")
+            .and_then(|hex| FieldElement::from_canonical_str_radix(hex, 16))
+            .ok_or_else(|| serde::de::Error::custom(format!("Invalid FieldElement: {s}")))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a, F: PrimeField> arbitrary::Arbitrary<'a> for FieldElement<F> {
+    /// Generates small integers as often as arbitrary field elements, as these are far more common in circuits.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        if u.arbitrary()? {
+            Ok(FieldElement::from(u.arbitrary::<i128>()?))
+        } else {
+            let bytes: [u8; 32] = u.arbitrary()?;
+            Ok(FieldElement::from_be_bytes_reduce(&bytes))
         }
     }
 }
@@ -168,7 +264,17 @@ impl<F: PrimeField> FieldElement<F> {
         self == &Self::one()
     }
 
-    pub fn pow(&self, exponent: &Self) -> Self {
+    /// Raises this field element to the power of `exponent` using square-and-multiply.
+    ///
+    /// Following the usual field convention, `0^0` is defined to be `1`.
+    pub fn pow(&self, exponent: u64) -> Self {
+        FieldElement(self.0.pow([exponent]))
+    }
+
+    /// Raises this field element to the power of `exponent`, interpreting `exponent` as an integer.
+    ///
+    /// Following the usual field convention, `0^0` is defined to be `1`.
+    pub fn pow_field(&self, exponent: &Self) -> Self {
         FieldElement(self.0.pow(exponent.0.into_bigint()))
     }
 
@@ -194,9 +300,17 @@ impl<F: PrimeField> FieldElement<F> {
         }
     }
 
+    /// Returns the prime modulus of the field.
     pub fn modulus() -> BigUint {
         F::MODULUS.into()
     }
+
+    /// Returns the name of the field which this crate has been compiled for, e.g. `"bn254"`.
+    ///
+    /// This can be used to reject values which were produced for a circuit over a different field.
+    pub const fn field_name() -> &'static str {
+        crate::CHOSEN_FIELD.name()
+    }
     /// Returns None, if the string is not a canonical
     /// representation of a field element; less than the order
     /// or if the hex string is invalid.
@@ -237,6 +351,27 @@ impl<F: PrimeField> FieldElement<F> {
         (self.num_bits() <= 64).then(|| self.to_u128() as u64)
     }
 
+    /// Interprets the field element as a signed integer, where elements above `p / 2` represent the negative
+    /// value `x - p`, as produced by `FieldElement::from(i128)`.
+    ///
+    /// Returns `None` if the signed value does not fit in an `i128`.
+    // Takes `&self` for consistency with the other fallible integer conversions such as `try_to_u64`.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_i128(&self) -> Option<i128> {
+        if let Some(value) = self.try_into_u128() {
+            return i128::try_from(value).ok();
+        }
+        let magnitude = (-*self).try_into_u128()?;
+        // `i128::MIN` has a magnitude of 2^127, which wraps to itself when negated.
+        (magnitude <= 1 << 127).then(|| (magnitude as i128).wrapping_neg())
+    }
+
+    /// Returns whether the element lies above `p / 2`, i.e. whether it represents a negative value when the field is
+    /// interpreted as signed integers in the range `(-p / 2, p / 2]`.
+    pub fn is_negative(&self) -> bool {
+        (-*self).0.into_bigint() < self.0.into_bigint()
+    }
+
     /// Computes the inverse or returns zero if the inverse does not exist
     /// Before using this FieldElement, please ensure that this behavior is necessary
     pub fn inverse(&self) -> FieldElement<F> {
@@ -248,6 +383,54 @@ impl<F: PrimeField> FieldElement<F> {
         self.0.inverse_in_place().map(|f| FieldElement(*f))
     }
 
+    /// Returns whether this field element is equal to `other`, in constant time.
+    ///
+    /// Unlike `==`, the limbs of the canonical representations of both elements are compared using [`subtle`], so
+    /// the time taken does not reveal where the elements first differ.
+    /// This should be used when comparing values derived from secrets.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = (self.0.into_bigint(), other.0.into_bigint());
+        lhs.as_ref().ct_eq(rhs.as_ref()).into()
+    }
+
+    /// Returns a square root of this field element, or `None` if it is not a quadratic residue.
+    ///
+    /// Of the two roots `r` and `-r`, the one which is smaller when interpreted as an integer is returned.
+    pub fn sqrt(&self) -> Option<Self> {
+        let root = FieldElement(ark_ff::Field::sqrt(&self.0)?);
+        Some(std::cmp::min(root, -root))
+    }
+
+    /// Replaces each element of `elems` with its inverse.
+    ///
+    /// This uses Montgomery's trick so that only a single field inversion is performed
+    /// regardless of the number of elements, which is much cheaper than inverting each element individually.
+    ///
+    /// If any element is zero then an error is returned and `elems` is left unmodified.
+    pub fn batch_invert(elems: &mut [FieldElement<F>]) -> Result<(), ZeroInverse> {
+        if let Some(index) = elems.iter().position(|elem| elem.is_zero()) {
+            return Err(ZeroInverse { index });
+        }
+
+        // `prefix_products[i]` holds the product of the first `i` elements.
+        let mut prefix_products = Vec::with_capacity(elems.len());
+        let mut product = F::one();
+        for elem in elems.iter() {
+            prefix_products.push(product);
+            product *= elem.0;
+        }
+
+        // Every element is nonzero so their product is invertible.
+        let mut inverse = product.inverse().expect("product of nonzero elements is nonzero");
+        for (elem, prefix_product) in elems.iter_mut().zip(prefix_products).rev() {
+            // `inverse` is the inverse of the product of all elements up to and including `elem`.
+            let elem_inverse = inverse * prefix_product;
+            inverse *= elem.0;
+            elem.0 = elem_inverse;
+        }
+        Ok(())
+    }
+
     // XXX: This method is used while this field element
     // implementation is not generic.
     pub fn into_repr(self) -> F {
@@ -260,22 +443,73 @@ impl<F: PrimeField> FieldElement<F> {
         bytes.reverse();
         hex::encode(bytes)
     }
+    /// Returns the canonical decimal representation of this field element, i.e. the integer in `[0, p)`.
+    ///
+    /// Unlike the [`Display`][std::fmt::Display] implementation, this never uses negative or exponent notation.
+    pub fn to_decimal_string(self) -> String {
+        BigUint::from_bytes_be(&self.to_be_bytes()).to_str_radix(10)
+    }
+
+    /// Parses an integer in the given radix, returning `None` if it is invalid or not less than the field modulus.
+    fn from_canonical_str_radix(input: &str, radix: u32) -> Option<FieldElement<F>> {
+        if input.is_empty() || !input.chars().all(|c| c.is_digit(radix)) {
+            return None;
+        }
+        let value = BigUint::parse_bytes(input.as_bytes(), radix)?;
+        (value < Self::modulus()).then(|| FieldElement::from_be_bytes_reduce(&value.to_bytes_be()))
+    }
+
     pub fn from_hex(hex_str: &str) -> Option<FieldElement<F>> {
         let value = hex_str.strip_prefix("0x").unwrap_or(hex_str);
         let hex_as_bytes = hex::decode(value).ok()?;
         Some(FieldElement::from_be_bytes_reduce(&hex_as_bytes))
     }
 
+    /// Returns the big-endian representation of this field element.
+    ///
+    /// The output is always [`max_num_bytes`][FieldElement::max_num_bytes] long.
     pub fn to_be_bytes(self) -> Vec<u8> {
-        // to_be_bytes! uses little endian which is why we reverse the output
-        // TODO: Add a little endian equivalent, so the caller can use whichever one
-        // TODO they desire
+        let mut bytes = self.to_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Returns the little-endian representation of this field element.
+    ///
+    /// The output is always [`max_num_bytes`][FieldElement::max_num_bytes] long.
+    pub fn to_le_bytes(self) -> Vec<u8> {
         let mut bytes = Vec::new();
         self.0.serialize_uncompressed(&mut bytes).unwrap();
-        bytes.reverse();
         bytes
     }
 
+    /// Converts the big-endian representation of a field element, as returned by [`to_be_bytes`][FieldElement::to_be_bytes],
+    /// back into a field element.
+    ///
+    /// Returns an error if `bytes` is not [`max_num_bytes`][FieldElement::max_num_bytes] long
+    /// or does not encode a value less than the field modulus.
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<FieldElement<F>, FromBytesError> {
+        let expected = Self::max_num_bytes() as usize;
+        if bytes.len() != expected {
+            return Err(FromBytesError::InvalidLength { expected, actual: bytes.len() });
+        }
+        if BigUint::from_bytes_be(bytes) >= Self::modulus() {
+            return Err(FromBytesError::NotReduced);
+        }
+        Ok(FieldElement::from_be_bytes_reduce(bytes))
+    }
+
+    /// Converts the little-endian representation of a field element, as returned by [`to_le_bytes`][FieldElement::to_le_bytes],
+    /// back into a field element.
+    ///
+    /// Returns an error if `bytes` is not [`max_num_bytes`][FieldElement::max_num_bytes] long
+    /// or does not encode a value less than the field modulus.
+    pub fn from_le_bytes(bytes: &[u8]) -> Result<FieldElement<F>, FromBytesError> {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_be_bytes(&bytes)
+    }
+
     /// Converts bytes into a FieldElement and applies a
     /// reduction if needed.
     pub fn from_be_bytes_reduce(bytes: &[u8]) -> FieldElement<F> {
@@ -436,11 +670,271 @@ mod tests {
             assert_eq!(minus_i_field_element.to_hex(), string)
         }
     }
+    #[test]
+    fn byte_conversions_round_trip() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        // The largest power of two which can be represented in `max_num_bits` bits without exceeding the modulus.
+        let largest_power_of_two =
+            FieldElement::from(2_i128).pow(FieldElement::max_num_bits() as u64 - 1);
+        let values =
+            [FieldElement::zero(), FieldElement::one(), largest_power_of_two, -FieldElement::one()];
+
+        for value in values {
+            let be_bytes = value.to_be_bytes();
+            let le_bytes = value.to_le_bytes();
+            assert_eq!(be_bytes.len(), FieldElement::max_num_bytes() as usize);
+            assert_eq!(le_bytes.iter().rev().copied().collect::<Vec<_>>(), be_bytes);
+
+            assert_eq!(FieldElement::from_be_bytes(&be_bytes), Ok(value));
+            assert_eq!(FieldElement::from_le_bytes(&le_bytes), Ok(value));
+        }
+
+        assert_eq!(FieldElement::one().to_le_bytes()[0], 1);
+        assert_eq!(FieldElement::one().to_be_bytes()[31], 1);
+    }
+
+    #[test]
+    fn from_bytes_rejects_invalid_input() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+        use super::FromBytesError;
+
+        let modulus = FieldElement::modulus().to_bytes_be();
+        assert_eq!(FieldElement::from_be_bytes(&modulus), Err(FromBytesError::NotReduced));
+        assert_eq!(FieldElement::from_be_bytes(&[0xff; 32]), Err(FromBytesError::NotReduced));
+        assert_eq!(
+            FieldElement::from_le_bytes(&[1]),
+            Err(FromBytesError::InvalidLength { expected: 32, actual: 1 })
+        );
+    }
+
+    #[test]
+    fn batch_invert_matches_individual_inverses() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        // Pseudorandom values generated by a linear congruential generator, along with edge values.
+        let mut state = 0x2545_f491_4f6c_dd1d_u128;
+        let mut elems: Vec<FieldElement> = (0..32)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                FieldElement::from(state)
+            })
+            .collect();
+        elems.extend([FieldElement::one(), -FieldElement::one(), FieldElement::from(2_i128)]);
+
+        let expected: Vec<_> = elems.iter().map(|elem| elem.inverse()).collect();
+        FieldElement::batch_invert(&mut elems).unwrap();
+        assert_eq!(elems, expected);
+
+        let mut empty: Vec<FieldElement> = Vec::new();
+        assert_eq!(FieldElement::batch_invert(&mut empty), Ok(()));
+    }
+
+    #[test]
+    fn batch_invert_rejects_zero() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+        use super::ZeroInverse;
+
+        let original = vec![FieldElement::from(3_i128), FieldElement::zero(), FieldElement::one()];
+        let mut elems = original.clone();
+        assert_eq!(FieldElement::batch_invert(&mut elems), Err(ZeroInverse { index: 1 }));
+        assert_eq!(elems, original);
+    }
+
+    #[test]
+    fn pow_small_cases() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let two = FieldElement::from(2_i128);
+        assert_eq!(two.pow(10), FieldElement::from(1024_i128));
+        assert_eq!(two.pow_field(&FieldElement::from(10_i128)), FieldElement::from(1024_i128));
+
+        for x in [FieldElement::zero(), FieldElement::one(), two, -FieldElement::one()] {
+            assert_eq!(x.pow(0), FieldElement::one());
+            assert_eq!(x.pow_field(&FieldElement::zero()), FieldElement::one());
+            assert_eq!(x.pow(1), x);
+        }
+    }
+
+    #[test]
+    fn pow_satisfies_fermats_little_theorem() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        // `p - 1` is `-1` when interpreted as a field element.
+        let p_minus_one = -FieldElement::one();
+        for x in [1_i128, 2, 3, 12345, -7] {
+            assert_eq!(FieldElement::from(x).pow_field(&p_minus_one), FieldElement::one());
+        }
+        assert_eq!(FieldElement::zero().pow_field(&p_minus_one), FieldElement::zero());
+    }
+
+    #[test]
+    fn ct_eq_matches_eq() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let values = [
+            FieldElement::zero(),
+            FieldElement::one(),
+            FieldElement::from(256_i128),
+            FieldElement::from(u128::MAX),
+            -FieldElement::one(),
+            -FieldElement::from(256_i128),
+        ];
+        for lhs in values {
+            for rhs in values {
+                assert_eq!(lhs.ct_eq(&rhs), lhs == rhs, "{lhs} and {rhs}");
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_of_residues_and_non_residues() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        assert_eq!(FieldElement::from(4_i128).sqrt(), Some(FieldElement::from(2_i128)));
+        assert_eq!(FieldElement::zero().sqrt(), Some(FieldElement::zero()));
+        // 5 generates the multiplicative group of the BN254 scalar field and so is not a square.
+        assert_eq!(FieldElement::from(5_i128).sqrt(), None);
+
+        for x in [1_i128, 2, 9, 12345, -1, -7] {
+            let square = FieldElement::from(x) * FieldElement::from(x);
+            let root = square.sqrt().unwrap();
+            assert_eq!(root * root, square);
+            // The smaller of the two roots is always returned.
+            assert!(root <= -root);
+        }
+    }
+
+    #[test]
+    fn serde_json_uses_decimal_strings() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let five = FieldElement::from(5_u128);
+        assert_eq!(serde_json::to_string(&five).unwrap(), "\"5\"");
+
+        let values = [FieldElement::zero(), five, -FieldElement::one()];
+        for value in values {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, format!("\"{}\"", value.to_decimal_string()));
+            assert_eq!(serde_json::from_str::<FieldElement>(&json).unwrap(), value);
+        }
+
+        assert_eq!(
+            serde_json::from_str::<FieldElement>("\"0x0a\"").unwrap(),
+            FieldElement::from(10_u128)
+        );
+        assert_eq!(
+            serde_json::from_str::<FieldElement>("\"10\"").unwrap(),
+            FieldElement::from(10_u128)
+        );
+    }
+
+    #[test]
+    fn serde_json_reads_legacy_hex_strings() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        // Written before field elements were serialized as decimal strings.
+        let legacy_json = r#"[
+            "0000000000000000000000000000000000000000000000000000000000000010",
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000"
+        ]"#;
+        let values: Vec<FieldElement> = serde_json::from_str(legacy_json).unwrap();
+        assert_eq!(values, vec![FieldElement::from(16_u128), -FieldElement::one()]);
+
+        let json = serde_json::to_string(&values).unwrap();
+        assert_eq!(json, format!("[\"16\",\"{}\"]", (-FieldElement::one()).to_decimal_string()));
+        assert_eq!(serde_json::from_str::<Vec<FieldElement>>(&json).unwrap(), values);
+
+        // A decimal representation as wide as the legacy hex strings is written as hex to avoid ambiguity.
+        let wide = FieldElement::from(10_u128).pow(63);
+        assert_eq!(wide.to_decimal_string().len(), 64);
+        let json = serde_json::to_string(&wide).unwrap();
+        assert_eq!(json, format!("\"0x{}\"", wide.to_hex()));
+        assert_eq!(serde_json::from_str::<FieldElement>(&json).unwrap(), wide);
+    }
+
+    #[test]
+    fn serde_json_rejects_unreduced_values() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let modulus = FieldElement::modulus();
+        let decimal = format!("\"{}\"", modulus.to_str_radix(10));
+        let hex = format!("\"0x{}\"", modulus.to_str_radix(16));
+        assert!(serde_json::from_str::<FieldElement>(&decimal).is_err());
+        assert!(serde_json::from_str::<FieldElement>(&hex).is_err());
+        assert!(serde_json::from_str::<FieldElement>("\"-1\"").is_err());
+        assert!(serde_json::from_str::<FieldElement>("\"\"").is_err());
+    }
+
+    #[test]
+    fn serde_hex_round_trip() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct Wrapper(#[serde(with = "crate::generic_ark::serde_hex")] FieldElement);
+
+        let value = Wrapper(FieldElement::from(255_u128));
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, "\"0x00000000000000000000000000000000000000000000000000000000000000ff\"");
+        assert_eq!(serde_json::from_str::<Wrapper>(&json).unwrap(), value);
+    }
+
+    #[test]
+    fn i128_conversions_round_trip() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let minus_one = FieldElement::from(-1_i128);
+        assert_eq!(
+            minus_one.to_hex(),
+            "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000"
+        );
+        assert_eq!(minus_one.to_i128(), Some(-1));
+
+        for value in [0, 1, 42, -42, i128::MAX, i128::MIN] {
+            assert_eq!(FieldElement::from(value).to_i128(), Some(value));
+        }
+        assert_eq!((FieldElement::from(i128::MAX) + FieldElement::one()).to_i128(), None);
+        assert_eq!((FieldElement::from(i128::MIN) - FieldElement::one()).to_i128(), None);
+    }
+
+    #[test]
+    fn is_negative_splits_field_at_half_modulus() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        assert!(!FieldElement::zero().is_negative());
+        assert!(!FieldElement::from(42_i128).is_negative());
+        assert!(FieldElement::from(-42_i128).is_negative());
+
+        // (p - 1) / 2 is the largest positive value, with its successor being its negation.
+        let half = -FieldElement::one() / FieldElement::from(2_i128);
+        assert!(!half.is_negative());
+        assert!((half + FieldElement::one()).is_negative());
+        assert_eq!(half + FieldElement::one(), -half);
+    }
+
     #[test]
     fn max_num_bits_smoke() {
         let max_num_bits_bn254 = crate::generic_ark::FieldElement::<ark_bn254::Fr>::max_num_bits();
         assert_eq!(max_num_bits_bn254, 254)
     }
+
+    #[test]
+    fn field_metadata_matches_bn254() {
+        type FieldElement = crate::generic_ark::FieldElement<ark_bn254::Fr>;
+
+        let bn254_modulus = num_bigint::BigUint::parse_bytes(
+            b"21888242871839275222246405745257275088548364400416034343698204186575808495617",
+            10,
+        )
+        .unwrap();
+        assert_eq!(FieldElement::modulus(), bn254_modulus);
+        assert_eq!(FieldElement::max_num_bits() as u64, bn254_modulus.bits());
+        assert_eq!(FieldElement::field_name(), "bn254");
+
+        // The largest element requires every bit, unlike small elements.
+        assert_eq!((-FieldElement::one()).num_bits(), FieldElement::max_num_bits());
+        assert_eq!(FieldElement::from(5_i128).num_bits(), 3);
+    }
 }
 
 fn mask_vector_le(bytes: &mut [u8], num_bits: usize) {
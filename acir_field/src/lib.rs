@@ -5,11 +5,13 @@ cfg_if::cfg_if! {
     if #[cfg(feature = "bn254")] {
         mod generic_ark;
         pub type FieldElement = generic_ark::FieldElement<ark_bn254::Fr>;
+        pub use generic_ark::{serde_hex, FromBytesError, ZeroInverse};
         pub const CHOSEN_FIELD : FieldOptions = FieldOptions::BN254;
 
     } else if #[cfg(feature = "bls12_381")] {
         mod generic_ark;
         pub type FieldElement = generic_ark::FieldElement<ark_bls12_381::Fr>;
+        pub use generic_ark::{serde_hex, FromBytesError, ZeroInverse};
         pub const CHOSEN_FIELD : FieldOptions = FieldOptions::BLS12_381;
     } else {
         compile_error!("please specify a field to compile with");
@@ -22,6 +24,16 @@ pub enum FieldOptions {
     BLS12_381,
 }
 
+impl FieldOptions {
+    /// Returns the name of the field, e.g. `"bn254"`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FieldOptions::BN254 => "bn254",
+            FieldOptions::BLS12_381 => "bls12_381",
+        }
+    }
+}
+
 // This is needed because features are additive through the dependency graph; if a dependency turns on the bn254, then it
 // will be turned on in all crates that depend on it
 #[macro_export]
@@ -172,6 +172,8 @@ pub fn compile(
                     }
                     acir::circuit::opcodes::BlackBoxFuncCall::RANGE { .. } => (),
                     acir::circuit::opcodes::BlackBoxFuncCall::SHA256 { outputs, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::Sha512 { outputs, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::Sha384 { outputs, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::Keccak256 { outputs, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::Keccak256VariableLength {
                         outputs,
@@ -198,8 +200,10 @@ pub fn compile(
                         output,
                         ..
                     }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::Poseidon { output, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::EcdsaSecp256k1 { output, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::EcdsaSecp256r1 { output, .. }
+                    | acir::circuit::opcodes::BlackBoxFuncCall::EddsaVerify { output, .. }
                     | acir::circuit::opcodes::BlackBoxFuncCall::SchnorrVerify { output, .. } => {
                         transformer.mark_solvable(*output)
                     }
@@ -228,8 +232,8 @@ pub fn compile(
                 new_acir_opcode_positions.push(acir_opcode_positions[index]);
                 transformed_opcodes.push(opcode.clone());
             }
-            Opcode::MemoryInit { .. } => {
-                // `MemoryInit` does not write values to the `WitnessMap`
+            Opcode::MemoryInit { .. } | Opcode::Nop { .. } => {
+                // `MemoryInit` and `Nop` do not write values to the `WitnessMap`
                 new_acir_opcode_positions.push(acir_opcode_positions[index]);
                 transformed_opcodes.push(opcode.clone());
             }
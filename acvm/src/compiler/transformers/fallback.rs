@@ -21,8 +21,11 @@ impl FallbackTransformer {
 
         for (idx, opcode) in acir.opcodes.into_iter().enumerate() {
             match &opcode {
-                Opcode::Arithmetic(_) | Opcode::Directive(_) | Opcode::Brillig(_) => {
-                    // directive, arithmetic expression or blocks are handled by acvm
+                Opcode::Arithmetic(_)
+                | Opcode::Directive(_)
+                | Opcode::Brillig(_)
+                | Opcode::Nop { .. } => {
+                    // directive, arithmetic expression, nop or blocks are handled by acvm
                     new_opcode_positions.push(opcode_positions[idx]);
                     acir_supported_opcodes.push(opcode);
                     continue;
@@ -4,7 +4,9 @@
 pub mod compiler;
 pub mod pwg;
 
-pub use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
+pub use acvm_blackbox_solver::{
+    BlackBoxFunctionSolver, BlackBoxResolutionError, DefaultBlackBoxSolver,
+};
 use core::fmt::Debug;
 use pwg::OpcodeResolutionError;
 
@@ -3,8 +3,17 @@ use acir::{
     FieldElement,
 };
 
+use thiserror::Error;
+
 use super::{insert_value, ErrorLocation, OpcodeNotSolvable, OpcodeResolutionError};
 
+/// A recognized form of arithmetic constraint which is not satisfied by the witness assignments.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum ConstraintError {
+    #[error("Witness _{} with value {value} is not boolean", witness.witness_index())]
+    NotBoolean { witness: Witness, value: FieldElement },
+}
+
 /// An Arithmetic solver will take a Circuit's arithmetic opcodes with witness assignments
 /// and create the other witness variables
 pub(super) struct ArithmeticSolver;
@@ -26,9 +35,9 @@ impl ArithmeticSolver {
     /// Derives the rest of the witness based on the initial low level variables
     pub(super) fn solve(
         initial_witness: &mut WitnessMap,
-        opcode: &Expression,
+        expression: &Expression,
     ) -> Result<(), OpcodeResolutionError> {
-        let opcode = &ArithmeticSolver::evaluate(opcode, initial_witness);
+        let opcode = &ArithmeticSolver::evaluate(expression, initial_witness);
         // Evaluate multiplication term
         let mul_result = ArithmeticSolver::solve_mul_term(opcode, initial_witness);
         // Evaluate the fan-in terms
@@ -46,9 +55,7 @@ impl ArithmeticSolver {
                     let total_sum = a + opcode.q_c;
                     if (q + b).is_zero() {
                         if !total_sum.is_zero() {
-                            Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                                opcode_location: ErrorLocation::Unresolved,
-                            })
+                            Err(unsatisfied_constraint(expression, initial_witness))
                         } else {
                             Ok(())
                         }
@@ -76,9 +83,7 @@ impl ArithmeticSolver {
                 let total_sum = sum + opcode.q_c;
                 if partial_prod.is_zero() {
                     if !total_sum.is_zero() {
-                        Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                            opcode_location: ErrorLocation::Unresolved,
-                        })
+                        Err(unsatisfied_constraint(expression, initial_witness))
                     } else {
                         Ok(())
                     }
@@ -93,9 +98,7 @@ impl ArithmeticSolver {
                 // All the variables in the MulTerm are solved and the Fan-in is also solved
                 // There is nothing to solve
                 if !(a + b + opcode.q_c).is_zero() {
-                    Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                        opcode_location: ErrorLocation::Unresolved,
-                    })
+                    Err(unsatisfied_constraint(expression, initial_witness))
                 } else {
                     Ok(())
                 }
@@ -110,9 +113,7 @@ impl ArithmeticSolver {
                 let total_sum = total_prod + partial_sum + opcode.q_c;
                 if coeff.is_zero() {
                     if !total_sum.is_zero() {
-                        Err(OpcodeResolutionError::UnsatisfiedConstrain {
-                            opcode_location: ErrorLocation::Unresolved,
-                        })
+                        Err(unsatisfied_constraint(expression, initial_witness))
                     } else {
                         Ok(())
                     }
@@ -239,6 +240,69 @@ impl ArithmeticSolver {
     }
 }
 
+/// Builds the error for an `expression` which is not satisfied by the witness assignments.
+///
+/// The expression is split into `lhs == rhs` by moving the terms with negative coefficients to the right hand side.
+/// Terms containing unassigned witnesses are skipped as their contributions must cancel out for the expression to be unsatisfiable.
+///
+/// The opcode location is resolved by the ACVM as the arithmetic solver has no knowledge of it.
+fn unsatisfied_constraint(
+    expression: &Expression,
+    initial_witness: &WitnessMap,
+) -> OpcodeResolutionError {
+    if let Some((witness, &value)) =
+        boolean_witness(expression).and_then(|w| initial_witness.get(&w).map(|value| (w, value)))
+    {
+        return OpcodeResolutionError::ConstraintError {
+            opcode_location: ErrorLocation::Unresolved,
+            error: ConstraintError::NotBoolean { witness, value },
+        };
+    }
+
+    let mut lhs = FieldElement::zero();
+    let mut rhs = FieldElement::zero();
+    let mut add_term = |coefficient: FieldElement, value: Option<FieldElement>| {
+        if let Some(value) = value {
+            if coefficient.is_negative() {
+                rhs -= coefficient * value;
+            } else {
+                lhs += coefficient * value;
+            }
+        }
+    };
+
+    for (coefficient, w_l, w_r) in &expression.mul_terms {
+        let value = initial_witness
+            .get(w_l)
+            .and_then(|w_l| initial_witness.get(w_r).map(|w_r| *w_l * *w_r));
+        add_term(*coefficient, value);
+    }
+    for (coefficient, witness) in &expression.linear_combinations {
+        add_term(*coefficient, initial_witness.get(witness).copied());
+    }
+    add_term(expression.q_c, Some(FieldElement::one()));
+
+    OpcodeResolutionError::UnsatisfiedConstrain {
+        opcode_location: ErrorLocation::Unresolved,
+        expression: Some(Box::new(expression.clone())),
+        lhs,
+        rhs,
+    }
+}
+
+/// Returns the witness constrained by `expression` if it has the form `q * (w * w - w) == 0`,
+/// as built by [`Opcode::boolean_constraint`][acir::circuit::Opcode::boolean_constraint].
+fn boolean_witness(expression: &Expression) -> Option<Witness> {
+    match (expression.mul_terms.as_slice(), expression.linear_combinations.as_slice()) {
+        ([(q_m, w_l, w_r)], [(q_l, w)])
+            if w_l == w_r && w_l == w && !q_m.is_zero() && (*q_m + *q_l).is_zero() =>
+        {
+            expression.q_c.is_zero().then_some(*w)
+        }
+        _ => None,
+    }
+}
+
 #[test]
 fn arithmetic_smoke_test() {
     let a = Witness(0);
@@ -9,7 +9,7 @@ use crate::{
 };
 
 pub(super) fn fixed_base_scalar_mul(
-    backend: &impl BlackBoxFunctionSolver,
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     low: FunctionInput,
     high: FunctionInput,
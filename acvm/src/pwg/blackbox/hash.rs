@@ -3,43 +3,63 @@ use acir::{
     native_types::{Witness, WitnessMap},
     BlackBoxFunc, FieldElement,
 };
-use acvm_blackbox_solver::{hash_to_field_128_security, BlackBoxResolutionError};
+use acvm_blackbox_solver::BlackBoxResolutionError;
 
 use crate::pwg::{insert_value, witness_to_value};
-use crate::OpcodeResolutionError;
+use crate::{BlackBoxFunctionSolver, OpcodeResolutionError};
 
 /// Attempts to solve a `HashToField128Security` opcode
 /// If successful, `initial_witness` will be mutated to contain the new witness assignment.
 pub(super) fn solve_hash_to_field(
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     inputs: &[FunctionInput],
     output: &Witness,
 ) -> Result<(), OpcodeResolutionError> {
     let message_input = get_hash_input(initial_witness, inputs, None)?;
-    let field = hash_to_field_128_security(&message_input)?;
+    let field = backend.hash_to_field_128_security(&message_input)?;
 
     insert_value(output, field, initial_witness)?;
 
     Ok(())
 }
 
-/// Attempts to solve a 256 bit hash function opcode.
+/// Attempts to solve a `Poseidon` opcode
 /// If successful, `initial_witness` will be mutated to contain the new witness assignment.
-pub(super) fn solve_generic_256_hash_opcode(
+pub(super) fn solve_poseidon(
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
+    initial_witness: &mut WitnessMap,
+    inputs: &[FunctionInput],
+    output: &Witness,
+) -> Result<(), OpcodeResolutionError> {
+    let inputs: Vec<FieldElement> = inputs
+        .iter()
+        .map(|input| witness_to_value(initial_witness, input.witness).copied())
+        .collect::<Result<_, _>>()?;
+    let hash = backend.poseidon(&inputs)?;
+
+    insert_value(output, hash, initial_witness)?;
+
+    Ok(())
+}
+
+/// Attempts to solve a hash function opcode with an `N` byte digest.
+/// If successful, `initial_witness` will be mutated to contain the new witness assignment.
+pub(super) fn solve_generic_hash_opcode<const N: usize>(
     initial_witness: &mut WitnessMap,
     inputs: &[FunctionInput],
     var_message_size: Option<&FunctionInput>,
     outputs: &[Witness],
-    hash_function: fn(data: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError>,
+    hash_function: impl FnOnce(&[u8]) -> Result<[u8; N], BlackBoxResolutionError>,
     black_box_func: BlackBoxFunc,
 ) -> Result<(), OpcodeResolutionError> {
     let message_input = get_hash_input(initial_witness, inputs, var_message_size)?;
-    let digest: [u8; 32] = hash_function(&message_input)?;
+    let digest: [u8; N] = hash_function(&message_input)?;
 
-    let outputs: [Witness; 32] = outputs.try_into().map_err(|_| {
+    let outputs: [Witness; N] = outputs.try_into().map_err(|_| {
         OpcodeResolutionError::BlackBoxFunctionFailed(
             black_box_func,
-            format!("Expected 32 outputs but encountered {}", outputs.len()),
+            format!("Expected {N} outputs but encountered {}", outputs.len()),
         )
     })?;
     write_digest_to_outputs(initial_witness, outputs, digest)?;
@@ -86,10 +106,10 @@ fn get_hash_input(
 }
 
 /// Writes a `digest` to the [`WitnessMap`] at witness indices `outputs`.
-fn write_digest_to_outputs(
+fn write_digest_to_outputs<const N: usize>(
     initial_witness: &mut WitnessMap,
-    outputs: [Witness; 32],
-    digest: [u8; 32],
+    outputs: [Witness; N],
+    digest: [u8; N],
 ) -> Result<(), OpcodeResolutionError> {
     for (output_witness, value) in outputs.iter().zip(digest.into_iter()) {
         insert_value(
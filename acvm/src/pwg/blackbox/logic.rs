@@ -1,4 +1,5 @@
-use crate::pwg::{insert_value, witness_to_value};
+use super::BlackBoxError;
+use crate::pwg::{insert_value, witness_to_value, ErrorLocation};
 use crate::OpcodeResolutionError;
 use acir::{
     circuit::opcodes::FunctionInput,
@@ -14,12 +15,8 @@ pub(super) fn and(
     rhs: &FunctionInput,
     output: &Witness,
 ) -> Result<(), OpcodeResolutionError> {
-    assert_eq!(
-        lhs.num_bits, rhs.num_bits,
-        "number of bits specified for each input must be the same"
-    );
-    solve_logic_opcode(initial_witness, &lhs.witness, &rhs.witness, *output, |left, right| {
-        left.and(right, lhs.num_bits)
+    solve_logic_opcode(initial_witness, lhs, rhs, *output, |left, right, num_bits| {
+        left.and(right, num_bits)
     })
 }
 
@@ -31,26 +28,52 @@ pub(super) fn xor(
     rhs: &FunctionInput,
     output: &Witness,
 ) -> Result<(), OpcodeResolutionError> {
-    assert_eq!(
-        lhs.num_bits, rhs.num_bits,
-        "number of bits specified for each input must be the same"
-    );
-    solve_logic_opcode(initial_witness, &lhs.witness, &rhs.witness, *output, |left, right| {
-        left.xor(right, lhs.num_bits)
+    solve_logic_opcode(initial_witness, lhs, rhs, *output, |left, right, num_bits| {
+        left.xor(right, num_bits)
     })
 }
 
 /// Derives the rest of the witness based on the initial low level variables
+///
+/// Both inputs must declare the same bit width and their values must fit within it.
+/// The result is masked to this width.
 fn solve_logic_opcode(
     initial_witness: &mut WitnessMap,
-    a: &Witness,
-    b: &Witness,
+    lhs: &FunctionInput,
+    rhs: &FunctionInput,
     result: Witness,
-    logic_op: impl Fn(&FieldElement, &FieldElement) -> FieldElement,
+    logic_op: impl Fn(&FieldElement, &FieldElement, u32) -> FieldElement,
 ) -> Result<(), OpcodeResolutionError> {
-    let w_l_value = witness_to_value(initial_witness, *a)?;
-    let w_r_value = witness_to_value(initial_witness, *b)?;
-    let assignment = logic_op(w_l_value, w_r_value);
+    if lhs.num_bits != rhs.num_bits {
+        return Err(OpcodeResolutionError::BlackBoxError {
+            opcode_location: ErrorLocation::Unresolved,
+            error: BlackBoxError::MismatchedBitWidth { lhs: lhs.num_bits, rhs: rhs.num_bits },
+        });
+    }
+    let num_bits = lhs.num_bits;
+
+    let w_l_value = input_value(initial_witness, lhs)?;
+    let w_r_value = input_value(initial_witness, rhs)?;
+    let assignment = logic_op(w_l_value, w_r_value, num_bits);
 
     insert_value(&result, assignment, initial_witness)
 }
+
+/// Returns the value assigned to `input`, checking that it fits within the input's declared bit width.
+fn input_value<'w>(
+    initial_witness: &'w WitnessMap,
+    input: &FunctionInput,
+) -> Result<&'w FieldElement, OpcodeResolutionError> {
+    let value = witness_to_value(initial_witness, input.witness)?;
+    if value.num_bits() > input.num_bits {
+        return Err(OpcodeResolutionError::BlackBoxError {
+            opcode_location: ErrorLocation::Unresolved,
+            error: BlackBoxError::RangeCheckFailed {
+                witness: input.witness,
+                value: *value,
+                num_bits: input.num_bits,
+            },
+        });
+    }
+    Ok(value)
+}
@@ -1,12 +1,11 @@
+use super::{insert_value, OpcodeNotSolvable, OpcodeResolutionError};
+use crate::BlackBoxFunctionSolver;
 use acir::{
     circuit::opcodes::{BlackBoxFuncCall, FunctionInput},
     native_types::{Witness, WitnessMap},
     FieldElement,
 };
-use acvm_blackbox_solver::{blake2s, keccak256, sha256};
-
-use super::{insert_value, OpcodeNotSolvable, OpcodeResolutionError};
-use crate::BlackBoxFunctionSolver;
+use thiserror::Error;
 
 mod fixed_base_scalar_mul;
 mod hash;
@@ -17,15 +16,25 @@ mod signature;
 
 use fixed_base_scalar_mul::fixed_base_scalar_mul;
 // Hash functions should eventually be exposed for external consumers.
-use hash::{solve_generic_256_hash_opcode, solve_hash_to_field};
+use hash::{solve_generic_hash_opcode, solve_hash_to_field, solve_poseidon};
 use logic::{and, xor};
 use pedersen::pedersen;
 use range::solve_range_opcode;
 use signature::{
     ecdsa::{secp256k1_prehashed, secp256r1_prehashed},
+    eddsa::eddsa_verify,
     schnorr::schnorr_verify,
 };
 
+/// A black box function call whose inputs do not satisfy its constraints.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum BlackBoxError {
+    #[error("Witness _{} with value {value} does not fit within {num_bits} bits", witness.witness_index())]
+    RangeCheckFailed { witness: Witness, value: FieldElement, num_bits: u32 },
+    #[error("Inputs of a bitwise operation have mismatched bit widths of {lhs} and {rhs}")]
+    MismatchedBitWidth { lhs: u32, rhs: u32 },
+}
+
 /// Check if all of the inputs to the function have assignments
 ///
 /// Returns the first missing assignment if any are missing
@@ -48,7 +57,7 @@ fn contains_all_inputs(witness_assignments: &WitnessMap, inputs: &[FunctionInput
 }
 
 pub(crate) fn solve(
-    backend: &impl BlackBoxFunctionSolver,
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     bb_func: &BlackBoxFuncCall,
 ) -> Result<(), OpcodeResolutionError> {
@@ -65,42 +74,61 @@ pub(crate) fn solve(
         BlackBoxFuncCall::AND { lhs, rhs, output } => and(initial_witness, lhs, rhs, output),
         BlackBoxFuncCall::XOR { lhs, rhs, output } => xor(initial_witness, lhs, rhs, output),
         BlackBoxFuncCall::RANGE { input } => solve_range_opcode(initial_witness, input),
-        BlackBoxFuncCall::SHA256 { inputs, outputs } => solve_generic_256_hash_opcode(
+        BlackBoxFuncCall::SHA256 { inputs, outputs } => solve_generic_hash_opcode(
             initial_witness,
             inputs,
             None,
             outputs,
-            sha256,
+            |bytes| backend.sha256(bytes),
             bb_func.get_black_box_func(),
         ),
-        BlackBoxFuncCall::Blake2s { inputs, outputs } => solve_generic_256_hash_opcode(
+        BlackBoxFuncCall::Sha512 { inputs, outputs } => solve_generic_hash_opcode(
             initial_witness,
             inputs,
             None,
             outputs,
-            blake2s,
+            |bytes| backend.sha512(bytes),
             bb_func.get_black_box_func(),
         ),
-        BlackBoxFuncCall::Keccak256 { inputs, outputs } => solve_generic_256_hash_opcode(
+        BlackBoxFuncCall::Sha384 { inputs, outputs } => solve_generic_hash_opcode(
             initial_witness,
             inputs,
             None,
             outputs,
-            keccak256,
+            |bytes| backend.sha384(bytes),
+            bb_func.get_black_box_func(),
+        ),
+        BlackBoxFuncCall::Blake2s { inputs, outputs } => solve_generic_hash_opcode(
+            initial_witness,
+            inputs,
+            None,
+            outputs,
+            |bytes| backend.blake2s(bytes),
+            bb_func.get_black_box_func(),
+        ),
+        BlackBoxFuncCall::Keccak256 { inputs, outputs } => solve_generic_hash_opcode(
+            initial_witness,
+            inputs,
+            None,
+            outputs,
+            |bytes| backend.keccak256(bytes),
             bb_func.get_black_box_func(),
         ),
         BlackBoxFuncCall::Keccak256VariableLength { inputs, var_message_size, outputs } => {
-            solve_generic_256_hash_opcode(
+            solve_generic_hash_opcode(
                 initial_witness,
                 inputs,
                 Some(var_message_size),
                 outputs,
-                keccak256,
+                |bytes| backend.keccak256(bytes),
                 bb_func.get_black_box_func(),
             )
         }
         BlackBoxFuncCall::HashToField128Security { inputs, output } => {
-            solve_hash_to_field(initial_witness, inputs, output)
+            solve_hash_to_field(backend, initial_witness, inputs, output)
+        }
+        BlackBoxFuncCall::Poseidon { inputs, output } => {
+            solve_poseidon(backend, initial_witness, inputs, output)
         }
         BlackBoxFuncCall::SchnorrVerify {
             public_key_x,
@@ -127,6 +155,7 @@ pub(crate) fn solve(
             hashed_message: message,
             output,
         } => secp256k1_prehashed(
+            backend,
             initial_witness,
             public_key_x,
             public_key_y,
@@ -141,6 +170,7 @@ pub(crate) fn solve(
             hashed_message: message,
             output,
         } => secp256r1_prehashed(
+            backend,
             initial_witness,
             public_key_x,
             public_key_y,
@@ -148,6 +178,9 @@ pub(crate) fn solve(
             message,
             *output,
         ),
+        BlackBoxFuncCall::EddsaVerify { public_key, signature, message, output } => {
+            eddsa_verify(backend, initial_witness, public_key, signature, message, *output)
+        }
         BlackBoxFuncCall::FixedBaseScalarMul { low, high, outputs } => {
             fixed_base_scalar_mul(backend, initial_witness, *low, *high, *outputs)
         }
@@ -9,7 +9,7 @@ use crate::{
 };
 
 pub(super) fn pedersen(
-    backend: &impl BlackBoxFunctionSolver,
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     inputs: &[FunctionInput],
     domain_separator: u32,
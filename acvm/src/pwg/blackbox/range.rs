@@ -1,3 +1,4 @@
+use super::BlackBoxError;
 use crate::{
     pwg::{witness_to_value, ErrorLocation},
     OpcodeResolutionError,
@@ -10,8 +11,13 @@ pub(super) fn solve_range_opcode(
 ) -> Result<(), OpcodeResolutionError> {
     let w_value = witness_to_value(initial_witness, input.witness)?;
     if w_value.num_bits() > input.num_bits {
-        return Err(OpcodeResolutionError::UnsatisfiedConstrain {
+        return Err(OpcodeResolutionError::BlackBoxError {
             opcode_location: ErrorLocation::Unresolved,
+            error: BlackBoxError::RangeCheckFailed {
+                witness: input.witness,
+                value: *w_value,
+                num_bits: input.num_bits,
+            },
         });
     }
     Ok(())
@@ -1,15 +1,14 @@
+use crate::{pwg::insert_value, BlackBoxFunctionSolver, OpcodeResolutionError};
 use acir::{
     circuit::opcodes::FunctionInput,
     native_types::{Witness, WitnessMap},
     FieldElement,
 };
-use acvm_blackbox_solver::{ecdsa_secp256k1_verify, ecdsa_secp256r1_verify};
-
-use crate::{pwg::insert_value, OpcodeResolutionError};
 
 use super::to_u8_vec;
 
 pub(crate) fn secp256k1_prehashed(
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     public_key_x_inputs: &[FunctionInput],
     public_key_y_inputs: &[FunctionInput],
@@ -44,13 +43,15 @@ pub(crate) fn secp256k1_prehashed(
             )
         })?;
 
-    let is_valid = ecdsa_secp256k1_verify(&hashed_message, &pub_key_x, &pub_key_y, &signature)?;
+    let is_valid =
+        backend.ecdsa_secp256k1_verify(&hashed_message, &pub_key_x, &pub_key_y, &signature)?;
 
     insert_value(&output, FieldElement::from(is_valid), initial_witness)?;
     Ok(())
 }
 
 pub(crate) fn secp256r1_prehashed(
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     public_key_x_inputs: &[FunctionInput],
     public_key_y_inputs: &[FunctionInput],
@@ -84,7 +85,8 @@ pub(crate) fn secp256r1_prehashed(
             )
         })?;
 
-    let is_valid = ecdsa_secp256r1_verify(&hashed_message, &pub_key_x, &pub_key_y, &signature)?;
+    let is_valid =
+        backend.ecdsa_secp256r1_verify(&hashed_message, &pub_key_x, &pub_key_y, &signature)?;
 
     insert_value(&output, FieldElement::from(is_valid), initial_witness)?;
     Ok(())
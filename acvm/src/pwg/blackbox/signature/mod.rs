@@ -18,4 +18,5 @@ fn to_u8_vec(
 }
 
 pub(super) mod ecdsa;
+pub(super) mod eddsa;
 pub(super) mod schnorr;
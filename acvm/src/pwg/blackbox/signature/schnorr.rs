@@ -11,7 +11,7 @@ use acir::{
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn schnorr_verify(
-    backend: &impl BlackBoxFunctionSolver,
+    backend: &(impl BlackBoxFunctionSolver + ?Sized),
     initial_witness: &mut WitnessMap,
     public_key_x: FunctionInput,
     public_key_y: FunctionInput,
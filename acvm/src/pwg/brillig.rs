@@ -1,5 +1,7 @@
+use std::time::Instant;
+
 use acir::{
-    brillig::{RegisterIndex, Value},
+    brillig::{ForeignCallParam, RegisterIndex, Value},
     circuit::{
         brillig::{Brillig, BrilligInputs, BrilligOutputs},
         OpcodeLocation,
@@ -17,11 +19,12 @@ use super::{get_value, insert_value};
 pub(super) struct BrilligSolver;
 
 impl BrilligSolver {
-    pub(super) fn solve<B: BlackBoxFunctionSolver>(
+    pub(super) fn solve<B: BlackBoxFunctionSolver + ?Sized>(
         initial_witness: &mut WitnessMap,
         brillig: &Brillig,
         bb_solver: &B,
         acir_index: usize,
+        deadline: Option<Instant>,
     ) -> Result<Option<ForeignCallWaitInfo>, OpcodeResolutionError> {
         // If the predicate is `None`, then we simply return the value 1
         // If the predicate is `Some` but we cannot find a value, then we return stalled
@@ -82,7 +85,8 @@ impl BrilligSolver {
             brillig.bytecode.clone(),
             brillig.foreign_call_results.clone(),
             bb_solver,
-        );
+        )
+        .with_deadline(deadline);
 
         // Run the Brillig VM on these inputs, bytecode, etc!
         let vm_status = vm.process_opcodes();
@@ -111,9 +115,9 @@ impl BrilligSolver {
                 Ok(None)
             }
             VMStatus::InProgress => unreachable!("Brillig VM has not completed execution"),
-            VMStatus::Failure { message, call_stack } => {
+            VMStatus::Failure { error, call_stack } => {
                 Err(OpcodeResolutionError::BrilligFunctionFailed {
-                    message,
+                    error,
                     call_stack: call_stack
                         .iter()
                         .map(|brillig_index| OpcodeLocation::Brillig {
@@ -159,5 +163,5 @@ pub struct ForeignCallWaitInfo {
     /// An identifier interpreted by the caller process
     pub function: String,
     /// Resolved inputs to a foreign call computed in the previous steps of a Brillig VM process
-    pub inputs: Vec<Vec<Value>>,
+    pub inputs: Vec<ForeignCallParam>,
 }
@@ -65,8 +65,13 @@ pub(super) fn solve_directives(
             let decomposed_integer = big_integer.to_radix_le(*radix);
 
             if b.len() < decomposed_integer.len() {
+                // The value which can be represented by the available digits.
+                let truncated = big_integer % BigUint::from(*radix).pow(b.len() as u32);
                 return Err(OpcodeResolutionError::UnsatisfiedConstrain {
                     opcode_location: ErrorLocation::Unresolved,
+                    expression: None,
+                    lhs: value_a,
+                    rhs: FieldElement::from_be_bytes_reduce(&truncated.to_bytes_be()),
                 });
             }
 
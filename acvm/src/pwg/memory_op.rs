@@ -1,46 +1,72 @@
 use std::collections::HashMap;
 
 use acir::{
-    circuit::opcodes::MemOp,
+    circuit::opcodes::{BlockId, MemOp},
     native_types::{Expression, Witness, WitnessMap},
     FieldElement,
 };
+use thiserror::Error;
 
 use super::{arithmetic::ArithmeticSolver, get_value, insert_value, witness_to_value};
 use super::{ErrorLocation, OpcodeResolutionError};
 
 type MemoryIndex = u32;
 
+/// An invalid access to a block of memory.
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum MemoryError {
+    #[error("Index {index} is out of bounds for memory block {} of size {size}", block_id.0)]
+    IndexOutOfBounds { block_id: BlockId, index: FieldElement, size: u32 },
+    #[error("Memory block {} is accessed before being initialized", block_id.0)]
+    UninitializedBlock { block_id: BlockId },
+}
+
 /// Maintains the state for solving [`MemoryInit`][`acir::circuit::Opcode::MemoryInit`] and [`MemoryOp`][`acir::circuit::Opcode::MemoryOp`] opcodes.
-#[derive(Default)]
 pub(super) struct MemoryOpSolver {
+    block_id: BlockId,
     block_value: HashMap<MemoryIndex, FieldElement>,
+    /// The size of the block as given by its [`MemoryInit`][`acir::circuit::Opcode::MemoryInit`] opcode.
     block_len: u32,
 }
 
 impl MemoryOpSolver {
+    pub(super) fn new(block_id: BlockId) -> Self {
+        MemoryOpSolver { block_id, block_value: HashMap::new(), block_len: 0 }
+    }
+
+    /// Converts `index` into a [`MemoryIndex`], checking that it lies within the block.
+    fn checked_index(&self, index: FieldElement) -> Result<MemoryIndex, OpcodeResolutionError> {
+        match index.try_to_u64() {
+            Some(memory_index) if memory_index < self.block_len as u64 => {
+                Ok(memory_index as MemoryIndex)
+            }
+            _ => Err(OpcodeResolutionError::MemoryError {
+                opcode_location: ErrorLocation::Unresolved,
+                error: MemoryError::IndexOutOfBounds {
+                    block_id: self.block_id,
+                    index,
+                    size: self.block_len,
+                },
+            }),
+        }
+    }
+
     fn write_memory_index(
         &mut self,
-        index: MemoryIndex,
+        index: FieldElement,
         value: FieldElement,
     ) -> Result<(), OpcodeResolutionError> {
-        if index >= self.block_len {
-            return Err(OpcodeResolutionError::IndexOutOfBounds {
-                opcode_location: ErrorLocation::Unresolved,
-                index,
-                array_size: self.block_len,
-            });
-        }
+        let index = self.checked_index(index)?;
         self.block_value.insert(index, value);
         Ok(())
     }
 
-    fn read_memory_index(&self, index: MemoryIndex) -> Result<FieldElement, OpcodeResolutionError> {
-        self.block_value.get(&index).copied().ok_or(OpcodeResolutionError::IndexOutOfBounds {
-            opcode_location: ErrorLocation::Unresolved,
-            index,
-            array_size: self.block_len,
-        })
+    fn read_memory_index(
+        &self,
+        index: FieldElement,
+    ) -> Result<FieldElement, OpcodeResolutionError> {
+        let index = self.checked_index(index)?;
+        Ok(self.block_value[&index])
     }
 
     /// Set the block_value from a MemoryInit opcode
@@ -52,7 +78,7 @@ impl MemoryOpSolver {
         self.block_len = init.len() as u32;
         for (memory_index, witness) in init.iter().enumerate() {
             self.write_memory_index(
-                memory_index as MemoryIndex,
+                FieldElement::from(memory_index as u128),
                 *witness_to_value(initial_witness, *witness)?,
             )?;
         }
@@ -68,8 +94,7 @@ impl MemoryOpSolver {
         let operation = get_value(&op.operation, initial_witness)?;
 
         // Find the memory index associated with this memory operation.
-        let index = get_value(&op.index, initial_witness)?;
-        let memory_index = index.try_to_u64().unwrap() as MemoryIndex;
+        let memory_index = get_value(&op.index, initial_witness)?;
 
         // Calculate the value associated with this memory operation.
         //
@@ -128,12 +153,13 @@ mod tests {
     use std::collections::BTreeMap;
 
     use acir::{
-        circuit::opcodes::MemOp,
+        circuit::opcodes::{BlockId, MemOp},
         native_types::{Expression, Witness, WitnessMap},
         FieldElement,
     };
 
-    use super::MemoryOpSolver;
+    use super::{MemoryError, MemoryOpSolver};
+    use crate::pwg::OpcodeResolutionError;
 
     #[test]
     fn test_solver() {
@@ -150,7 +176,7 @@ mod tests {
             MemOp::read_at_mem_index(FieldElement::one().into(), Witness(4)),
         ];
 
-        let mut block_solver = MemoryOpSolver::default();
+        let mut block_solver = MemoryOpSolver::new(BlockId(0));
         block_solver.init(&init, &initial_witness).unwrap();
 
         for op in trace {
@@ -160,6 +186,34 @@ mod tests {
         assert_eq!(initial_witness[&Witness(4)], FieldElement::from(2u128));
     }
 
+    #[test]
+    fn test_computed_index() {
+        let mut initial_witness = WitnessMap::from(BTreeMap::from_iter([
+            (Witness(1), FieldElement::zero()),
+            (Witness(2), FieldElement::one()),
+            (Witness(3), FieldElement::from(5u128)),
+        ]));
+
+        // Write to and read from index `w1 + w2`.
+        let index = &Expression::from(Witness(1)) + &Expression::from(Witness(2));
+        let trace = vec![
+            MemOp::write_to_mem_index(index.clone(), Witness(3).into()),
+            MemOp::read_at_mem_index(index, Witness(4)),
+        ];
+
+        let mut block_solver = MemoryOpSolver::new(BlockId(0));
+        block_solver.init(&[Witness(1), Witness(2)], &initial_witness).unwrap();
+        for op in trace {
+            block_solver.solve_memory_op(&op, &mut initial_witness, &None).unwrap();
+        }
+
+        assert_eq!(
+            block_solver.read_memory_index(FieldElement::one()),
+            Ok(FieldElement::from(5u128))
+        );
+        assert_eq!(initial_witness[&Witness(4)], FieldElement::from(5u128));
+    }
+
     #[test]
     fn test_index_out_of_bounds() {
         let mut initial_witness = WitnessMap::from(BTreeMap::from_iter([
@@ -174,7 +228,7 @@ mod tests {
             MemOp::write_to_mem_index(FieldElement::from(1u128).into(), Witness(3).into()),
             MemOp::read_at_mem_index(FieldElement::from(2u128).into(), Witness(4)),
         ];
-        let mut block_solver = MemoryOpSolver::default();
+        let mut block_solver = MemoryOpSolver::new(BlockId(0));
         block_solver.init(&init, &initial_witness).unwrap();
         let mut err = None;
         for op in invalid_trace {
@@ -185,11 +239,34 @@ mod tests {
 
         assert!(matches!(
             err,
-            Some(crate::pwg::OpcodeResolutionError::IndexOutOfBounds {
-                opcode_location: _,
-                index: 2,
-                array_size: 2
-            })
+            Some(OpcodeResolutionError::MemoryError {
+                error: MemoryError::IndexOutOfBounds { block_id: BlockId(0), index, size: 2 },
+                ..
+            }) if index == FieldElement::from(2u128)
+        ));
+    }
+
+    #[test]
+    fn test_large_index_is_not_truncated() {
+        let mut initial_witness = WitnessMap::from(BTreeMap::from_iter([
+            (Witness(1), FieldElement::from(1u128)),
+            (Witness(2), FieldElement::from(1u128)),
+        ]));
+
+        let mut block_solver = MemoryOpSolver::new(BlockId(0));
+        block_solver.init(&[Witness(1), Witness(2)], &initial_witness).unwrap();
+
+        // This index would alias index 1 if it were truncated to 32 bits.
+        let index = FieldElement::from((1u128 << 32) + 1);
+        let op = MemOp::read_at_mem_index(index.into(), Witness(3));
+        let err = block_solver.solve_memory_op(&op, &mut initial_witness, &None).err();
+
+        assert!(matches!(
+            err,
+            Some(OpcodeResolutionError::MemoryError {
+                error: MemoryError::IndexOutOfBounds { index: err_index, size: 2, .. },
+                ..
+            }) if err_index == index
         ));
     }
 
@@ -207,7 +284,7 @@ mod tests {
             MemOp::write_to_mem_index(FieldElement::from(1u128).into(), Witness(3).into()),
             MemOp::read_at_mem_index(FieldElement::from(2u128).into(), Witness(4)),
         ];
-        let mut block_solver = MemoryOpSolver::default();
+        let mut block_solver = MemoryOpSolver::new(BlockId(0));
         block_solver.init(&init, &initial_witness).unwrap();
         let mut err = None;
         for op in invalid_trace {
@@ -239,7 +316,7 @@ mod tests {
             MemOp::read_at_mem_index(FieldElement::from(0u128).into(), Witness(4)),
             MemOp::read_at_mem_index(FieldElement::from(1u128).into(), Witness(5)),
         ];
-        let mut block_solver = MemoryOpSolver::default();
+        let mut block_solver = MemoryOpSolver::new(BlockId(0));
         block_solver.init(&init, &initial_witness).unwrap();
         let mut err = None;
         for op in invalid_trace {
@@ -1,6 +1,9 @@
 // Re-usable methods that backends can use to implement their PWG
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    time::Instant,
+};
 
 use acir::{
     brillig::ForeignCallResult,
@@ -9,6 +12,7 @@ use acir::{
     BlackBoxFunc, FieldElement,
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
+use brillig_vm::BrilligError;
 
 use self::{
     arithmetic::ArithmeticSolver, brillig::BrilligSolver, directives::solve_directives,
@@ -27,8 +31,14 @@ mod directives;
 // black box functions
 mod blackbox;
 mod memory_op;
+// solving independent partitions concurrently
+mod parallel;
 
+pub use arithmetic::ConstraintError;
+pub use blackbox::BlackBoxError;
 pub use brillig::ForeignCallWaitInfo;
+pub use memory_op::MemoryError;
+pub use parallel::{solve_parallel, ParallelSolveError};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ACVMStatus {
@@ -61,6 +71,39 @@ impl std::fmt::Display for ACVMStatus {
     }
 }
 
+/// The outcome of [`ACVM::solve_partial`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveResult {
+    /// All opcodes have been solved.
+    Solved,
+
+    /// An opcode could not be satisfied by the current witness assignments.
+    Failed(OpcodeResolutionError),
+
+    /// Every opcode which could be solved has been, but the remaining opcodes are missing witness assignments.
+    Stalled {
+        /// The indices of the opcodes which have not been solved, in execution order.
+        remaining_opcodes: Vec<usize>,
+        /// The unassigned witnesses which the remaining opcodes are waiting on.
+        ///
+        /// Witnesses which one of the remaining opcodes would assign a value to are excluded,
+        /// so supplying values for these witnesses allows solving to progress.
+        blocked_on: BTreeSet<Witness>,
+    },
+}
+
+/// Receives notifications about the progress of the ACVM when solving using [`ACVM::solve_with_observer`].
+///
+/// Observers only have read access to the opcodes being solved and so cannot affect the result of solving.
+pub trait SolveObserver {
+    /// Called after the opcode at `index` has been solved.
+    fn on_opcode_solved(&mut self, _index: usize, _opcode: &Opcode) {}
+
+    /// Called when solving halts on the opcode at `index` without solving it,
+    /// either due to a failure or to wait on a foreign call.
+    fn on_opcode_stalled(&mut self, _index: usize) {}
+}
+
 // This enum represents the different cases in which an
 // opcode can be unsolvable.
 // The most common being that one of its input has not been
@@ -103,14 +146,24 @@ pub enum OpcodeResolutionError {
     OpcodeNotSolvable(#[from] OpcodeNotSolvable),
     #[error("Backend does not currently support the {0} opcode. ACVM does not currently have a fallback for this opcode.")]
     UnsupportedBlackBoxFunc(BlackBoxFunc),
-    #[error("Cannot satisfy constraint")]
-    UnsatisfiedConstrain { opcode_location: ErrorLocation },
-    #[error("Index out of bounds, array has size {array_size:?}, but index was {index:?}")]
-    IndexOutOfBounds { opcode_location: ErrorLocation, index: u32, array_size: u32 },
+    #[error("Cannot satisfy constraint: expected {lhs} == {rhs}")]
+    UnsatisfiedConstrain {
+        opcode_location: ErrorLocation,
+        /// The arithmetic expression which is not equal to zero, if the constraint is an arithmetic opcode.
+        expression: Option<Box<Expression>>,
+        lhs: FieldElement,
+        rhs: FieldElement,
+    },
+    #[error("Cannot satisfy constraint: {error}")]
+    ConstraintError { opcode_location: ErrorLocation, error: ConstraintError },
+    #[error("Invalid memory access: {error}")]
+    MemoryError { opcode_location: ErrorLocation, error: MemoryError },
+    #[error("Cannot satisfy black box function call: {error}")]
+    BlackBoxError { opcode_location: ErrorLocation, error: BlackBoxError },
     #[error("Failed to solve blackbox function: {0}, reason: {1}")]
     BlackBoxFunctionFailed(BlackBoxFunc, String),
-    #[error("Failed to solve brillig function, reason: {message}")]
-    BrilligFunctionFailed { message: String, call_stack: Vec<OpcodeLocation> },
+    #[error("Failed to solve brillig function, reason: {error}")]
+    BrilligFunctionFailed { error: BrilligError, call_stack: Vec<OpcodeLocation> },
 }
 
 impl From<BlackBoxResolutionError> for OpcodeResolutionError {
@@ -126,7 +179,61 @@ impl From<BlackBoxResolutionError> for OpcodeResolutionError {
     }
 }
 
-pub struct ACVM<'backend, B: BlackBoxFunctionSolver> {
+/// An error which caused the ACVM to fail, classified by the stage of the solver which raised it.
+///
+/// Each variant carries the index of the opcode which was being solved. Errors which are not specific to a single
+/// stage, such as missing witness assignments or unsatisfied arithmetic opcodes which are not of a recognized form,
+/// are wrapped as [`AcvmError::Other`].
+#[derive(Clone, PartialEq, Eq, Debug, Error)]
+pub enum AcvmError {
+    #[error("Cannot satisfy black box function call of opcode {opcode_index}: {error}")]
+    BlackBox { opcode_index: usize, error: BlackBoxError },
+    #[error("Failed to solve brillig function of opcode {opcode_index}: {error}")]
+    Brillig { opcode_index: usize, error: BrilligError, call_stack: Vec<OpcodeLocation> },
+    #[error("Invalid memory access in opcode {opcode_index}: {error}")]
+    Memory { opcode_index: usize, error: MemoryError },
+    #[error("Cannot satisfy constraint of opcode {opcode_index}: {error}")]
+    Constraint { opcode_index: usize, error: ConstraintError },
+    #[error("Failed to solve opcode {opcode_index}: {error}")]
+    Other { opcode_index: usize, error: OpcodeResolutionError },
+    #[error("Deadline exceeded before solving opcode {opcode_index}")]
+    Timeout { opcode_index: usize },
+}
+
+impl AcvmError {
+    /// Classifies `error`, which was raised while solving the opcode at `opcode_index`.
+    pub fn new(opcode_index: usize, error: OpcodeResolutionError) -> Self {
+        match error {
+            OpcodeResolutionError::BlackBoxError { error, .. } => {
+                AcvmError::BlackBox { opcode_index, error }
+            }
+            OpcodeResolutionError::BrilligFunctionFailed { error, call_stack } => {
+                AcvmError::Brillig { opcode_index, error, call_stack }
+            }
+            OpcodeResolutionError::MemoryError { error, .. } => {
+                AcvmError::Memory { opcode_index, error }
+            }
+            OpcodeResolutionError::ConstraintError { error, .. } => {
+                AcvmError::Constraint { opcode_index, error }
+            }
+            error => AcvmError::Other { opcode_index, error },
+        }
+    }
+
+    /// Returns the index of the opcode which raised the error.
+    pub fn opcode_index(&self) -> usize {
+        match self {
+            AcvmError::BlackBox { opcode_index, .. }
+            | AcvmError::Brillig { opcode_index, .. }
+            | AcvmError::Memory { opcode_index, .. }
+            | AcvmError::Constraint { opcode_index, .. }
+            | AcvmError::Other { opcode_index, .. }
+            | AcvmError::Timeout { opcode_index } => *opcode_index,
+        }
+    }
+}
+
+pub struct ACVM<'backend, B: BlackBoxFunctionSolver + ?Sized> {
     status: ACVMStatus,
 
     backend: &'backend B,
@@ -138,11 +245,19 @@ pub struct ACVM<'backend, B: BlackBoxFunctionSolver> {
     opcodes: Vec<Opcode>,
     /// Index of the next opcode to be executed.
     instruction_pointer: usize,
+    /// Number of opcodes which have been solved so far.
+    resolved_opcodes: usize,
+    /// Indices of the opcodes which [`ACVM::solve_partial`] was unable to solve, in execution order.
+    stalled_opcodes: Vec<usize>,
+    /// Index of the opcode which caused the ACVM to fail, if any.
+    failed_opcode: Option<usize>,
+    /// The time after which Brillig opcodes stop executing, set while solving with [`ACVM::solve_with_deadline`].
+    deadline: Option<Instant>,
 
     witness_map: WitnessMap,
 }
 
-impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
+impl<'backend, B: BlackBoxFunctionSolver + ?Sized> ACVM<'backend, B> {
     pub fn new(backend: &'backend B, opcodes: Vec<Opcode>, initial_witness: WitnessMap) -> Self {
         let status = if opcodes.is_empty() { ACVMStatus::Solved } else { ACVMStatus::InProgress };
         ACVM {
@@ -151,6 +266,10 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
             block_solvers: HashMap::default(),
             opcodes,
             instruction_pointer: 0,
+            resolved_opcodes: 0,
+            stalled_opcodes: Vec::new(),
+            failed_opcode: None,
+            deadline: None,
             witness_map: initial_witness,
         }
     }
@@ -172,6 +291,27 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
         self.instruction_pointer
     }
 
+    /// Returns the number of opcodes which have been solved so far along with the total number of opcodes.
+    ///
+    /// This can be polled between calls to [`ACVM::solve_opcode`] to report progress. The count never decreases
+    /// and is equal to the total once the circuit has been solved.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.resolved_opcodes, self.opcodes.len())
+    }
+
+    /// Returns the error which caused the ACVM to fail as an [`AcvmError`].
+    ///
+    /// Returns `None` if the ACVM has not failed, or if it failed due to an input passed to [`ACVM::resume`]
+    /// conflicting with the witness map rather than while solving an opcode.
+    pub fn error(&self) -> Option<AcvmError> {
+        match (&self.status, self.failed_opcode) {
+            (ACVMStatus::Failure(error), Some(opcode_index)) => {
+                Some(AcvmError::new(opcode_index, error.clone()))
+            }
+            _ => None,
+        }
+    }
+
     /// Finalize the ACVM execution, returning the resulting [`WitnessMap`].
     pub fn finalize(self) -> WitnessMap {
         if self.status != ACVMStatus::Solved {
@@ -233,15 +373,251 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
     /// 1. All opcodes have been executed successfully.
     /// 2. The circuit has been found to be unsatisfiable.
     /// 2. A Brillig [foreign call][`ForeignCallWaitInfo`] has been encountered and must be resolved.
+    ///
+    /// If [`ACVM::solve_partial`] has stalled then only the opcodes which it was unable to solve are executed,
+    /// as with [`ACVM::resume`].
     pub fn solve(&mut self) -> ACVMStatus {
+        if self.status == ACVMStatus::InProgress && !self.stalled_opcodes.is_empty() {
+            self.resume(WitnessMap::new());
+            return self.status.clone();
+        }
         while self.status == ACVMStatus::InProgress {
             self.solve_opcode();
         }
         self.status.clone()
     }
 
+    /// Executes the ACVM's circuit until execution halts, as with [`ACVM::solve`],
+    /// notifying `observer` as each opcode is solved.
+    pub fn solve_with_observer(&mut self, observer: &mut dyn SolveObserver) -> ACVMStatus {
+        while self.status == ACVMStatus::InProgress {
+            let index = self.instruction_pointer;
+            self.solve_opcode();
+            if self.instruction_pointer > index {
+                observer.on_opcode_solved(index, &self.opcodes[index]);
+            } else {
+                observer.on_opcode_stalled(index);
+            }
+        }
+        self.status.clone()
+    }
+
+    /// Executes the ACVM's circuit until execution halts, as with [`ACVM::solve`],
+    /// also returning the witnesses which were assigned values in the order in which they were solved.
+    ///
+    /// Witnesses solved by the same opcode are ordered by their index. If execution halts to wait on a
+    /// foreign call then calling this method again after resolving it continues the order from that opcode.
+    pub fn solve_with_order(&mut self) -> (ACVMStatus, Vec<Witness>) {
+        let mut order = Vec::new();
+        while self.status == ACVMStatus::InProgress {
+            let unknowns: Vec<Witness> = self.opcodes[self.instruction_pointer]
+                .witnesses()
+                .into_iter()
+                .filter(|witness| !self.witness_map.contains_key(witness))
+                .collect();
+            self.solve_opcode();
+            order.extend(
+                unknowns.into_iter().filter(|witness| self.witness_map.contains_key(witness)),
+            );
+        }
+        (self.status.clone(), order)
+    }
+
+    /// Executes the ACVM's circuit until execution halts, as with [`ACVM::solve`], or until `deadline` passes.
+    ///
+    /// The clock is checked before each opcode and periodically while executing Brillig bytecode, so a single slow
+    /// black box function call may overrun the deadline. On timeout [`AcvmError::Timeout`] is returned with the index
+    /// of the opcode which was about to be solved, and the ACVM is left in progress so solving can be continued later.
+    /// Other failures are returned as described in [`ACVM::error`].
+    pub fn solve_with_deadline(&mut self, deadline: Instant) -> Result<ACVMStatus, AcvmError> {
+        self.deadline = Some(deadline);
+        let mut timed_out = false;
+        while self.status == ACVMStatus::InProgress {
+            if Instant::now() >= deadline {
+                timed_out = true;
+                break;
+            }
+            self.solve_opcode();
+        }
+        self.deadline = None;
+
+        if let ACVMStatus::Failure(OpcodeResolutionError::BrilligFunctionFailed {
+            error: BrilligError::DeadlineExceeded { .. },
+            ..
+        }) = self.status
+        {
+            // The Brillig VM runs from the start of its bytecode on each attempt, so the opcode can be retried.
+            self.failed_opcode = None;
+            self.status(ACVMStatus::InProgress);
+            timed_out = true;
+        }
+        if timed_out {
+            return Err(AcvmError::Timeout { opcode_index: self.instruction_pointer });
+        }
+        match self.error() {
+            Some(error) => Err(error),
+            None => Ok(self.status.clone()),
+        }
+    }
+
+    /// Solves as many of the circuit's opcodes as possible, skipping over any which are missing witness assignments
+    /// rather than halting on them.
+    ///
+    /// Skipped opcodes are retried once later opcodes have been solved. Memory opcodes are always solved in order within
+    /// their block, so any memory opcode following a skipped one in the same block is skipped as well. Brillig opcodes
+    /// which make a foreign call are also reported as remaining. If no remaining opcode is blocked on a missing input,
+    /// so that solving only waits on the foreign call, then the ACVM's status becomes
+    /// [`ACVMStatus::RequiresForeignCall`] with the instruction pointer at the Brillig opcode which made the call,
+    /// so that it can be resolved with [`ACVM::resolve_pending_foreign_call`].
+    ///
+    /// After stalling, solving can be continued with [`ACVM::resume`] once the missing witnesses are known,
+    /// or with [`ACVM::solve`] once any foreign call has been resolved.
+    pub fn solve_partial(&mut self) -> SolveResult {
+        let remaining_opcodes = (self.instruction_pointer..self.opcodes.len()).collect();
+        self.solve_remaining(remaining_opcodes)
+    }
+
+    /// Adds `additional_inputs` to the witness map and continues solving after [`ACVM::solve_partial`] has stalled.
+    ///
+    /// Only the opcodes which were not solved previously are executed again, so the result is the same as that of
+    /// solving the circuit from scratch with all of the inputs present.
+    /// Returns [`SolveResult::Failed`] if an input conflicts with a value already in the witness map.
+    pub fn resume(&mut self, additional_inputs: WitnessMap) -> SolveResult {
+        for (witness, value) in additional_inputs {
+            if let Err(error) = insert_value(&witness, value, &mut self.witness_map) {
+                self.failed_opcode = None;
+                self.fail(error.clone());
+                return SolveResult::Failed(error);
+            }
+        }
+
+        let mut remaining_opcodes = std::mem::take(&mut self.stalled_opcodes);
+        if remaining_opcodes.is_empty() {
+            remaining_opcodes.extend(self.instruction_pointer..self.opcodes.len());
+        }
+        self.status(ACVMStatus::InProgress);
+        self.solve_remaining(remaining_opcodes)
+    }
+
+    /// Solves as many of the opcodes at `remaining_opcodes` as possible, as described in [`ACVM::solve_partial`].
+    fn solve_remaining(&mut self, mut remaining_opcodes: Vec<usize>) -> SolveResult {
+        let mut first_error = None;
+        let mut first_foreign_call = None;
+
+        loop {
+            let mut stalled_blocks = HashSet::new();
+            let mut unsolved = Vec::new();
+            for index in remaining_opcodes.iter().copied() {
+                let block_id = match &self.opcodes[index] {
+                    Opcode::MemoryInit { block_id, .. } | Opcode::MemoryOp { block_id, .. } => {
+                        Some(*block_id)
+                    }
+                    _ => None,
+                };
+                if block_id.map_or(false, |block_id| stalled_blocks.contains(&block_id)) {
+                    unsolved.push(index);
+                    continue;
+                }
+
+                let stalled = match self.execute_opcode(index) {
+                    Ok(None) => false,
+                    Ok(Some(foreign_call)) => {
+                        first_foreign_call.get_or_insert((index, foreign_call));
+                        true
+                    }
+                    Err(error @ OpcodeResolutionError::OpcodeNotSolvable(_)) => {
+                        first_error.get_or_insert((index, error));
+                        true
+                    }
+                    Err(error) => {
+                        self.instruction_pointer = self.opcodes.len();
+                        self.failed_opcode = Some(index);
+                        self.fail(error.clone());
+                        return SolveResult::Failed(error);
+                    }
+                };
+                if stalled {
+                    stalled_blocks.extend(block_id);
+                    unsolved.push(index);
+                }
+            }
+
+            let made_progress = unsolved.len() < remaining_opcodes.len();
+            remaining_opcodes = unsolved;
+            if !made_progress || remaining_opcodes.is_empty() {
+                break;
+            }
+        }
+
+        self.instruction_pointer = self.opcodes.len();
+        if remaining_opcodes.is_empty() {
+            self.status(ACVMStatus::Solved);
+            return SolveResult::Solved;
+        }
+
+        let blocked_on = self.blocking_witnesses(&remaining_opcodes);
+        match (first_foreign_call, first_error) {
+            // Other remaining opcodes may be waiting on the outputs of the foreign call, but not on any inputs.
+            (Some((index, foreign_call)), _) if blocked_on.is_empty() => {
+                self.instruction_pointer = index;
+                self.wait_for_foreign_call(foreign_call);
+            }
+            (_, Some((index, error))) => {
+                self.failed_opcode = Some(index);
+                self.fail(error);
+            }
+            _ => (),
+        }
+        self.stalled_opcodes = remaining_opcodes.clone();
+        SolveResult::Stalled { remaining_opcodes, blocked_on }
+    }
+
+    /// Returns the unassigned witnesses read by the opcodes at `indices` which none of these opcodes assign.
+    ///
+    /// An arithmetic opcode can assign a value to a single unknown witness. As witnesses are allocated in the order
+    /// in which they are computed, the unknown witness with the highest index is assumed to be the one being assigned.
+    fn blocking_witnesses(&self, indices: &[usize]) -> BTreeSet<Witness> {
+        let mut read = BTreeSet::new();
+        let mut written = BTreeSet::new();
+        for opcode in indices.iter().map(|index| &self.opcodes[*index]) {
+            let mut unknowns: BTreeSet<Witness> = opcode
+                .read_witnesses()
+                .into_iter()
+                .filter(|witness| !self.witness_map.contains_key(witness))
+                .collect();
+            if opcode.is_arithmetic() {
+                written.extend(unknowns.pop_last());
+            }
+            read.extend(unknowns);
+            written.extend(opcode.write_witnesses());
+        }
+        read.difference(&written).copied().collect()
+    }
+
     pub fn solve_opcode(&mut self) -> ACVMStatus {
-        let opcode = &self.opcodes[self.instruction_pointer];
+        match self.execute_opcode(self.instruction_pointer) {
+            Ok(Some(foreign_call)) => self.wait_for_foreign_call(foreign_call),
+            Ok(None) => {
+                self.instruction_pointer += 1;
+                if self.instruction_pointer == self.opcodes.len() {
+                    self.status(ACVMStatus::Solved)
+                } else {
+                    self.status(ACVMStatus::InProgress)
+                }
+            }
+            Err(error) => {
+                self.failed_opcode = Some(self.instruction_pointer);
+                self.fail(error)
+            }
+        }
+    }
+
+    /// Attempts to solve the opcode at `index`, returning any foreign call which must be resolved before it can be solved.
+    fn execute_opcode(
+        &mut self,
+        index: usize,
+    ) -> Result<Option<ForeignCallWaitInfo>, OpcodeResolutionError> {
+        let opcode = &self.opcodes[index];
 
         let resolution = match opcode {
             Opcode::Arithmetic(expr) => ArithmeticSolver::solve(&mut self.witness_map, expr),
@@ -250,54 +626,65 @@ impl<'backend, B: BlackBoxFunctionSolver> ACVM<'backend, B> {
             }
             Opcode::Directive(directive) => solve_directives(&mut self.witness_map, directive),
             Opcode::MemoryInit { block_id, init } => {
-                let solver = self.block_solvers.entry(*block_id).or_default();
+                let solver = self
+                    .block_solvers
+                    .entry(*block_id)
+                    .or_insert_with(|| MemoryOpSolver::new(*block_id));
                 solver.init(init, &self.witness_map)
             }
             Opcode::MemoryOp { block_id, op, predicate } => {
-                let solver = self.block_solvers.entry(*block_id).or_default();
-                solver.solve_memory_op(op, &mut self.witness_map, predicate)
+                match self.block_solvers.get_mut(block_id) {
+                    Some(solver) => solver.solve_memory_op(op, &mut self.witness_map, predicate),
+                    None => Err(OpcodeResolutionError::MemoryError {
+                        opcode_location: ErrorLocation::Unresolved,
+                        error: MemoryError::UninitializedBlock { block_id: *block_id },
+                    }),
+                }
             }
             Opcode::Brillig(brillig) => {
                 match BrilligSolver::solve(
                     &mut self.witness_map,
                     brillig,
                     self.backend,
-                    self.instruction_pointer,
+                    index,
+                    self.deadline,
                 ) {
-                    Ok(Some(foreign_call)) => return self.wait_for_foreign_call(foreign_call),
+                    Ok(Some(foreign_call)) => return Ok(Some(foreign_call)),
                     res => res.map(|_| ()),
                 }
             }
+            Opcode::Nop { .. } => Ok(()),
         };
         match resolution {
             Ok(()) => {
-                self.instruction_pointer += 1;
-                if self.instruction_pointer == self.opcodes.len() {
-                    self.status(ACVMStatus::Solved)
-                } else {
-                    self.status(ACVMStatus::InProgress)
-                }
+                self.resolved_opcodes += 1;
+                Ok(None)
             }
             Err(mut error) => {
                 match &mut error {
-                    // If we have an index out of bounds or an unsatisfied constraint, the opcode label will be unresolved
+                    // If we have an invalid memory access or an unsatisfied constraint, the opcode label will be unresolved
                     // because the solvers do not have knowledge of this information.
                     // We resolve, by setting this to the corresponding opcode that we just attempted to solve.
-                    OpcodeResolutionError::IndexOutOfBounds {
+                    OpcodeResolutionError::MemoryError {
+                        opcode_location: opcode_index, ..
+                    }
+                    | OpcodeResolutionError::BlackBoxError {
+                        opcode_location: opcode_index, ..
+                    }
+                    | OpcodeResolutionError::ConstraintError {
                         opcode_location: opcode_index,
                         ..
                     }
                     | OpcodeResolutionError::UnsatisfiedConstrain {
                         opcode_location: opcode_index,
+                        ..
                     } => {
-                        *opcode_index = ErrorLocation::Resolved(OpcodeLocation::Acir(
-                            self.instruction_pointer(),
-                        ));
+                        *opcode_index = ErrorLocation::Resolved(OpcodeLocation::Acir(index));
                     }
                     // All other errors are thrown normally.
                     _ => (),
                 };
-                self.fail(error)
+                Err(error)
             }
         }
     }
@@ -350,6 +737,9 @@ pub fn insert_value(
     if old_value != value_to_insert {
         return Err(OpcodeResolutionError::UnsatisfiedConstrain {
             opcode_location: ErrorLocation::Unresolved,
+            expression: None,
+            lhs: old_value,
+            rhs: value_to_insert,
         });
     }
 
@@ -0,0 +1,105 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroUsize,
+    thread,
+};
+
+use acir::{
+    circuit::{Circuit, Opcode},
+    native_types::{Witness, WitnessMap},
+};
+use thiserror::Error;
+
+use super::{ACVMStatus, ForeignCallWaitInfo, OpcodeResolutionError, ACVM};
+use crate::BlackBoxFunctionSolver;
+
+#[derive(Clone, PartialEq, Debug, Error)]
+pub enum ParallelSolveError {
+    /// Opcode locations within `error` are relative to the partition rather than the original circuit.
+    #[error("Failed to solve partition {partition}: {error}")]
+    Failure { partition: usize, error: OpcodeResolutionError },
+    #[error("Partition {partition} requires a foreign call, which cannot be resolved when solving in parallel")]
+    RequiresForeignCall { partition: usize, wait_info: ForeignCallWaitInfo },
+}
+
+/// Solves each of the circuit's [independent partitions][Circuit::partition_independent] on its own thread and
+/// merges the resulting witness assignments with `initial_witness`.
+///
+/// Partitions are spread over at most [`thread::available_parallelism`] threads. Each partition is only given the
+/// initial assignments of the witnesses it references. Circuits which make foreign calls cannot be solved in
+/// parallel as the caller has no way of resolving them, so these return [`ParallelSolveError::RequiresForeignCall`].
+pub fn solve_parallel<B: BlackBoxFunctionSolver + Sync + ?Sized>(
+    backend: &B,
+    circuit: &Circuit,
+    initial_witness: WitnessMap,
+) -> Result<WitnessMap, ParallelSolveError> {
+    let jobs: Vec<(usize, Vec<Opcode>, WitnessMap)> = circuit
+        .partition_independent()
+        .into_iter()
+        .enumerate()
+        .map(|(partition, sub_circuit)| {
+            let witnesses: BTreeSet<Witness> =
+                sub_circuit.opcodes.iter().flat_map(Opcode::witnesses).collect();
+            let partition_witness: BTreeMap<Witness, _> = initial_witness
+                .iter_sorted()
+                .filter(|(witness, _)| witnesses.contains(witness))
+                .map(|(witness, value)| (witness, *value))
+                .collect();
+            (partition, sub_circuit.opcodes, partition_witness.into())
+        })
+        .collect();
+
+    let num_workers =
+        thread::available_parallelism().map_or(1, NonZeroUsize::get).min(jobs.len()).max(1);
+    let mut worker_jobs: Vec<Vec<_>> = (0..num_workers).map(|_| Vec::new()).collect();
+    for (index, job) in jobs.into_iter().enumerate() {
+        worker_jobs[index % num_workers].push(job);
+    }
+
+    let mut results: Vec<(usize, Result<WitnessMap, ParallelSolveError>)> =
+        thread::scope(|scope| {
+            let workers: Vec<_> = worker_jobs
+                .into_iter()
+                .map(|jobs| {
+                    scope.spawn(move || {
+                        jobs.into_iter()
+                            .map(|(partition, opcodes, witness)| {
+                                (partition, solve_partition(backend, partition, opcodes, witness))
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("solver thread panicked"))
+                .collect()
+        });
+    results.sort_by_key(|(partition, _)| *partition);
+
+    // Partitions do not share any witnesses, so their assignments never conflict.
+    let mut solved_witness = initial_witness;
+    for (_, result) in results {
+        for (witness, value) in result? {
+            solved_witness.insert(witness, value);
+        }
+    }
+    Ok(solved_witness)
+}
+
+fn solve_partition<B: BlackBoxFunctionSolver + ?Sized>(
+    backend: &B,
+    partition: usize,
+    opcodes: Vec<Opcode>,
+    initial_witness: WitnessMap,
+) -> Result<WitnessMap, ParallelSolveError> {
+    let mut acvm = ACVM::new(backend, opcodes, initial_witness);
+    match acvm.solve() {
+        ACVMStatus::Solved => Ok(acvm.finalize()),
+        ACVMStatus::InProgress => unreachable!("ACVM does not stop solving while in progress"),
+        ACVMStatus::Failure(error) => Err(ParallelSolveError::Failure { partition, error }),
+        ACVMStatus::RequiresForeignCall(wait_info) => {
+            Err(ParallelSolveError::RequiresForeignCall { partition, wait_info })
+        }
+    }
+}
@@ -1,19 +1,27 @@
-use std::collections::BTreeMap;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet},
+    time::{Duration, Instant},
+};
 
 use acir::{
     brillig::{BinaryFieldOp, Opcode as BrilligOpcode, RegisterIndex, RegisterOrMemory, Value},
     circuit::{
         brillig::{Brillig, BrilligInputs, BrilligOutputs},
-        opcodes::{BlockId, MemOp},
-        Opcode, OpcodeLocation,
+        opcodes::{BlackBoxFuncCall, BlockId, FunctionInput, MemOp},
+        Circuit, CircuitBuilder, Opcode, OpcodeLocation, PublicInputs,
     },
     native_types::{Expression, Witness, WitnessMap},
-    FieldElement,
+    BlackBoxFunc, FieldElement,
 };
 
 use acvm::{
-    pwg::{ACVMStatus, ErrorLocation, ForeignCallWaitInfo, OpcodeResolutionError, ACVM},
-    BlackBoxFunctionSolver,
+    brillig_vm::BrilligError,
+    pwg::{
+        solve_parallel, ACVMStatus, AcvmError, BlackBoxError, ConstraintError, ErrorLocation,
+        ForeignCallWaitInfo, MemoryError, OpcodeResolutionError, SolveObserver, SolveResult, ACVM,
+    },
+    BlackBoxFunctionSolver, DefaultBlackBoxSolver,
 };
 use acvm_blackbox_solver::BlackBoxResolutionError;
 
@@ -45,6 +53,17 @@ impl BlackBoxFunctionSolver for StubbedBackend {
     }
 }
 
+/// Returns a circuit which returns `w3 = w1 + w2` for the private parameters `w1` and `w2`.
+fn addition_circuit() -> Circuit {
+    Circuit {
+        current_witness_index: 3,
+        opcodes: vec![Opcode::Arithmetic(&(Witness(1) + Witness(2)) - Witness(3))],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2)]),
+        return_values: PublicInputs(BTreeSet::from([Witness(3)])),
+        ..Circuit::default()
+    }
+}
+
 // Reenable these test cases once we move the brillig implementation of inversion down into the acvm stdlib.
 
 #[test]
@@ -99,8 +118,11 @@ fn inversion_brillig_oracle_equivalence() {
                 function: "invert".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(1))],
                 inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                destination_types: None,
+                input_types: None,
             },
         ],
+        trap_messages: vec![],
         predicate: None,
     };
 
@@ -145,7 +167,8 @@ fn inversion_brillig_oracle_equivalence() {
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
     // As caller of VM, need to resolve foreign calls
-    let foreign_call_result = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let foreign_call_result =
+        Value::from(foreign_call_wait_info.inputs[0].unwrap_value().to_field().inverse());
     // Alter Brillig oracle opcode with foreign call resolution
     acvm.resolve_pending_foreign_call(foreign_call_result.into());
 
@@ -222,13 +245,18 @@ fn double_inversion_brillig_oracle() {
                 function: "invert".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(1))],
                 inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                destination_types: None,
+                input_types: None,
             },
             BrilligOpcode::ForeignCall {
                 function: "invert".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(3))],
                 inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(2))],
+                destination_types: None,
+                input_types: None,
             },
         ],
+        trap_messages: vec![],
         predicate: None,
     };
 
@@ -274,7 +302,8 @@ fn double_inversion_brillig_oracle() {
         acvm.get_pending_foreign_call().expect("should have a brillig foreign call request");
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
-    let x_plus_y_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let x_plus_y_inverse =
+        Value::from(foreign_call_wait_info.inputs[0].unwrap_value().to_field().inverse());
 
     // Resolve Brillig foreign call
     acvm.resolve_pending_foreign_call(x_plus_y_inverse.into());
@@ -291,7 +320,8 @@ fn double_inversion_brillig_oracle() {
         acvm.get_pending_foreign_call().expect("should have a brillig foreign call request");
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
-    let i_plus_j_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let i_plus_j_inverse =
+        Value::from(foreign_call_wait_info.inputs[0].unwrap_value().to_field().inverse());
     assert_ne!(x_plus_y_inverse, i_plus_j_inverse);
 
     // Alter Brillig oracle opcode
@@ -347,13 +377,18 @@ fn oracle_dependent_execution() {
                 function: "invert".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(1))],
                 inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                destination_types: None,
+                input_types: None,
             },
             BrilligOpcode::ForeignCall {
                 function: "invert".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(3))],
                 inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(2))],
+                destination_types: None,
+                input_types: None,
             },
         ],
+        trap_messages: vec![],
         predicate: None,
     };
 
@@ -396,7 +431,8 @@ fn oracle_dependent_execution() {
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
     // Resolve Brillig foreign call
-    let x_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let x_inverse =
+        Value::from(foreign_call_wait_info.inputs[0].unwrap_value().to_field().inverse());
     acvm.resolve_pending_foreign_call(x_inverse.into());
 
     // After filling data request, continue solving
@@ -412,7 +448,8 @@ fn oracle_dependent_execution() {
     assert_eq!(foreign_call_wait_info.inputs.len(), 1, "Should be waiting for a single input");
 
     // Resolve Brillig foreign call
-    let y_inverse = Value::from(foreign_call_wait_info.inputs[0][0].to_field().inverse());
+    let y_inverse =
+        Value::from(foreign_call_wait_info.inputs[0].unwrap_value().to_field().inverse());
     acvm.resolve_pending_foreign_call(y_inverse.into());
 
     // We've resolved all the brillig foreign calls so we should be able to complete execution now.
@@ -465,8 +502,11 @@ fn brillig_oracle_predicate() {
                 function: "invert".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(1))],
                 inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+                destination_types: None,
+                input_types: None,
             },
         ],
+        trap_messages: vec![],
         predicate: Some(Expression::default()),
         // oracle results
         foreign_call_results: vec![],
@@ -487,6 +527,54 @@ fn brillig_oracle_predicate() {
     // ACVM should be able to be finalized in `Solved` state.
     acvm.finalize();
 }
+
+#[test]
+fn brillig_false_predicate_skips_execution() {
+    // The bytecode traps, so the ACVM fails if it is ever executed.
+    let brillig = Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(Witness(1).into())],
+        outputs: vec![BrilligOutputs::Simple(Witness(4)), BrilligOutputs::Array(vec![Witness(5)])],
+        foreign_call_results: vec![],
+        bytecode: vec![BrilligOpcode::Trap],
+        trap_messages: vec![],
+        predicate: Some(Witness(3).into()),
+    });
+    // w3 = w1
+    let predicate =
+        Opcode::Arithmetic(&Expression::from(Witness(3)) - &Expression::from(Witness(1)));
+
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::zero())]));
+    let mut acvm = ACVM::new(&StubbedBackend, vec![predicate, brillig], initial_witness);
+
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.witness_map()[&Witness(4)], FieldElement::zero());
+    assert_eq!(acvm.witness_map()[&Witness(5)], FieldElement::zero());
+}
+
+#[test]
+fn brillig_trap_reports_message() {
+    let brillig = Brillig {
+        inputs: vec![],
+        outputs: vec![],
+        foreign_call_results: vec![],
+        bytecode: vec![BrilligOpcode::TrapWithMessage { message: 0 }],
+        trap_messages: vec!["value out of range".to_owned()],
+        predicate: None,
+    };
+
+    let mut acvm =
+        ACVM::new(&StubbedBackend, vec![Opcode::Brillig(brillig.clone())], WitnessMap::default());
+    let ACVMStatus::Failure(OpcodeResolutionError::BrilligFunctionFailed {
+        error: BrilligError::Trapped { message, pc },
+        ..
+    }) = acvm.solve()
+    else {
+        panic!("expected the Brillig opcode to trap");
+    };
+    assert_eq!(pc, 0);
+    assert_eq!(brillig.trap_message(message), Some("value out of range"));
+}
+
 #[test]
 fn unsatisfied_opcode_resolved() {
     let a = Witness(0);
@@ -512,18 +600,83 @@ fn unsatisfied_opcode_resolved() {
     values.insert(c, FieldElement::from(1_i128));
     values.insert(d, FieldElement::from(2_i128));
 
-    let opcodes = vec![Opcode::Arithmetic(opcode_a)];
+    let opcodes = vec![Opcode::Arithmetic(opcode_a.clone())];
     let mut acvm = ACVM::new(&StubbedBackend, opcodes, values);
     let solver_status = acvm.solve();
     assert_eq!(
         solver_status,
         ACVMStatus::Failure(OpcodeResolutionError::UnsatisfiedConstrain {
             opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+            expression: Some(Box::new(opcode_a)),
+            lhs: FieldElement::from(4_i128),
+            rhs: FieldElement::from(5_i128),
         }),
         "The first opcode is not satisfiable, expected an error indicating this"
     );
 }
 
+#[test]
+fn unsatisfied_constraint_reports_values() {
+    // w1 * w2 + 2 * w3 - w4 - 3 = 0
+    let expression = Expression {
+        mul_terms: vec![(FieldElement::one(), Witness(1), Witness(2))],
+        linear_combinations: vec![
+            (FieldElement::from(2_i128), Witness(3)),
+            (-FieldElement::one(), Witness(4)),
+        ],
+        q_c: -FieldElement::from(3_i128),
+    };
+    let solvable = Opcode::Arithmetic(&Expression::from(Witness(1)) - Witness(5));
+
+    // 2 * 3 + 2 * 1 != 4 + 3
+    let values = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(2_i128)),
+        (Witness(2), FieldElement::from(3_i128)),
+        (Witness(3), FieldElement::from(1_i128)),
+        (Witness(4), FieldElement::from(4_i128)),
+    ]));
+
+    let mut acvm =
+        ACVM::new(&StubbedBackend, vec![solvable, Opcode::Arithmetic(expression.clone())], values);
+    let ACVMStatus::Failure(error) = acvm.solve() else {
+        panic!("expected the second opcode to be unsatisfiable");
+    };
+    assert_eq!(
+        error,
+        OpcodeResolutionError::UnsatisfiedConstrain {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+            expression: Some(Box::new(expression)),
+            lhs: FieldElement::from(8_i128),
+            rhs: FieldElement::from(7_i128),
+        }
+    );
+    assert_eq!(error.to_string(), "Cannot satisfy constraint: expected 8 == 7");
+}
+
+#[test]
+fn boolean_constraint_failure_reports_value() {
+    let values = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(2_i128))]));
+    let mut acvm = ACVM::new(&StubbedBackend, vec![Opcode::boolean_constraint(Witness(1))], values);
+
+    let ACVMStatus::Failure(error) = acvm.solve() else {
+        panic!("expected the boolean constraint to fail");
+    };
+    assert_eq!(
+        error,
+        OpcodeResolutionError::ConstraintError {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+            error: ConstraintError::NotBoolean {
+                witness: Witness(1),
+                value: FieldElement::from(2_i128)
+            },
+        }
+    );
+
+    let values = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+    let mut acvm = ACVM::new(&StubbedBackend, vec![Opcode::boolean_constraint(Witness(1))], values);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+}
+
 #[test]
 fn unsatisfied_opcode_resolved_brillig() {
     let a = Witness(0);
@@ -568,6 +721,7 @@ fn unsatisfied_opcode_resolved_brillig() {
         ],
         outputs: vec![BrilligOutputs::Simple(w_result)],
         bytecode: vec![equal_opcode, jmp_if_opcode, trap_opcode, stop_opcode],
+        trap_messages: vec![],
         predicate: Some(Expression::one()),
         // oracle results
         foreign_call_results: vec![],
@@ -600,7 +754,7 @@ fn unsatisfied_opcode_resolved_brillig() {
     assert_eq!(
         solver_status,
         ACVMStatus::Failure(OpcodeResolutionError::BrilligFunctionFailed {
-            message: "explicit trap hit in brillig".to_string(),
+            error: BrilligError::Trap,
             call_stack: vec![OpcodeLocation::Brillig { acir_index: 0, brillig_index: 2 }]
         }),
         "The first opcode is not satisfiable, expected an error indicating this"
@@ -646,3 +800,918 @@ fn memory_operations() {
 
     assert_eq!(witness_map[&Witness(8)], FieldElement::from(6u128));
 }
+
+#[test]
+fn progress_advances_per_opcode() {
+    let block_id = BlockId(0);
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id, init: vec![Witness(1), Witness(2)] },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(FieldElement::one().into(), Witness(3).into()),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(4)),
+            predicate: None,
+        },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    assert_eq!(acvm.progress(), (0, 3));
+    for resolved in 1..=3 {
+        acvm.solve_opcode();
+        assert_eq!(acvm.progress(), (resolved, 3));
+    }
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.witness_map()[&Witness(4)], FieldElement::from(3u128));
+}
+
+#[test]
+fn solve_parallel_merges_partitions() {
+    let addition = |lhs: u32, rhs: u32, output: u32| {
+        Opcode::Arithmetic(&(Witness(lhs) + Witness(rhs)) - Witness(output))
+    };
+    let circuit = Circuit {
+        current_witness_index: 6,
+        opcodes: vec![addition(1, 2, 3), addition(4, 5, 6)],
+        private_parameters: BTreeSet::from([Witness(1), Witness(2), Witness(4), Witness(5)]),
+        ..Circuit::default()
+    };
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(4), FieldElement::from(4u128)),
+        (Witness(5), FieldElement::from(5u128)),
+    ]));
+
+    let solved_witness = solve_parallel(&StubbedBackend, &circuit, initial_witness).unwrap();
+    assert_eq!(solved_witness[&Witness(3)], FieldElement::from(3u128));
+    assert_eq!(solved_witness[&Witness(6)], FieldElement::from(9u128));
+}
+
+#[test]
+fn solve_with_order_reports_derivation_order() {
+    let block_id = BlockId(0);
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id, init: vec![Witness(1), Witness(2)] },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::write_to_mem_index(FieldElement::one().into(), Witness(3).into()),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id,
+            op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(5)),
+            predicate: None,
+        },
+        // w4 = w5 + w1
+        Opcode::Arithmetic(&(Witness(5) + Witness(1)) - Witness(4)),
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    let (status, order) = acvm.solve_with_order();
+    assert_eq!(status, ACVMStatus::Solved);
+    assert_eq!(order, vec![Witness(5), Witness(4)]);
+    assert_eq!(acvm.witness_map()[&Witness(4)], FieldElement::from(4u128));
+}
+
+#[test]
+fn nops_do_not_affect_solving() {
+    let opcodes = vec![
+        // w3 = w1 + w2
+        Opcode::Arithmetic(&(Witness(1) + Witness(2)) - Witness(3)),
+        // w4 = w3 * w1
+        Opcode::Arithmetic(Expression {
+            mul_terms: vec![(FieldElement::one(), Witness(3), Witness(1))],
+            linear_combinations: vec![(-FieldElement::one(), Witness(4))],
+            q_c: FieldElement::zero(),
+        }),
+    ];
+    let with_nops = vec![
+        Opcode::Nop { tag: 0 },
+        opcodes[0].clone(),
+        Opcode::Nop { tag: 1 },
+        Opcode::Nop { tag: 2 },
+        opcodes[1].clone(),
+        Opcode::Nop { tag: 3 },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(2u128)),
+        (Witness(2), FieldElement::from(3u128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness.clone());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let mut acvm_with_nops = ACVM::new(&StubbedBackend, with_nops, initial_witness);
+    assert_eq!(acvm_with_nops.solve(), ACVMStatus::Solved);
+
+    assert_eq!(acvm_with_nops.finalize(), acvm.finalize());
+}
+
+#[test]
+fn poseidon_opcode() {
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+    ]));
+
+    let poseidon = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Poseidon {
+        inputs: vec![
+            FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() },
+            FunctionInput { witness: Witness(2), num_bits: FieldElement::max_num_bits() },
+        ],
+        output: Witness(3),
+    });
+
+    let mut acvm = ACVM::new(&StubbedBackend, vec![poseidon], initial_witness);
+    let solver_status = acvm.solve();
+    assert_eq!(solver_status, ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+
+    assert_eq!(
+        witness_map[&Witness(3)],
+        FieldElement::from_hex(
+            "0x115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+        )
+        .unwrap()
+    );
+}
+
+/// Solves a hash black box function over the bytes of `message`, returning the resulting digest.
+fn solve_hash(
+    hash: impl FnOnce(Vec<FunctionInput>, Vec<Witness>) -> BlackBoxFuncCall,
+    message: &[u8],
+    digest_size: u32,
+) -> Vec<u8> {
+    let message_size = message.len() as u32;
+    let inputs: Vec<_> =
+        (1..=message_size).map(|i| FunctionInput { witness: Witness(i), num_bits: 8 }).collect();
+    let outputs: Vec<_> = (message_size + 1..=message_size + digest_size).map(Witness).collect();
+    let initial_witness = WitnessMap::from(BTreeMap::from_iter(
+        inputs
+            .iter()
+            .zip(message)
+            .map(|(input, byte)| (input.witness, FieldElement::from(*byte as u128))),
+    ));
+
+    let opcode = Opcode::BlackBoxFuncCall(hash(inputs, outputs.clone()));
+    let mut acvm = ACVM::new(&StubbedBackend, vec![opcode], initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+
+    outputs.iter().map(|output| witness_map[output].to_u128() as u8).collect()
+}
+
+#[test]
+fn sha512_opcode() {
+    let sha512 = |inputs, outputs| BlackBoxFuncCall::Sha512 { inputs, outputs };
+
+    assert_eq!(
+        solve_hash(sha512, b"", 64),
+        hex_bytes("cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e")
+    );
+
+    // At 112 bytes the message length no longer fits in the first 128 byte block, so the padding spills into a second block.
+    let message = b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu";
+    assert_eq!(message.len(), 112);
+    assert_eq!(
+        solve_hash(sha512, message, 64),
+        hex_bytes("8e959b75dae313da8cf4f72814fc143f8f7779c6eb9f7fa17299aeadb6889018501d289e4900f7e4331b99dec4b5433ac7d329eeb6dd26545e96e55b874be909")
+    );
+}
+
+#[test]
+fn sha384_opcode() {
+    let sha384 = |inputs, outputs| BlackBoxFuncCall::Sha384 { inputs, outputs };
+
+    assert_eq!(
+        solve_hash(sha384, b"abc", 48),
+        hex_bytes("cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7")
+    );
+}
+
+fn hex_bytes(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+fn keccak256_variable_length_opcode() {
+    let message = b"abcdef";
+    let inputs: Vec<_> =
+        (1..=6).map(|i| FunctionInput { witness: Witness(i), num_bits: 8 }).collect();
+    let var_message_size = FunctionInput { witness: Witness(7), num_bits: 32 };
+    let outputs: Vec<_> = (8..40).map(Witness).collect();
+    let keccak = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Keccak256VariableLength {
+        inputs: inputs.clone(),
+        var_message_size,
+        outputs: outputs.clone(),
+    });
+
+    let solve = |message_size: u128| {
+        let mut initial_witness = WitnessMap::from(BTreeMap::from_iter(
+            inputs
+                .iter()
+                .zip(message)
+                .map(|(input, byte)| (input.witness, FieldElement::from(*byte as u128))),
+        ));
+        initial_witness.insert(var_message_size.witness, FieldElement::from(message_size));
+
+        let mut acvm = ACVM::new(&StubbedBackend, vec![keccak.clone()], initial_witness);
+        match acvm.solve() {
+            ACVMStatus::Solved => Ok(acvm.finalize()),
+            status => Err(status),
+        }
+    };
+    let digest = |witness_map: &WitnessMap| -> Vec<u8> {
+        outputs.iter().map(|output| witness_map[output].to_u128() as u8).collect()
+    };
+
+    let short_witness_map = solve(3).unwrap();
+    let full_witness_map = solve(6).unwrap();
+
+    // Only the first `var_message_size` bytes are hashed.
+    let keccak256 = |inputs, outputs| BlackBoxFuncCall::Keccak256 { inputs, outputs };
+    assert_eq!(digest(&short_witness_map), solve_hash(keccak256, b"abc", 32));
+    assert_eq!(digest(&full_witness_map), solve_hash(keccak256, message, 32));
+    assert_ne!(digest(&short_witness_map), digest(&full_witness_map));
+
+    // The message size cannot exceed the number of bytes in the message.
+    assert!(matches!(
+        solve(7),
+        Err(ACVMStatus::Failure(OpcodeResolutionError::BlackBoxFunctionFailed(
+            BlackBoxFunc::Keccak256,
+            _
+        )))
+    ));
+}
+
+#[test]
+fn eddsa_verify_opcode() {
+    // Test vector 1 from section 7.1 of RFC 8032, which signs the empty message.
+    let public_key = hex_bytes("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let signature = hex_bytes("e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b");
+
+    let solve = |message: &[u8]| {
+        let bytes: Vec<u8> = public_key.iter().chain(&signature).chain(message).copied().collect();
+        let inputs: Vec<_> = (1..=bytes.len() as u32)
+            .map(|i| FunctionInput { witness: Witness(i), num_bits: 8 })
+            .collect();
+        let output = Witness(bytes.len() as u32 + 1);
+        let initial_witness = WitnessMap::from(BTreeMap::from_iter(
+            inputs
+                .iter()
+                .zip(&bytes)
+                .map(|(input, byte)| (input.witness, FieldElement::from(*byte as u128))),
+        ));
+
+        let eddsa = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::EddsaVerify {
+            public_key: inputs[..32].to_vec(),
+            signature: inputs[32..96].to_vec(),
+            message: inputs[96..].to_vec(),
+            output,
+        });
+        let mut acvm = ACVM::new(&StubbedBackend, vec![eddsa], initial_witness);
+        assert_eq!(acvm.solve(), ACVMStatus::Solved);
+        acvm.finalize()[&output]
+    };
+
+    assert_eq!(solve(b""), FieldElement::one());
+    assert_eq!(solve(b"a"), FieldElement::zero());
+}
+
+/// A backend which records the black box functions it is asked to solve.
+#[derive(Default)]
+struct RecordingBackend {
+    invoked: RefCell<Vec<BlackBoxFunc>>,
+}
+
+impl BlackBoxFunctionSolver for RecordingBackend {
+    fn schnorr_verify(
+        &self,
+        _public_key_x: &FieldElement,
+        _public_key_y: &FieldElement,
+        _signature: &[u8],
+        _message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        self.invoked.borrow_mut().push(BlackBoxFunc::SchnorrVerify);
+        Ok(true)
+    }
+    fn pedersen(
+        &self,
+        inputs: &[FieldElement],
+        _domain_separator: u32,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.invoked.borrow_mut().push(BlackBoxFunc::Pedersen);
+        Ok((inputs[0], inputs[0] + FieldElement::one()))
+    }
+    fn fixed_base_scalar_mul(
+        &self,
+        low: &FieldElement,
+        high: &FieldElement,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        self.invoked.borrow_mut().push(BlackBoxFunc::FixedBaseScalarMul);
+        Ok((*low, *high))
+    }
+    fn poseidon(&self, _inputs: &[FieldElement]) -> Result<FieldElement, BlackBoxResolutionError> {
+        self.invoked.borrow_mut().push(BlackBoxFunc::Poseidon);
+        Ok(FieldElement::zero())
+    }
+}
+
+#[test]
+fn pluggable_black_box_solver() {
+    let pedersen = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Pedersen {
+        inputs: vec![FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() }],
+        outputs: (Witness(2), Witness(3)),
+        domain_separator: 0,
+    });
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(5u128))]));
+
+    let backend = RecordingBackend::default();
+    let solver: &dyn BlackBoxFunctionSolver = &backend;
+    let mut acvm = ACVM::new(solver, vec![pedersen.clone()], initial_witness.clone());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+
+    assert_eq!(backend.invoked.borrow().as_slice(), [BlackBoxFunc::Pedersen]);
+    assert_eq!(witness_map[&Witness(2)], FieldElement::from(5u128));
+    assert_eq!(witness_map[&Witness(3)], FieldElement::from(6u128));
+
+    // The default solver has no implementation of Pedersen.
+    let mut acvm = ACVM::new(&DefaultBlackBoxSolver, vec![pedersen], initial_witness);
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::UnsupportedBlackBoxFunc(BlackBoxFunc::Pedersen))
+    );
+}
+
+#[test]
+fn fixed_base_scalar_mul_of_single_scalar() {
+    let low = FieldElement::from(0x1234_u128);
+    let high = FieldElement::from(0x5678_u128);
+    let scalar = low + high * FieldElement::from(2_u128).pow(128);
+    let outputs = (Witness(2), Witness(3));
+
+    let explicit = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::FixedBaseScalarMul {
+        low: FunctionInput { witness: Witness(4), num_bits: 128 },
+        high: FunctionInput { witness: Witness(5), num_bits: 128 },
+        outputs,
+    });
+    let backend = RecordingBackend::default();
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(4), low), (Witness(5), high)]));
+    let mut acvm = ACVM::new(&backend, vec![explicit], initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let expected = acvm.finalize();
+
+    let circuit = CircuitBuilder::new()
+        .fixed_base_scalar_mul(
+            FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() },
+            outputs,
+        )
+        .private(Witness(1))
+        .build();
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), scalar)]));
+    let mut acvm = ACVM::new(&backend, circuit.opcodes, initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+
+    assert_eq!(witness_map[&Witness(2)], expected[&Witness(2)]);
+    assert_eq!(witness_map[&Witness(3)], expected[&Witness(3)]);
+}
+
+#[test]
+fn overridden_black_box_function() {
+    let poseidon = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::Poseidon {
+        inputs: vec![FunctionInput { witness: Witness(1), num_bits: FieldElement::max_num_bits() }],
+        output: Witness(2),
+    });
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+
+    let backend = RecordingBackend::default();
+    let mut acvm = ACVM::new(&backend, vec![poseidon], initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+
+    assert_eq!(backend.invoked.borrow().as_slice(), [BlackBoxFunc::Poseidon]);
+    assert_eq!(acvm.witness_map()[&Witness(2)], FieldElement::zero());
+}
+
+#[test]
+fn partial_solve_reports_blocking_witnesses() {
+    let mut circuit = addition_circuit();
+    circuit.opcodes.extend([
+        // w4 = 2 * w1
+        Opcode::Arithmetic(Expression::from(Witness(1)) * FieldElement::from(2u128) - Witness(4)),
+        // w5 = w3
+        Opcode::Arithmetic(Expression::from(Witness(3)) - Witness(5)),
+    ]);
+    let opcodes = circuit.opcodes;
+
+    // The private parameter `w2` is omitted.
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes.clone(), initial_witness);
+    assert_eq!(
+        acvm.solve_partial(),
+        SolveResult::Stalled {
+            remaining_opcodes: vec![0, 2],
+            blocked_on: BTreeSet::from([Witness(2)])
+        }
+    );
+    // Opcodes which do not depend upon `w2` are still solved.
+    assert_eq!(acvm.witness_map()[&Witness(4)], FieldElement::from(2u128));
+
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::one()),
+        (Witness(2), FieldElement::one()),
+    ]));
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    assert_eq!(acvm.solve_partial(), SolveResult::Solved);
+    assert_eq!(acvm.finalize()[&Witness(5)], FieldElement::from(2u128));
+}
+
+/// An observer which records the opcodes it is notified about.
+#[derive(Default)]
+struct RecordingObserver {
+    solved: Vec<(usize, Opcode)>,
+    stalled: Vec<usize>,
+}
+
+impl SolveObserver for RecordingObserver {
+    fn on_opcode_solved(&mut self, index: usize, opcode: &Opcode) {
+        self.solved.push((index, opcode.clone()));
+    }
+
+    fn on_opcode_stalled(&mut self, index: usize) {
+        self.stalled.push(index);
+    }
+}
+
+#[test]
+fn observer_sees_each_solved_opcode() {
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::write_to_mem_index(FieldElement::one().into(), Witness(3).into()),
+            predicate: None,
+        },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(4)),
+            predicate: None,
+        },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(3u128)),
+    ]));
+
+    let mut observer = RecordingObserver::default();
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes.clone(), initial_witness);
+    assert_eq!(acvm.solve_with_observer(&mut observer), ACVMStatus::Solved);
+    assert_eq!(acvm.witness_map()[&Witness(4)], FieldElement::from(3u128));
+
+    assert_eq!(observer.solved, opcodes.into_iter().enumerate().collect::<Vec<_>>());
+    assert!(observer.stalled.is_empty());
+
+    // Solving halts on the first opcode as its inputs are missing.
+    let mut observer = RecordingObserver::default();
+    let opcode = Opcode::Arithmetic(&Expression::from(Witness(1)) + Witness(2));
+    let mut acvm = ACVM::new(&StubbedBackend, vec![opcode], WitnessMap::new());
+    assert!(matches!(acvm.solve_with_observer(&mut observer), ACVMStatus::Failure(_)));
+    assert!(observer.solved.is_empty());
+    assert_eq!(observer.stalled, vec![0]);
+}
+
+#[test]
+fn memory_write_with_false_predicate() {
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+        // The write is skipped as its predicate `w4` is zero.
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::write_to_mem_index(FieldElement::zero().into(), Witness(3).into()),
+            predicate: Some(Witness(4).into()),
+        },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(FieldElement::zero().into(), Witness(5)),
+            predicate: None,
+        },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+        (Witness(3), FieldElement::from(5u128)),
+        (Witness(4), FieldElement::zero()),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.witness_map()[&Witness(5)], FieldElement::from(1u128));
+}
+
+#[test]
+fn memory_read_out_of_bounds() {
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1), Witness(2)] },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(FieldElement::from(2u128).into(), Witness(3)),
+            predicate: None,
+        },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(1u128)),
+        (Witness(2), FieldElement::from(2u128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::MemoryError {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+            error: MemoryError::IndexOutOfBounds {
+                block_id: BlockId(0),
+                index: FieldElement::from(2u128),
+                size: 2,
+            },
+        })
+    );
+}
+
+#[test]
+fn memory_op_on_uninitialized_block() {
+    let opcodes = vec![
+        Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1)] },
+        Opcode::MemoryOp {
+            block_id: BlockId(9),
+            op: MemOp::read_at_mem_index(FieldElement::zero().into(), Witness(2)),
+            predicate: None,
+        },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, opcodes, initial_witness);
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::MemoryError {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(1)),
+            error: MemoryError::UninitializedBlock { block_id: BlockId(9) },
+        })
+    );
+}
+
+#[test]
+fn range_check_failure_reports_value() {
+    let range = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+        input: FunctionInput { witness: Witness(1), num_bits: 8 },
+    });
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(256u128))]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, vec![range], initial_witness);
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::BlackBoxError {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+            error: BlackBoxError::RangeCheckFailed {
+                witness: Witness(1),
+                value: FieldElement::from(256u128),
+                num_bits: 8,
+            },
+        })
+    );
+}
+
+#[test]
+fn failures_surface_as_acvm_errors() {
+    // w2 = w1 + 1 is solved before the range check on w2 fails.
+    let black_box_failure = vec![
+        Opcode::Arithmetic((&Expression::from(Witness(1)) - Witness(2)) + FieldElement::one()),
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::RANGE {
+            input: FunctionInput { witness: Witness(2), num_bits: 8 },
+        }),
+    ];
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(255u128))]));
+    let mut acvm = ACVM::new(&StubbedBackend, black_box_failure, initial_witness);
+    assert!(acvm.error().is_none());
+    acvm.solve();
+    let error = acvm.error().expect("range check should fail");
+    assert_eq!(error.opcode_index(), 1);
+    assert_eq!(
+        error,
+        AcvmError::BlackBox {
+            opcode_index: 1,
+            error: BlackBoxError::RangeCheckFailed {
+                witness: Witness(2),
+                value: FieldElement::from(256u128),
+                num_bits: 8,
+            },
+        }
+    );
+
+    let memory_failure = vec![
+        Opcode::MemoryInit { block_id: BlockId(0), init: vec![Witness(1)] },
+        Opcode::MemoryOp {
+            block_id: BlockId(0),
+            op: MemOp::read_at_mem_index(FieldElement::one().into(), Witness(2)),
+            predicate: None,
+        },
+    ];
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+    let mut acvm = ACVM::new(&StubbedBackend, memory_failure, initial_witness);
+    acvm.solve();
+    assert_eq!(
+        acvm.error(),
+        Some(AcvmError::Memory {
+            opcode_index: 1,
+            error: MemoryError::IndexOutOfBounds {
+                block_id: BlockId(0),
+                index: FieldElement::one(),
+                size: 1,
+            },
+        })
+    );
+}
+
+#[test]
+fn solve_with_deadline_times_out() {
+    // w2 = w1 + 1
+    let increment = Opcode::Arithmetic(Expression {
+        mul_terms: vec![],
+        linear_combinations: vec![
+            (FieldElement::one(), Witness(1)),
+            (-FieldElement::one(), Witness(2)),
+        ],
+        q_c: FieldElement::one(),
+    });
+    let initial_witness = WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())]));
+
+    // The deadline has passed before solving starts, so no opcodes are solved.
+    let mut acvm = ACVM::new(&StubbedBackend, vec![increment], initial_witness.clone());
+    assert_eq!(
+        acvm.solve_with_deadline(Instant::now()),
+        Err(AcvmError::Timeout { opcode_index: 0 })
+    );
+    assert_eq!(acvm.instruction_pointer(), 0);
+    assert_eq!(acvm.witness_map(), &initial_witness);
+
+    // Solving can be continued once more time is available.
+    let deadline = Instant::now() + Duration::from_secs(60);
+    assert_eq!(acvm.solve_with_deadline(deadline), Ok(ACVMStatus::Solved));
+    assert_eq!(acvm.witness_map()[&Witness(2)], FieldElement::from(2u128));
+}
+
+#[test]
+fn solve_with_deadline_interrupts_brillig() {
+    // The Brillig bytecode never finishes, so only the periodic check within the VM can stop it.
+    let infinite_loop = Opcode::Brillig(Brillig {
+        inputs: vec![],
+        outputs: vec![],
+        foreign_call_results: vec![],
+        bytecode: vec![BrilligOpcode::Jump { location: 0 }],
+        trap_messages: vec![],
+        predicate: None,
+    });
+    let mut acvm = ACVM::new(&StubbedBackend, vec![infinite_loop], WitnessMap::default());
+    let deadline = Instant::now() + Duration::from_millis(5);
+    assert_eq!(acvm.solve_with_deadline(deadline), Err(AcvmError::Timeout { opcode_index: 0 }));
+    assert_eq!(acvm.instruction_pointer(), 0);
+}
+
+#[test]
+fn bitwise_and_of_bytes() {
+    let and = Opcode::BlackBoxFuncCall(BlackBoxFuncCall::AND {
+        lhs: FunctionInput { witness: Witness(1), num_bits: 8 },
+        rhs: FunctionInput { witness: Witness(2), num_bits: 8 },
+        output: Witness(3),
+    });
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(0xf3u128)),
+        (Witness(2), FieldElement::from(0xbcu128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, vec![and], initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.witness_map()[&Witness(3)], FieldElement::from(0xb0u128));
+}
+
+#[test]
+fn bitwise_operations_validate_bit_widths() {
+    let xor = |lhs_bits, rhs_bits| {
+        Opcode::BlackBoxFuncCall(BlackBoxFuncCall::XOR {
+            lhs: FunctionInput { witness: Witness(1), num_bits: lhs_bits },
+            rhs: FunctionInput { witness: Witness(2), num_bits: rhs_bits },
+            output: Witness(3),
+        })
+    };
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(0x1ffu128)),
+        (Witness(2), FieldElement::from(0x0fu128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, vec![xor(16, 8)], initial_witness.clone());
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::BlackBoxError {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+            error: BlackBoxError::MismatchedBitWidth { lhs: 16, rhs: 8 },
+        })
+    );
+
+    // `w1` does not fit within the declared 8 bits.
+    let mut acvm = ACVM::new(&StubbedBackend, vec![xor(8, 8)], initial_witness);
+    assert_eq!(
+        acvm.solve(),
+        ACVMStatus::Failure(OpcodeResolutionError::BlackBoxError {
+            opcode_location: ErrorLocation::Resolved(OpcodeLocation::Acir(0)),
+            error: BlackBoxError::RangeCheckFailed {
+                witness: Witness(1),
+                value: FieldElement::from(0x1ffu128),
+                num_bits: 8,
+            },
+        })
+    );
+}
+
+#[test]
+fn inlined_brillig_solves_identically() {
+    // w3 = (w1 - w2) * w1 / 2
+    let bytecode = vec![
+        BrilligOpcode::Const {
+            destination: RegisterIndex(2),
+            value: Value::from(2u128),
+            bit_size: FieldElement::max_num_bits(),
+        },
+        BrilligOpcode::BinaryFieldOp {
+            destination: RegisterIndex(1),
+            op: BinaryFieldOp::Sub,
+            lhs: RegisterIndex(0),
+            rhs: RegisterIndex(1),
+        },
+        BrilligOpcode::BinaryFieldOp {
+            destination: RegisterIndex(1),
+            op: BinaryFieldOp::Mul,
+            lhs: RegisterIndex(1),
+            rhs: RegisterIndex(0),
+        },
+        BrilligOpcode::BinaryFieldOp {
+            destination: RegisterIndex(0),
+            op: BinaryFieldOp::Div,
+            lhs: RegisterIndex(1),
+            rhs: RegisterIndex(2),
+        },
+    ];
+    let brillig = Opcode::Brillig(Brillig {
+        inputs: vec![
+            BrilligInputs::Single(Witness(1).into()),
+            BrilligInputs::Single(Witness(2).into()),
+        ],
+        outputs: vec![BrilligOutputs::Simple(Witness(3))],
+        foreign_call_results: Vec::new(),
+        bytecode,
+        trap_messages: vec![],
+        predicate: None,
+    });
+    let mut circuit = Circuit { opcodes: vec![brillig], ..Circuit::default() };
+    let initial_witness = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(7u128)),
+        (Witness(2), FieldElement::from(3u128)),
+    ]));
+
+    let mut acvm = ACVM::new(&StubbedBackend, circuit.opcodes.clone(), initial_witness.clone());
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let expected = acvm.finalize();
+    assert_eq!(expected[&Witness(3)], FieldElement::from(14u128));
+
+    circuit.inline_trivial_brillig();
+    assert!(circuit.opcodes.iter().all(Opcode::is_arithmetic));
+    let mut acvm = ACVM::new(&StubbedBackend, circuit.opcodes, initial_witness);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    assert_eq!(acvm.finalize(), expected);
+}
+
+#[test]
+fn resume_after_stalling() {
+    let circuit = addition_circuit();
+
+    // The inputs are provided one at a time, with solving stalling on the second.
+    let mut acvm = ACVM::new(
+        &StubbedBackend,
+        circuit.opcodes.clone(),
+        WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(2u128))])),
+    );
+    assert_eq!(
+        acvm.solve_partial(),
+        SolveResult::Stalled {
+            remaining_opcodes: vec![0],
+            blocked_on: BTreeSet::from([Witness(2)])
+        }
+    );
+    assert_eq!(
+        acvm.resume(WitnessMap::from(BTreeMap::from([(Witness(2), FieldElement::from(3u128))]))),
+        SolveResult::Solved
+    );
+    let resumed = acvm.finalize();
+
+    let all_inputs = WitnessMap::from(BTreeMap::from([
+        (Witness(1), FieldElement::from(2u128)),
+        (Witness(2), FieldElement::from(3u128)),
+    ]));
+    let mut acvm = ACVM::new(&StubbedBackend, circuit.opcodes, all_inputs);
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let solved = acvm.finalize();
+
+    assert_eq!(resumed, solved);
+    assert_eq!(solved[&Witness(3)], FieldElement::from(5u128));
+}
+
+#[test]
+fn solve_after_partial_solve_waits_on_foreign_call() {
+    // w2 = 1 / w1, computed by an oracle
+    let brillig = Opcode::Brillig(Brillig {
+        inputs: vec![BrilligInputs::Single(Witness(1).into())],
+        outputs: vec![BrilligOutputs::Simple(Witness(2))],
+        foreign_call_results: vec![],
+        bytecode: vec![BrilligOpcode::ForeignCall {
+            function: "invert".into(),
+            destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            destination_types: None,
+            input_types: None,
+        }],
+        trap_messages: vec![],
+        predicate: None,
+    });
+    // w3 = w1 + w2
+    let sum = Opcode::Arithmetic(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::one(), Witness(1)),
+            (FieldElement::one(), Witness(2)),
+            (-FieldElement::one(), Witness(3)),
+        ],
+        q_c: FieldElement::zero(),
+    });
+    // w4 = 2 * w1, which can be solved without the oracle
+    let double = Opcode::Arithmetic(Expression {
+        mul_terms: Vec::new(),
+        linear_combinations: vec![
+            (FieldElement::from(2u128), Witness(1)),
+            (-FieldElement::one(), Witness(4)),
+        ],
+        q_c: FieldElement::zero(),
+    });
+    let initial_witness =
+        WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::from(2u128))]));
+    let mut acvm = ACVM::new(&StubbedBackend, vec![brillig, sum, double], initial_witness);
+
+    assert_eq!(
+        acvm.solve_partial(),
+        SolveResult::Stalled { remaining_opcodes: vec![0, 1], blocked_on: BTreeSet::new() }
+    );
+    assert_eq!(acvm.witness_map()[&Witness(4)], FieldElement::from(4u128));
+
+    // The foreign call is the only reason for stalling, so it is surfaced as with `ACVM::solve`.
+    assert_eq!(acvm.instruction_pointer(), 0);
+    let foreign_call = acvm.get_pending_foreign_call().expect("should be waiting on the oracle");
+    let inverse = Value::from(foreign_call.inputs[0].unwrap_value().to_field().inverse());
+    acvm.resolve_pending_foreign_call(inverse.into());
+
+    // Only the opcodes which were skipped are solved again.
+    assert_eq!(acvm.solve(), ACVMStatus::Solved);
+    let witness_map = acvm.finalize();
+    let half = FieldElement::from(2u128).inverse();
+    assert_eq!(witness_map[&Witness(2)], half);
+    assert_eq!(witness_map[&Witness(3)], FieldElement::from(2u128) + half);
+}
+
+#[test]
+fn resume_rejects_conflicting_inputs() {
+    let opcodes =
+        vec![Opcode::Arithmetic(&Expression::from(Witness(1)) - &Expression::from(Witness(2)))];
+    let mut acvm = ACVM::new(
+        &StubbedBackend,
+        opcodes,
+        WitnessMap::from(BTreeMap::from([(Witness(1), FieldElement::one())])),
+    );
+    assert_eq!(acvm.solve_partial(), SolveResult::Solved);
+
+    let conflicting = WitnessMap::from(BTreeMap::from([(Witness(2), FieldElement::zero())]));
+    assert!(matches!(acvm.resume(conflicting), SolveResult::Failed(_)));
+}
@@ -0,0 +1,66 @@
+//! Verification of pure Ed25519 signatures as specified in [RFC 8032][rfc8032].
+//!
+//! Only the "pure" variant is supported, i.e. the message is hashed as part of verification rather than
+//! being prehashed by the caller (Ed25519ph) and no context string is used (Ed25519ctx).
+//!
+//! [rfc8032]: https://datatracker.ietf.org/doc/html/rfc8032
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+pub(super) fn verify_eddsa_signature(
+    public_key: &[u8; 32],
+    signature: &[u8; 64],
+    message: &[u8],
+) -> bool {
+    let Ok(public_key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    // Signatures with non-canonical `S` values are rejected during verification, preventing malleability.
+    public_key.verify(message, &Signature::from_bytes(signature)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify_eddsa_signature;
+
+    fn hex_array<const N: usize>(hex: &str) -> [u8; N] {
+        let bytes: Vec<u8> = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect();
+        bytes.try_into().unwrap()
+    }
+
+    // Test vector 1 from section 7.1 of RFC 8032.
+    const PUBLIC_KEY: &str = "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a";
+    const SIGNATURE: &str = "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b";
+
+    #[test]
+    fn verifies_rfc8032_test_vector() {
+        assert!(verify_eddsa_signature(&hex_array(PUBLIC_KEY), &hex_array(SIGNATURE), &[]));
+    }
+
+    #[test]
+    fn verifies_rfc8032_test_vector_with_message() {
+        // Test vector 2 from section 7.1 of RFC 8032.
+        let public_key = "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+        let signature = "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00";
+        assert!(verify_eddsa_signature(&hex_array(public_key), &hex_array(signature), &[0x72]));
+    }
+
+    #[test]
+    fn rejects_invalid_signatures() {
+        let public_key = hex_array(PUBLIC_KEY);
+        let signature: [u8; 64] = hex_array(SIGNATURE);
+        assert!(!verify_eddsa_signature(&public_key, &signature, b"tampered"));
+
+        let mut bad_signature = signature;
+        bad_signature[0] ^= 1;
+        assert!(!verify_eddsa_signature(&public_key, &bad_signature, &[]));
+
+        // `S` values which are not reduced modulo the group order are rejected.
+        let mut malleated_signature = signature;
+        malleated_signature[63] |= 0x80;
+        assert!(!verify_eddsa_signature(&public_key, &malleated_signature, &[]));
+    }
+}
@@ -8,10 +8,15 @@
 use acir::{BlackBoxFunc, FieldElement};
 use blake2::digest::generic_array::GenericArray;
 use blake2::{Blake2s256, Digest};
-use sha2::Sha256;
+use sha2::{Sha256, Sha384, Sha512};
 use sha3::Keccak256;
 use thiserror::Error;
 
+mod eddsa;
+mod poseidon;
+
+pub use poseidon::poseidon;
+
 #[derive(Clone, PartialEq, Eq, Debug, Error)]
 pub enum BlackBoxResolutionError {
     #[error("unsupported blackbox function: {0}")]
@@ -20,8 +25,11 @@ pub enum BlackBoxResolutionError {
     Failed(BlackBoxFunc, String),
 }
 
-/// This component will generate outputs for Blackbox function calls where the underlying [`acir::BlackBoxFunc`]
-/// doesn't have a canonical Rust implementation.
+/// This component will generate outputs for Blackbox function calls.
+///
+/// Functions such as [`BlackBoxFunctionSolver::pedersen`] don't have a canonical Rust implementation and so must be
+/// implemented by the backend. The remaining functions default to the reference implementations exported by this crate
+/// but may be overridden, e.g. to make use of a hardware-accelerated implementation.
 ///
 /// Returns an [`BlackBoxResolutionError`] if the backend does not support the given [`acir::BlackBoxFunc`].
 pub trait BlackBoxFunctionSolver {
@@ -42,28 +50,155 @@ pub trait BlackBoxFunctionSolver {
         low: &FieldElement,
         high: &FieldElement,
     ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError>;
+
+    /// Computes the SHA-256 digest of `inputs`, defaulting to [`sha256`].
+    fn sha256(&self, inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
+        sha256(inputs)
+    }
+    /// Computes the SHA-512 digest of `inputs`, defaulting to [`sha512`].
+    fn sha512(&self, inputs: &[u8]) -> Result<[u8; 64], BlackBoxResolutionError> {
+        sha512(inputs)
+    }
+    /// Computes the SHA-384 digest of `inputs`, defaulting to [`sha384`].
+    fn sha384(&self, inputs: &[u8]) -> Result<[u8; 48], BlackBoxResolutionError> {
+        sha384(inputs)
+    }
+    /// Computes the BLAKE2s-256 digest of `inputs`, defaulting to [`blake2s`].
+    fn blake2s(&self, inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
+        blake2s(inputs)
+    }
+    /// Computes the Keccak-256 digest of `inputs`, defaulting to [`keccak256`].
+    fn keccak256(&self, inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
+        keccak256(inputs)
+    }
+    /// Hashes `inputs` to a field element, defaulting to [`hash_to_field_128_security`].
+    fn hash_to_field_128_security(
+        &self,
+        inputs: &[u8],
+    ) -> Result<FieldElement, BlackBoxResolutionError> {
+        hash_to_field_128_security(inputs)
+    }
+    /// Computes the Poseidon hash of `inputs`, defaulting to [`poseidon`].
+    fn poseidon(&self, inputs: &[FieldElement]) -> Result<FieldElement, BlackBoxResolutionError> {
+        poseidon(inputs)
+    }
+    /// Verifies an ECDSA signature over secp256k1, defaulting to [`ecdsa_secp256k1_verify`].
+    fn ecdsa_secp256k1_verify(
+        &self,
+        hashed_msg: &[u8],
+        public_key_x: &[u8; 32],
+        public_key_y: &[u8; 32],
+        signature: &[u8; 64],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        ecdsa_secp256k1_verify(hashed_msg, public_key_x, public_key_y, signature)
+    }
+    /// Verifies an ECDSA signature over secp256r1, defaulting to [`ecdsa_secp256r1_verify`].
+    fn ecdsa_secp256r1_verify(
+        &self,
+        hashed_msg: &[u8],
+        public_key_x: &[u8; 32],
+        public_key_y: &[u8; 32],
+        signature: &[u8; 64],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        ecdsa_secp256r1_verify(hashed_msg, public_key_x, public_key_y, signature)
+    }
+    /// Verifies an Ed25519 signature, defaulting to [`eddsa_verify`].
+    fn eddsa_verify(
+        &self,
+        public_key: &[u8; 32],
+        signature: &[u8; 64],
+        message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        eddsa_verify(public_key, signature, message)
+    }
+}
+
+/// A [`BlackBoxFunctionSolver`] which uses the reference implementations of this crate.
+///
+/// Functions without a reference implementation return [`BlackBoxResolutionError::Unsupported`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultBlackBoxSolver;
+
+impl BlackBoxFunctionSolver for DefaultBlackBoxSolver {
+    fn schnorr_verify(
+        &self,
+        _public_key_x: &FieldElement,
+        _public_key_y: &FieldElement,
+        _signature: &[u8],
+        _message: &[u8],
+    ) -> Result<bool, BlackBoxResolutionError> {
+        Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::SchnorrVerify))
+    }
+    fn pedersen(
+        &self,
+        _inputs: &[FieldElement],
+        _domain_separator: u32,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::Pedersen))
+    }
+    fn fixed_base_scalar_mul(
+        &self,
+        _low: &FieldElement,
+        _high: &FieldElement,
+    ) -> Result<(FieldElement, FieldElement), BlackBoxResolutionError> {
+        Err(BlackBoxResolutionError::Unsupported(BlackBoxFunc::FixedBaseScalarMul))
+    }
 }
 
+/// Computes the SHA-256 digest of `inputs`.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::sha256`] unless a backend overrides it.
 pub fn sha256(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
     generic_hash_256::<Sha256>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::SHA256, err))
 }
 
+/// Computes the SHA-512 digest of `inputs`.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::sha512`] unless a backend overrides it.
+pub fn sha512(inputs: &[u8]) -> Result<[u8; 64], BlackBoxResolutionError> {
+    generic_hash::<Sha512, 64>(inputs)
+        .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Sha512, err))
+}
+
+/// Computes the SHA-384 digest of `inputs`.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::sha384`] unless a backend overrides it.
+pub fn sha384(inputs: &[u8]) -> Result<[u8; 48], BlackBoxResolutionError> {
+    generic_hash::<Sha384, 48>(inputs)
+        .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Sha384, err))
+}
+
+/// Computes the BLAKE2s-256 digest of `inputs`.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::blake2s`] unless a backend overrides it.
 pub fn blake2s(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
     generic_hash_256::<Blake2s256>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Blake2s, err))
 }
 
+/// Computes the Keccak-256 digest of `inputs`, as used by Ethereum rather than the standardized SHA3-256.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::keccak256`] unless a backend overrides it.
 pub fn keccak256(inputs: &[u8]) -> Result<[u8; 32], BlackBoxResolutionError> {
     generic_hash_256::<Keccak256>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::Keccak256, err))
 }
 
+/// Hashes `inputs` with BLAKE2s-256, interpreting the digest as a big-endian integer reduced into the field.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::hash_to_field_128_security`] unless a backend
+/// overrides it.
 pub fn hash_to_field_128_security(inputs: &[u8]) -> Result<FieldElement, BlackBoxResolutionError> {
     generic_hash_to_field::<Blake2s256>(inputs)
         .map_err(|err| BlackBoxResolutionError::Failed(BlackBoxFunc::HashToField128Security, err))
 }
 
+/// Verifies the ECDSA `signature` over secp256k1 of the already hashed message `hashed_msg`, where `signature` is
+/// the concatenation of `r` and `s`.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::ecdsa_secp256k1_verify`] unless a backend overrides
+/// it.
 pub fn ecdsa_secp256k1_verify(
     hashed_msg: &[u8],
     public_key_x: &[u8; 32],
@@ -73,6 +208,11 @@ pub fn ecdsa_secp256k1_verify(
     Ok(verify_secp256k1_ecdsa_signature(hashed_msg, public_key_x, public_key_y, signature))
 }
 
+/// Verifies the ECDSA `signature` over secp256r1 of the already hashed message `hashed_msg`, where `signature` is
+/// the concatenation of `r` and `s`.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::ecdsa_secp256r1_verify`] unless a backend overrides
+/// it.
 pub fn ecdsa_secp256r1_verify(
     hashed_msg: &[u8],
     public_key_x: &[u8; 32],
@@ -82,10 +222,28 @@ pub fn ecdsa_secp256r1_verify(
     Ok(verify_secp256r1_ecdsa_signature(hashed_msg, public_key_x, public_key_y, signature))
 }
 
+/// Verifies the Ed25519 `signature` of `message` under `public_key`, as specified by RFC 8032.
+///
+/// This is the implementation used by [`BlackBoxFunctionSolver::eddsa_verify`] unless a backend overrides it.
+pub fn eddsa_verify(
+    public_key: &[u8; 32],
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<bool, BlackBoxResolutionError> {
+    Ok(eddsa::verify_eddsa_signature(public_key, signature, message))
+}
+
 /// Does a generic hash of the inputs returning the resulting 32 bytes separately.
 fn generic_hash_256<D: Digest>(message: &[u8]) -> Result<[u8; 32], String> {
-    let output_bytes: [u8; 32] =
-        D::digest(message).as_slice().try_into().map_err(|_| "digest should be 256 bits")?;
+    generic_hash::<D, 32>(message)
+}
+
+/// Does a generic hash of the inputs returning the resulting `N` bytes separately.
+fn generic_hash<D: Digest, const N: usize>(message: &[u8]) -> Result<[u8; N], String> {
+    let output_bytes: [u8; N] = D::digest(message)
+        .as_slice()
+        .try_into()
+        .map_err(|_| format!("digest should be {} bits", N * 8))?;
 
     Ok(output_bytes)
 }
@@ -211,7 +369,46 @@ fn verify_secp256r1_ecdsa_signature(
 
 #[cfg(test)]
 mod test {
-    use super::{verify_secp256k1_ecdsa_signature, verify_secp256r1_ecdsa_signature};
+    use super::{
+        keccak256, sha256, verify_secp256k1_ecdsa_signature, verify_secp256r1_ecdsa_signature,
+    };
+
+    #[test]
+    fn sha256_matches_standard_vectors() {
+        // FIPS 180-2 test vectors.
+        assert_eq!(
+            sha256(b"").unwrap(),
+            [
+                227, 176, 196, 66, 152, 252, 28, 20, 154, 251, 244, 200, 153, 111, 185, 36, 39,
+                174, 65, 228, 100, 155, 147, 76, 164, 149, 153, 27, 120, 82, 184, 85
+            ]
+        );
+        assert_eq!(
+            sha256(b"abc").unwrap(),
+            [
+                186, 120, 22, 191, 143, 1, 207, 234, 65, 65, 64, 222, 93, 174, 34, 35, 176, 3, 97,
+                163, 150, 23, 122, 156, 180, 16, 255, 97, 242, 0, 21, 173
+            ]
+        );
+    }
+
+    #[test]
+    fn keccak256_matches_standard_vectors() {
+        assert_eq!(
+            keccak256(b"").unwrap(),
+            [
+                197, 210, 70, 1, 134, 247, 35, 60, 146, 126, 125, 178, 220, 199, 3, 192, 229, 0,
+                182, 83, 202, 130, 39, 59, 123, 250, 216, 4, 93, 133, 164, 112
+            ]
+        );
+        assert_eq!(
+            keccak256(b"abc").unwrap(),
+            [
+                78, 3, 101, 122, 234, 69, 169, 79, 199, 212, 123, 168, 38, 200, 214, 103, 192, 209,
+                230, 227, 58, 100, 160, 54, 236, 68, 245, 143, 161, 45, 108, 69
+            ]
+        );
+    }
 
     #[test]
     fn verifies_valid_k1_signature_with_low_s_value() {
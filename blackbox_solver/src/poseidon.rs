@@ -0,0 +1,228 @@
+//! An implementation of the Poseidon hash function over the native field which is compatible with [circomlib].
+//!
+//! The round constants and MDS matrix are generated using the Grain LFSR described in the [Poseidon paper][paper]
+//! with the same parameters as circomlib: an `x^5` S-box, 8 full rounds and a number of partial rounds
+//! which depends upon the width of the state.
+//!
+//! [circomlib]: https://github.com/iden3/circomlib/blob/master/circuits/poseidon.circom
+//! [paper]: https://eprint.iacr.org/2019/458.pdf
+
+use acir::{BlackBoxFunc, FieldElement};
+use num_bigint::BigUint;
+use once_cell::sync::OnceCell;
+
+use crate::BlackBoxResolutionError;
+
+const FULL_ROUNDS: usize = 8;
+
+/// The number of partial rounds used for each number of inputs, starting from a single input.
+const PARTIAL_ROUNDS: [usize; 16] =
+    [56, 57, 56, 60, 60, 63, 64, 63, 60, 66, 60, 65, 70, 60, 64, 68];
+
+/// The round constants and MDS matrix for a state of a given width.
+type Parameters = (Vec<FieldElement>, Vec<Vec<FieldElement>>);
+
+/// The parameters for each number of inputs, generated on first use as generating them is far more expensive than
+/// hashing.
+static PARAMETERS: [OnceCell<Parameters>; PARTIAL_ROUNDS.len()] = {
+    // Only used to initialize the array, so no cell is ever shared between its uses.
+    #[allow(clippy::declare_interior_mutable_const)]
+    const UNINITIALIZED: OnceCell<Parameters> = OnceCell::new();
+    [UNINITIALIZED; PARTIAL_ROUNDS.len()]
+};
+
+/// Calculates the Poseidon hash of between 1 and 16 field elements.
+pub fn poseidon(inputs: &[FieldElement]) -> Result<FieldElement, BlackBoxResolutionError> {
+    if inputs.is_empty() || inputs.len() > PARTIAL_ROUNDS.len() {
+        return Err(BlackBoxResolutionError::Failed(
+            BlackBoxFunc::Poseidon,
+            format!(
+                "expected between 1 and {} inputs but got {}",
+                PARTIAL_ROUNDS.len(),
+                inputs.len()
+            ),
+        ));
+    }
+
+    let width = inputs.len() + 1;
+    let partial_rounds = PARTIAL_ROUNDS[inputs.len() - 1];
+    let (round_constants, mds) =
+        PARAMETERS[inputs.len() - 1].get_or_init(|| generate_parameters(width, partial_rounds));
+
+    let mut state = Vec::with_capacity(width);
+    state.push(FieldElement::zero());
+    state.extend_from_slice(inputs);
+
+    for round in 0..FULL_ROUNDS + partial_rounds {
+        for (element, constant) in state.iter_mut().zip(&round_constants[round * width..]) {
+            *element += *constant;
+        }
+
+        let is_full_round = round < FULL_ROUNDS / 2 || round >= FULL_ROUNDS / 2 + partial_rounds;
+        if is_full_round {
+            state.iter_mut().for_each(|element| *element = sbox(*element));
+        } else {
+            state[0] = sbox(state[0]);
+        }
+
+        state = mds
+            .iter()
+            .map(|row| {
+                row.iter().zip(&state).fold(FieldElement::zero(), |acc, (m, s)| acc + *m * *s)
+            })
+            .collect();
+    }
+
+    Ok(state[0])
+}
+
+fn sbox(x: FieldElement) -> FieldElement {
+    let x_squared = x * x;
+    x_squared * x_squared * x
+}
+
+/// Generates the round constants and MDS matrix for a state of `width` elements.
+fn generate_parameters(width: usize, partial_rounds: usize) -> Parameters {
+    let mut lfsr = GrainLfsr::new(width, partial_rounds);
+
+    let round_constants = (0..(FULL_ROUNDS + partial_rounds) * width)
+        .map(|_| lfsr.next_field_element_rejection_sampled())
+        .collect();
+
+    // The MDS matrix is a Cauchy matrix `M[i][j] = 1 / (x_i + y_j)` for distinct `x_i` and `y_j`.
+    let mds = loop {
+        let elements: Vec<_> = (0..2 * width).map(|_| lfsr.next_field_element_reduced()).collect();
+        let all_distinct =
+            elements.iter().enumerate().all(|(i, element)| !elements[..i].contains(element));
+        if !all_distinct {
+            continue;
+        }
+
+        let (xs, ys) = elements.split_at(width);
+        let mds: Option<Vec<Vec<_>>> =
+            xs.iter().map(|x| ys.iter().map(|y| (*x + *y).try_inverse()).collect()).collect();
+        if let Some(mds) = mds {
+            break mds;
+        }
+    };
+
+    (round_constants, mds)
+}
+
+/// The Grain LFSR used to generate the Poseidon parameters, as specified in appendix F of the Poseidon paper.
+struct GrainLfsr {
+    bits: [bool; 80],
+    /// The index in `bits` of the oldest bit in the register.
+    position: usize,
+}
+
+impl GrainLfsr {
+    fn new(width: usize, partial_rounds: usize) -> Self {
+        let field_bits = FieldElement::max_num_bits() as usize;
+        // The field is a prime field (1) and the S-box is `x^alpha` (0).
+        let parameters = [
+            (1, 2),
+            (0, 4),
+            (field_bits, 12),
+            (width, 12),
+            (FULL_ROUNDS, 10),
+            (partial_rounds, 10),
+        ];
+
+        // The last 30 bits of the initial state are all set.
+        let mut bits = [true; 80];
+        let mut index = 0;
+        for (value, num_bits) in parameters {
+            for bit in (0..num_bits).rev() {
+                bits[index] = (value >> bit) & 1 == 1;
+                index += 1;
+            }
+        }
+
+        let mut lfsr = GrainLfsr { bits, position: 0 };
+        for _ in 0..160 {
+            lfsr.update();
+        }
+        lfsr
+    }
+
+    fn update(&mut self) -> bool {
+        let bit = |offset: usize| self.bits[(self.position + offset) % 80];
+        let new_bit = bit(62) ^ bit(51) ^ bit(38) ^ bit(23) ^ bit(13) ^ bit(0);
+        self.bits[self.position] = new_bit;
+        self.position = (self.position + 1) % 80;
+        new_bit
+    }
+
+    /// Output bits are taken in pairs, the second bit being output only if the first is set.
+    fn next_bit(&mut self) -> bool {
+        loop {
+            let keep = self.update();
+            let bit = self.update();
+            if keep {
+                return bit;
+            }
+        }
+    }
+
+    fn next_integer(&mut self) -> BigUint {
+        let mut integer = BigUint::default();
+        for _ in 0..FieldElement::max_num_bits() {
+            integer = (integer << 1u8) + u8::from(self.next_bit());
+        }
+        integer
+    }
+
+    fn next_field_element_rejection_sampled(&mut self) -> FieldElement {
+        let modulus = FieldElement::modulus();
+        loop {
+            let integer = self.next_integer();
+            if integer < modulus {
+                return FieldElement::from_be_bytes_reduce(&integer.to_bytes_be());
+            }
+        }
+    }
+
+    fn next_field_element_reduced(&mut self) -> FieldElement {
+        FieldElement::from_be_bytes_reduce(&self.next_integer().to_bytes_be())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acir::FieldElement;
+
+    use super::{poseidon, PARAMETERS};
+
+    #[test]
+    #[cfg(feature = "bn254")]
+    fn matches_circomlib() {
+        let hash = poseidon(&[FieldElement::from(1u128), FieldElement::from(2u128)]).unwrap();
+        assert_eq!(
+            hash.to_hex(),
+            "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+        );
+
+        let hash = poseidon(&[FieldElement::from(1u128)]).unwrap();
+        assert_eq!(
+            hash.to_hex(),
+            "29176100eaa962bdc1fe6c654d6a3c130e96a4d1168b33848b897dc502820133"
+        );
+    }
+
+    #[test]
+    fn reuses_parameters_across_calls() {
+        let inputs = [FieldElement::from(3u128), FieldElement::from(4u128)];
+        let hash = poseidon(&inputs).unwrap();
+        let parameters = PARAMETERS[inputs.len() - 1].get().unwrap();
+
+        assert_eq!(poseidon(&inputs).unwrap(), hash);
+        assert!(std::ptr::eq(PARAMETERS[inputs.len() - 1].get().unwrap(), parameters));
+    }
+
+    #[test]
+    fn rejects_invalid_number_of_inputs() {
+        assert!(poseidon(&[]).is_err());
+        assert!(poseidon(&[FieldElement::one(); 17]).is_err());
+    }
+}
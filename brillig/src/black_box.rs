@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 /// These opcodes provide an equivalent of ACIR blackbox functions.
 /// They are implemented as native functions in the VM.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BlackBoxOp {
     /// Calculates the SHA256 hash of the inputs.
     Sha256 { message: HeapVector, output: HeapArray },
@@ -0,0 +1,207 @@
+//! Renders Brillig bytecode in a compact mnemonic form which is easier to read than the [`Debug`] output.
+
+use std::fmt::Write;
+
+use crate::{
+    BinaryFieldOp, BinaryIntOp, BlackBoxOp, HeapArray, HeapNestedArray, HeapVector, Opcode,
+    RegisterIndex, RegisterOrMemory,
+};
+
+/// Renders `bytecode` with each opcode on its own line, prefixed by its program counter.
+///
+/// Registers are written as `r<index>`, heap arrays as `[<pointer>; <size>]`, heap vectors as
+/// `[<pointer>; <size register>]` and nested arrays as `[<pointer>; <shape array>]`, e.g. `[r0; [r1; 2]]`. Inputs
+/// are separated from outputs by an arrow, e.g. a foreign call is rendered as `0: FOREIGN_CALL invert (r0) -> (r0)`.
+pub fn disassemble(bytecode: &[Opcode]) -> String {
+    let mut output = String::new();
+    for (program_counter, opcode) in bytecode.iter().enumerate() {
+        writeln!(output, "{program_counter}: {}", opcode_mnemonic(opcode))
+            .expect("writing to a `String` cannot fail");
+    }
+    output
+}
+
+fn opcode_mnemonic(opcode: &Opcode) -> String {
+    match opcode {
+        Opcode::BinaryFieldOp { destination, op, lhs, rhs } => format!(
+            "FIELD_{} {}, {} -> {}",
+            binary_field_op(op),
+            register(lhs),
+            register(rhs),
+            register(destination)
+        ),
+        Opcode::BinaryIntOp { destination, op, bit_size, lhs, rhs } => format!(
+            "INT_{} u{bit_size} {}, {} -> {}",
+            binary_int_op(op),
+            register(lhs),
+            register(rhs),
+            register(destination)
+        ),
+        Opcode::JumpIfNot { condition, location } => {
+            format!("JUMP_IF_NOT {} @{location}", register(condition))
+        }
+        Opcode::JumpIf { condition, location } => {
+            format!("JUMP_IF {} @{location}", register(condition))
+        }
+        Opcode::Jump { location } => format!("JUMP @{location}"),
+        Opcode::Call { location } => format!("CALL @{location}"),
+        Opcode::Const { destination, value, bit_size } => {
+            format!("CONST u{bit_size} {} -> {}", value.to_field(), register(destination))
+        }
+        Opcode::Return => "RETURN".to_owned(),
+        Opcode::ForeignCall { function, destinations, inputs, .. } => format!(
+            "FOREIGN_CALL {function} ({}) -> ({})",
+            operands(inputs),
+            operands(destinations)
+        ),
+        Opcode::Mov { destination, source } => {
+            format!("MOV {} -> {}", register(source), register(destination))
+        }
+        Opcode::Load { destination, source_pointer } => {
+            format!("LOAD [{}] -> {}", register(source_pointer), register(destination))
+        }
+        Opcode::Store { destination_pointer, source } => {
+            format!("STORE {} -> [{}]", register(source), register(destination_pointer))
+        }
+        Opcode::BlackBox(op) => black_box_op(op),
+        Opcode::Trap => "TRAP".to_owned(),
+        Opcode::Stop => "STOP".to_owned(),
+        Opcode::Select { condition, if_true, if_false, destination } => format!(
+            "SELECT {} ? {} : {} -> {}",
+            register(condition),
+            register(if_true),
+            register(if_false),
+            register(destination)
+        ),
+        Opcode::TrapWithMessage { message } => format!("TRAP #{message}"),
+    }
+}
+
+fn binary_field_op(op: &BinaryFieldOp) -> &'static str {
+    match op {
+        BinaryFieldOp::Add => "ADD",
+        BinaryFieldOp::Sub => "SUB",
+        BinaryFieldOp::Mul => "MUL",
+        BinaryFieldOp::Div => "DIV",
+        BinaryFieldOp::Equals => "EQ",
+    }
+}
+
+fn binary_int_op(op: &BinaryIntOp) -> &'static str {
+    match op {
+        BinaryIntOp::Add => "ADD",
+        BinaryIntOp::Sub => "SUB",
+        BinaryIntOp::Mul => "MUL",
+        BinaryIntOp::SignedDiv => "SDIV",
+        BinaryIntOp::UnsignedDiv => "UDIV",
+        BinaryIntOp::Equals => "EQ",
+        BinaryIntOp::LessThan => "LT",
+        BinaryIntOp::LessThanEquals => "LTE",
+        BinaryIntOp::And => "AND",
+        BinaryIntOp::Or => "OR",
+        BinaryIntOp::Xor => "XOR",
+        BinaryIntOp::Shl => "SHL",
+        BinaryIntOp::Shr => "SHR",
+        BinaryIntOp::SignedLessThan => "SLT",
+        BinaryIntOp::SignedLessThanEquals => "SLTE",
+        BinaryIntOp::Ashr => "ASHR",
+        BinaryIntOp::Mod => "MOD",
+    }
+}
+
+fn black_box_op(op: &BlackBoxOp) -> String {
+    let (name, inputs, outputs) = match op {
+        BlackBoxOp::Sha256 { message, output } => {
+            ("SHA256", vec![heap_vector(message)], vec![heap_array(output)])
+        }
+        BlackBoxOp::Blake2s { message, output } => {
+            ("BLAKE2S", vec![heap_vector(message)], vec![heap_array(output)])
+        }
+        BlackBoxOp::Keccak256 { message, output } => {
+            ("KECCAK256", vec![heap_vector(message)], vec![heap_array(output)])
+        }
+        BlackBoxOp::HashToField128Security { message, output } => {
+            ("HASH_TO_FIELD_128_SECURITY", vec![heap_vector(message)], vec![register(output)])
+        }
+        BlackBoxOp::EcdsaSecp256k1 {
+            hashed_msg,
+            public_key_x,
+            public_key_y,
+            signature,
+            result,
+        } => (
+            "ECDSA_SECP256K1",
+            vec![
+                heap_vector(hashed_msg),
+                heap_array(public_key_x),
+                heap_array(public_key_y),
+                heap_array(signature),
+            ],
+            vec![register(result)],
+        ),
+        BlackBoxOp::EcdsaSecp256r1 {
+            hashed_msg,
+            public_key_x,
+            public_key_y,
+            signature,
+            result,
+        } => (
+            "ECDSA_SECP256R1",
+            vec![
+                heap_vector(hashed_msg),
+                heap_array(public_key_x),
+                heap_array(public_key_y),
+                heap_array(signature),
+            ],
+            vec![register(result)],
+        ),
+        BlackBoxOp::SchnorrVerify { public_key_x, public_key_y, message, signature, result } => (
+            "SCHNORR_VERIFY",
+            vec![
+                register(public_key_x),
+                register(public_key_y),
+                heap_vector(message),
+                heap_vector(signature),
+            ],
+            vec![register(result)],
+        ),
+        BlackBoxOp::Pedersen { inputs, domain_separator, output } => (
+            "PEDERSEN",
+            vec![heap_vector(inputs), register(domain_separator)],
+            vec![heap_array(output)],
+        ),
+        BlackBoxOp::FixedBaseScalarMul { low, high, result } => {
+            ("FIXED_BASE_SCALAR_MUL", vec![register(low), register(high)], vec![heap_array(result)])
+        }
+    };
+    format!("{name} ({}) -> ({})", inputs.join(", "), outputs.join(", "))
+}
+
+fn register(register: &RegisterIndex) -> String {
+    format!("r{}", register.to_usize())
+}
+
+fn heap_array(array: &HeapArray) -> String {
+    format!("[{}; {}]", register(&array.pointer), array.size)
+}
+
+fn heap_vector(vector: &HeapVector) -> String {
+    format!("[{}; {}]", register(&vector.pointer), register(&vector.size))
+}
+
+fn heap_nested_array(array: &HeapNestedArray) -> String {
+    format!("[{}; {}]", register(&array.pointer), heap_array(&array.shape))
+}
+
+fn operands(operands: &[RegisterOrMemory]) -> String {
+    operands
+        .iter()
+        .map(|operand| match operand {
+            RegisterOrMemory::RegisterIndex(index) => register(index),
+            RegisterOrMemory::HeapArray(array) => heap_array(array),
+            RegisterOrMemory::HeapVector(vector) => heap_vector(vector),
+            RegisterOrMemory::HeapNestedArray(array) => heap_nested_array(array),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,34 +1,105 @@
-use crate::value::Value;
+use crate::value::{Typ, Value};
 use serde::{Deserialize, Serialize};
 
-/// Single output of a [foreign call][crate::Opcode::ForeignCall].
+/// Describes the type of a single input or output of a [foreign call][crate::Opcode::ForeignCall].
+#[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ForeignCallParamType {
+    /// A single value of the given type.
+    Simple(Typ),
+    /// An array of values which all share the given type.
+    Array(Typ),
+}
+
+impl ForeignCallParamType {
+    /// Returns whether `param` is of this type.
+    ///
+    /// An array type matches nested arrays so long as every value they contain is of the array's type.
+    pub fn matches(&self, param: &ForeignCallParam) -> bool {
+        match (self, param) {
+            (ForeignCallParamType::Simple(typ), ForeignCallParam::Single(value)) => {
+                typ.contains(value)
+            }
+            (ForeignCallParamType::Array(typ), ForeignCallParam::Array(_)) => {
+                param.values().iter().all(|value| typ.contains(value))
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Single input or output of a [foreign call][crate::Opcode::ForeignCall].
+///
+/// Arrays may themselves contain arrays so that the shape of nested data, such as a matrix, is preserved.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
-pub enum ForeignCallOutput {
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ForeignCallParam {
     Single(Value),
-    Array(Vec<Value>),
+    Array(Vec<ForeignCallParam>),
+}
+
+impl ForeignCallParam {
+    /// Returns every value held by the parameter, flattening any nested arrays in order.
+    pub fn values(&self) -> Vec<Value> {
+        match self {
+            ForeignCallParam::Single(value) => vec![*value],
+            ForeignCallParam::Array(elements) => {
+                elements.iter().flat_map(ForeignCallParam::values).collect()
+            }
+        }
+    }
+
+    /// Returns the value of a [`ForeignCallParam::Single`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parameter is an array.
+    pub fn unwrap_value(&self) -> Value {
+        match self {
+            ForeignCallParam::Single(value) => *value,
+            ForeignCallParam::Array(_) => panic!("Expected single value, found array"),
+        }
+    }
+}
+
+/// Former name of [`ForeignCallParam`], from before foreign call parameters could be nested.
+#[deprecated(note = "use `ForeignCallParam` instead")]
+pub type ForeignCallOutput = ForeignCallParam;
+
+impl From<Value> for ForeignCallParam {
+    fn from(value: Value) -> Self {
+        ForeignCallParam::Single(value)
+    }
+}
+
+impl From<Vec<Value>> for ForeignCallParam {
+    fn from(values: Vec<Value>) -> Self {
+        ForeignCallParam::Array(values.into_iter().map(ForeignCallParam::Single).collect())
+    }
 }
 
 /// Represents the full output of a [foreign call][crate::Opcode::ForeignCall].
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct ForeignCallResult {
     /// Resolved output values of the foreign call.
-    pub values: Vec<ForeignCallOutput>,
+    pub values: Vec<ForeignCallParam>,
 }
 
 impl From<Value> for ForeignCallResult {
     fn from(value: Value) -> Self {
-        ForeignCallResult { values: vec![ForeignCallOutput::Single(value)] }
+        ForeignCallResult { values: vec![value.into()] }
     }
 }
 
 impl From<Vec<Value>> for ForeignCallResult {
     fn from(values: Vec<Value>) -> Self {
-        ForeignCallResult { values: vec![ForeignCallOutput::Array(values)] }
+        ForeignCallResult { values: vec![values.into()] }
     }
 }
 
-impl From<Vec<ForeignCallOutput>> for ForeignCallResult {
-    fn from(values: Vec<ForeignCallOutput>) -> Self {
+impl From<Vec<ForeignCallParam>> for ForeignCallResult {
+    fn from(values: Vec<ForeignCallParam>) -> Self {
         ForeignCallResult { values }
     }
 }
@@ -9,14 +9,19 @@
 //! [brillig_vm]: https://crates.io/crates/brillig_vm
 
 mod black_box;
+mod disassemble;
 mod foreign_call;
 mod opcodes;
 mod value;
 
 pub use black_box::BlackBoxOp;
-pub use foreign_call::{ForeignCallOutput, ForeignCallResult};
+pub use disassemble::disassemble;
+#[allow(deprecated)]
+pub use foreign_call::ForeignCallOutput;
+pub use foreign_call::{ForeignCallParam, ForeignCallParamType, ForeignCallResult};
 pub use opcodes::{
-    BinaryFieldOp, BinaryIntOp, HeapArray, HeapVector, RegisterIndex, RegisterOrMemory,
+    BinaryFieldOp, BinaryIntOp, HeapArray, HeapNestedArray, HeapVector, RegisterIndex,
+    RegisterOrMemory,
 };
 pub use opcodes::{Label, Opcode};
 pub use value::Typ;
@@ -1,9 +1,10 @@
-use crate::{black_box::BlackBoxOp, Value};
+use crate::{black_box::BlackBoxOp, ForeignCallParamType, Value};
 use serde::{Deserialize, Serialize};
 
 pub type Label = usize;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct RegisterIndex(pub usize);
 
 /// `RegisterIndex` refers to the index in VM register space.
@@ -21,6 +22,7 @@ impl From<usize> for RegisterIndex {
 
 /// A fixed-sized array starting from a Brillig register memory location.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct HeapArray {
     pub pointer: RegisterIndex,
     pub size: usize,
@@ -28,18 +30,31 @@ pub struct HeapArray {
 
 /// A register-sized vector passed starting from a Brillig register memory location and with a register-held size
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct HeapVector {
     pub pointer: RegisterIndex,
     pub size: RegisterIndex,
 }
 
+/// An array of arrays starting from a Brillig register memory location, with a memory-held shape.
+///
+/// The values are stored contiguously in row-major order. `shape` points to the length of each dimension, outermost
+/// first, so a 2x3 matrix has a `shape` of size 2 holding `[2, 3]` and occupies 6 cells.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HeapNestedArray {
+    pub pointer: RegisterIndex,
+    pub shape: HeapArray,
+}
+
 /// Lays out various ways an external foreign call's input and output data may be interpreted inside Brillig.
 /// This data can either be an individual register value or memory.
 ///
 /// While we are usually agnostic to how memory is passed within Brillig,
 /// this needs to be encoded somehow when dealing with an external system.
 /// For simplicity, the extra type information is given right in the ForeignCall instructions.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum RegisterOrMemory {
     /// A single register value passed to or from an external call
     /// It is an 'immediate' value - used without dereferencing memory.
@@ -54,9 +69,14 @@ pub enum RegisterOrMemory {
     /// In the case of a foreign call input, the vector is read from this Brillig memory location + as many cells as the 2nd register indicates.
     /// In the case of a foreign call output, the vector is written to this Brillig memory location and as 'size' cells, with size being stored in the second register.
     HeapVector(HeapVector),
+    /// A nested array passed to or from an external call
+    /// In the case of a foreign call input, the array is read from this Brillig memory location and passed on with the nesting given by its shape.
+    /// In the case of a foreign call output, the result must have the array's shape and is written to this Brillig memory location.
+    HeapNestedArray(HeapNestedArray),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Opcode {
     /// Takes the fields in registers `lhs` and `rhs`
     /// Performs the specified binary operation
@@ -96,9 +116,14 @@ pub enum Opcode {
     Call {
         location: Label,
     },
+    /// Loads an immediate value into the destination register as an integer of `bit_size` bits.
+    ///
+    /// As with the results of a [`BinaryIntOp`], the value is stored in two's complement, so a negative value
+    /// (one above `p / 2`) is sign extended to `bit_size` bits. Field constants use the bit size of the field.
     Const {
         destination: RegisterIndex,
         value: Value,
+        bit_size: u32,
     },
     Return,
     /// Used to get data from an outside source.
@@ -111,8 +136,14 @@ pub enum Opcode {
         function: String,
         /// Destination registers (may be single values or memory pointers).
         destinations: Vec<RegisterOrMemory>,
+        /// The expected types of the foreign call's outputs, one per destination.
+        ///
+        /// If present, the VM rejects foreign call results which do not match these types.
+        destination_types: Option<Vec<ForeignCallParamType>>,
         /// Input registers (may be single values or memory pointers).
         inputs: Vec<RegisterOrMemory>,
+        /// The types of the foreign call's inputs, one per input, which are passed on to the caller.
+        input_types: Option<Vec<ForeignCallParamType>>,
     },
     Mov {
         destination: RegisterIndex,
@@ -131,6 +162,23 @@ pub enum Opcode {
     Trap,
     /// Stop execution
     Stop,
+    /// Copies the value in `if_true` to `destination` if the value in `condition` is one,
+    /// or the value in `if_false` if it is zero, without branching.
+    ///
+    /// Execution fails if `condition` holds any other value.
+    Select {
+        condition: RegisterIndex,
+        if_true: RegisterIndex,
+        if_false: RegisterIndex,
+        destination: RegisterIndex,
+    },
+    /// Denotes execution failure as [`Opcode::Trap`] does, but also identifies the failure so that a diagnostic
+    /// can be reported, e.g. for a failed assertion.
+    ///
+    /// `message` is an index into the `trap_messages` table of the ACIR `Brillig` opcode which holds this bytecode.
+    TrapWithMessage {
+        message: usize,
+    },
 }
 
 impl Opcode {
@@ -151,12 +199,15 @@ impl Opcode {
             Opcode::BlackBox(_) => "black_box",
             Opcode::Trap => "trap",
             Opcode::Stop => "stop",
+            Opcode::Select { .. } => "select",
+            Opcode::TrapWithMessage { .. } => "trap_with_message",
         }
     }
 }
 
 /// Binary fixed-length field expressions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BinaryFieldOp {
     Add,
     Sub,
@@ -168,6 +219,7 @@ pub enum BinaryFieldOp {
 
 /// Binary fixed-length integer expressions
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum BinaryIntOp {
     Add,
     Sub,
@@ -188,6 +240,14 @@ pub enum BinaryIntOp {
     Xor,
     /// (<<) Shift left
     Shl,
-    /// (>>) Shift right
+    /// (>>) Logical shift right, filling the vacated bits with zeros
     Shr,
+    /// (<) Less than, interpreting both operands as two's complement signed integers
+    SignedLessThan,
+    /// (<=) Less or equal, interpreting both operands as two's complement signed integers
+    SignedLessThanEquals,
+    /// (>>) Arithmetic shift right, filling the vacated bits with copies of the sign bit
+    Ashr,
+    /// (%) Unsigned remainder of the division of the truncated operands
+    Mod,
 }
@@ -4,14 +4,30 @@ use std::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Types of values allowed in the VM
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Typ {
     Field,
     Unsigned { bit_size: u32 },
     Signed { bit_size: u32 },
 }
 
+impl Typ {
+    /// Returns whether `value` can be represented by this type.
+    ///
+    /// Signed integers are expected in their two's complement representation.
+    pub fn contains(&self, value: &Value) -> bool {
+        match self {
+            Typ::Field => true,
+            Typ::Unsigned { bit_size } | Typ::Signed { bit_size } => {
+                value.to_field().num_bits() <= *bit_size
+            }
+        }
+    }
+}
+
 /// `Value` represents the base descriptor for a value in the VM.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Value {
     inner: FieldElement,
 }
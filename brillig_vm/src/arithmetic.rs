@@ -3,6 +3,8 @@ use acir::FieldElement;
 use num_bigint::{BigInt, BigUint};
 use num_traits::{One, ToPrimitive, Zero};
 
+use crate::BrilligError;
+
 /// Evaluate a binary operation on two FieldElements and return the result as a FieldElement.
 pub(crate) fn evaluate_binary_field_op(
     op: &BinaryFieldOp,
@@ -20,24 +22,34 @@ pub(crate) fn evaluate_binary_field_op(
 }
 
 /// Evaluate a binary operation on two unsigned big integers with a given bit size and return the result as a big integer.
+///
+/// Signed operations interpret their operands as two's complement integers of `bit_size` bits.
 pub(crate) fn evaluate_binary_bigint_op(
     op: &BinaryIntOp,
     a: BigUint,
     b: BigUint,
     bit_size: u32,
-) -> BigUint {
+) -> Result<BigUint, BrilligError> {
     let bit_modulo = &(BigUint::one() << bit_size);
-    match op {
+    if matches!(op, BinaryIntOp::UnsignedDiv | BinaryIntOp::SignedDiv | BinaryIntOp::Mod)
+        && (&b % bit_modulo).is_zero()
+    {
+        return Err(BrilligError::DivisionByZero);
+    }
+
+    let result = match op {
         // Perform addition, subtraction, and multiplication, applying a modulo operation to keep the result within the bit size.
         BinaryIntOp::Add => (a + b) % bit_modulo,
         BinaryIntOp::Sub => (bit_modulo + a - b) % bit_modulo,
         BinaryIntOp::Mul => (a * b) % bit_modulo,
         // Perform unsigned division using the modulo operation on a and b.
         BinaryIntOp::UnsignedDiv => (a % bit_modulo) / (b % bit_modulo),
+        // Perform unsigned remainder on the truncated a and b.
+        BinaryIntOp::Mod => (a % bit_modulo) % (b % bit_modulo),
         // Perform signed division by first converting a and b to signed integers and then back to unsigned after the operation.
         BinaryIntOp::SignedDiv => {
             let signed_div = to_big_signed(a, bit_size) / to_big_signed(b, bit_size);
-            to_big_unsigned(signed_div, bit_size)
+            to_big_unsigned(signed_div, bit_size) % bit_modulo
         }
         // Perform a == operation, returning 0 or 1
         BinaryIntOp::Equals => {
@@ -63,21 +75,63 @@ pub(crate) fn evaluate_binary_bigint_op(
                 BigUint::zero()
             }
         }
+        // Perform a signed < operation, returning 0 or 1
+        BinaryIntOp::SignedLessThan => {
+            if to_big_signed(a % bit_modulo, bit_size) < to_big_signed(b % bit_modulo, bit_size) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
+        // Perform a signed <= operation, returning 0 or 1
+        BinaryIntOp::SignedLessThanEquals => {
+            if to_big_signed(a % bit_modulo, bit_size) <= to_big_signed(b % bit_modulo, bit_size) {
+                BigUint::one()
+            } else {
+                BigUint::zero()
+            }
+        }
         // Perform bitwise AND, OR, XOR, left shift, and right shift operations, applying a modulo operation to keep the result within the bit size.
         BinaryIntOp::And => (a & b) % bit_modulo,
         BinaryIntOp::Or => (a | b) % bit_modulo,
         BinaryIntOp::Xor => (a ^ b) % bit_modulo,
+        // Shifting by the bit size or more shifts out every bit of `a`.
+        BinaryIntOp::Shl | BinaryIntOp::Shr if b >= BigUint::from(bit_size) => BigUint::zero(),
         BinaryIntOp::Shl => {
-            assert!(bit_size <= 128, "unsupported bit size for right shift");
-            let b = b.to_u128().unwrap();
+            let b = b.to_u32().unwrap();
             (a << b) % bit_modulo
         }
         BinaryIntOp::Shr => {
-            assert!(bit_size <= 128, "unsupported bit size for right shift");
-            let b = b.to_u128().unwrap();
-            (a >> b) % bit_modulo
+            let b = b.to_u32().unwrap();
+            (a % bit_modulo) >> b
         }
+        // Perform an arithmetic right shift, which rounds towards negative infinity.
+        BinaryIntOp::Ashr => {
+            let b = b.min(BigUint::from(bit_size)).to_u32().unwrap();
+            let shifted = to_big_signed(a % bit_modulo, bit_size) >> b;
+            to_big_unsigned(shifted, bit_size) % bit_modulo
+        }
+    };
+    Ok(result)
+}
+
+/// Converts an immediate `value` to the representation of a `bit_size`-bit integer used by
+/// [`evaluate_binary_bigint_op`].
+///
+/// Values above `p / 2` are negative and are sign extended to `bit_size` bits in two's complement, while other values
+/// are truncated to `bit_size` bits. Values with the bit size of the field are left unchanged.
+pub(crate) fn to_typed_constant(value: FieldElement, bit_size: u32) -> FieldElement {
+    if bit_size >= FieldElement::max_num_bits() {
+        return value;
     }
+    let bit_modulo = BigUint::one() << bit_size;
+    let typed = if value.is_negative() {
+        let magnitude = BigUint::from_bytes_be(&(-value).to_be_bytes()) % &bit_modulo;
+        (&bit_modulo - magnitude) % &bit_modulo
+    } else {
+        BigUint::from_bytes_be(&value.to_be_bytes()) % &bit_modulo
+    };
+    FieldElement::from_be_bytes_reduce(&typed.to_bytes_be())
 }
 
 fn to_big_signed(a: BigUint, bit_size: u32) -> BigInt {
@@ -111,7 +165,7 @@ mod tests {
         // Convert to big integers
         let lhs_big = BigUint::from(a);
         let rhs_big = BigUint::from(b);
-        let result_value = evaluate_binary_bigint_op(op, lhs_big, rhs_big, bit_size);
+        let result_value = evaluate_binary_bigint_op(op, lhs_big, rhs_big, bit_size).unwrap();
         // Convert back to u128
         result_value.to_u128().unwrap()
     }
@@ -213,6 +267,21 @@ mod tests {
         evaluate_int_ops(test_ops, BinaryIntOp::UnsignedDiv, bit_size);
     }
 
+    #[test]
+    fn mod_test() {
+        let bit_size = 32;
+
+        let test_ops = vec![
+            TestParams { a: 7, b: 3, result: 1 },
+            TestParams { a: 6, b: 3, result: 0 },
+            TestParams { a: 2, b: 5, result: 2 },
+            // Operands are truncated to the bit size before taking the remainder
+            TestParams { a: 2_u128.pow(bit_size) + 7, b: 3, result: 1 },
+        ];
+
+        evaluate_int_ops(test_ops, BinaryIntOp::Mod, bit_size);
+    }
+
     #[test]
     fn to_signed_roundtrip() {
         let bit_size = 32;
@@ -228,8 +297,98 @@ mod tests {
             TestParams { a: 5, b: to_negative(10, bit_size), result: 0 },
             TestParams { a: 5, b: to_negative(1, bit_size), result: to_negative(5, bit_size) },
             TestParams { a: to_negative(5, bit_size), b: to_negative(1, bit_size), result: 5 },
+            // Signed division truncates towards zero
+            TestParams { a: to_negative(7, bit_size), b: 2, result: to_negative(3, bit_size) },
         ];
 
         evaluate_int_ops(test_ops, BinaryIntOp::SignedDiv, bit_size);
     }
+
+    #[test]
+    fn signed_cmp_test() {
+        let bit_size = 32;
+
+        let test_ops = vec![
+            TestParams { a: to_negative(5, bit_size), b: to_negative(3, bit_size), result: 1 },
+            TestParams { a: to_negative(3, bit_size), b: to_negative(5, bit_size), result: 0 },
+            TestParams { a: to_negative(1, bit_size), b: 1, result: 1 },
+            TestParams { a: 1, b: to_negative(1, bit_size), result: 0 },
+            TestParams { a: to_negative(3, bit_size), b: to_negative(3, bit_size), result: 0 },
+        ];
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedLessThan, bit_size);
+
+        let test_ops = vec![
+            TestParams { a: to_negative(5, bit_size), b: to_negative(3, bit_size), result: 1 },
+            TestParams { a: to_negative(3, bit_size), b: to_negative(3, bit_size), result: 1 },
+            TestParams { a: 1, b: to_negative(1, bit_size), result: 0 },
+        ];
+        evaluate_int_ops(test_ops, BinaryIntOp::SignedLessThanEquals, bit_size);
+    }
+
+    #[test]
+    fn shift_test() {
+        for bit_size in [8, 32, 64] {
+            let max = 2_u128.pow(bit_size) - 1;
+            let msb = 2_u128.pow(bit_size - 1);
+
+            let shl = vec![
+                TestParams { a: 1, b: 3, result: 8 },
+                // Bits shifted past the bit size are discarded
+                TestParams { a: max, b: 7, result: (max << 7) & max },
+                TestParams { a: 1, b: bit_size.into(), result: 0 },
+                TestParams { a: 1, b: 200, result: 0 },
+            ];
+            evaluate_int_ops(shl, BinaryIntOp::Shl, bit_size);
+
+            let shr = vec![
+                TestParams { a: 8, b: 3, result: 1 },
+                TestParams { a: msb, b: 7, result: msb >> 7 },
+                TestParams { a: max, b: bit_size.into(), result: 0 },
+                TestParams { a: max, b: 200, result: 0 },
+            ];
+            evaluate_int_ops(shr, BinaryIntOp::Shr, bit_size);
+
+            let ashr = vec![
+                TestParams { a: 8, b: 3, result: 1 },
+                TestParams { a: to_negative(8, bit_size), b: 3, result: to_negative(1, bit_size) },
+                // Rounds towards negative infinity
+                TestParams { a: to_negative(7, bit_size), b: 1, result: to_negative(4, bit_size) },
+                TestParams { a: msb, b: 7, result: to_negative(msb >> 7, bit_size) },
+                TestParams { a: 5, b: 200, result: 0 },
+                TestParams { a: to_negative(5, bit_size), b: 200, result: max },
+            ];
+            evaluate_int_ops(ashr, BinaryIntOp::Ashr, bit_size);
+        }
+    }
+
+    #[test]
+    fn division_by_zero() {
+        for op in [BinaryIntOp::UnsignedDiv, BinaryIntOp::SignedDiv, BinaryIntOp::Mod] {
+            // 16 is zero once truncated to 4 bits
+            let result = evaluate_binary_bigint_op(&op, 5u128.into(), 16u128.into(), 4);
+            assert_eq!(result, Err(BrilligError::DivisionByZero));
+        }
+
+        let result = evaluate_binary_bigint_op(&BinaryIntOp::Mod, 7u128.into(), 0u128.into(), 32);
+        assert_eq!(result, Err(BrilligError::DivisionByZero));
+    }
+
+    #[test]
+    fn typed_constants_are_sign_extended() {
+        let typed = |value: i128, bit_size| to_typed_constant(FieldElement::from(value), bit_size);
+        assert_eq!(typed(5, 8), FieldElement::from(5_u128));
+        assert_eq!(typed(300, 8), FieldElement::from(44_u128));
+        assert_eq!(typed(-1, 8), FieldElement::from(255_u128));
+        assert_eq!(typed(-300, 8), FieldElement::from(212_u128));
+
+        // A sign extended constant compares as negative in signed operations of the same bit size.
+        let minus_one = BigUint::from_bytes_be(&typed(-1, 8).to_be_bytes());
+        let result =
+            evaluate_binary_bigint_op(&BinaryIntOp::SignedLessThan, minus_one, BigUint::zero(), 8);
+        assert_eq!(result, Ok(BigUint::one()));
+
+        // Field constants are stored as they are.
+        let minus_one = -FieldElement::one();
+        assert_eq!(to_typed_constant(minus_one, FieldElement::max_num_bits()), minus_one);
+    }
 }
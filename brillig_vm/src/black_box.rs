@@ -1,27 +1,26 @@
 use acir::brillig::{BlackBoxOp, HeapArray, HeapVector, Value};
 use acir::{BlackBoxFunc, FieldElement};
-use acvm_blackbox_solver::{
-    blake2s, ecdsa_secp256k1_verify, ecdsa_secp256r1_verify, hash_to_field_128_security, keccak256,
-    sha256, BlackBoxFunctionSolver, BlackBoxResolutionError,
-};
+use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
 
-use crate::{Memory, Registers};
+use crate::{BrilligError, Memory, Registers};
 
 fn read_heap_vector<'a>(
     memory: &'a Memory,
     registers: &Registers,
     vector: &HeapVector,
-) -> &'a [Value] {
-    memory
-        .read_slice(registers.get(vector.pointer).to_usize(), registers.get(vector.size).to_usize())
+) -> Result<&'a [Value], BrilligError> {
+    memory.try_read_slice(
+        registers.try_get(vector.pointer)?.to_usize(),
+        registers.try_get(vector.size)?.to_usize(),
+    )
 }
 
 fn read_heap_array<'a>(
     memory: &'a Memory,
     registers: &Registers,
     array: &HeapArray,
-) -> &'a [Value] {
-    memory.read_slice(registers.get(array.pointer).to_usize(), array.size)
+) -> Result<&'a [Value], BrilligError> {
+    memory.try_read_slice(registers.try_get(array.pointer)?.to_usize(), array.size)
 }
 
 /// Extracts the last byte of every value
@@ -39,35 +38,32 @@ fn to_value_vec(input: &[u8]) -> Vec<Value> {
     input.iter().map(|x| Value::from(*x as usize)).collect()
 }
 
-pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
+pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver + ?Sized>(
     op: &BlackBoxOp,
     solver: &Solver,
     registers: &mut Registers,
     memory: &mut Memory,
-) -> Result<(), BlackBoxResolutionError> {
+) -> Result<(), BrilligError> {
     match op {
         BlackBoxOp::Sha256 { message, output } => {
-            let message = to_u8_vec(read_heap_vector(memory, registers, message));
-            let bytes = sha256(message.as_slice())?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes));
-            Ok(())
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let bytes = solver.sha256(message.as_slice())?;
+            memory.write_slice(registers.try_get(output.pointer)?.to_usize(), &to_value_vec(&bytes))
         }
         BlackBoxOp::Blake2s { message, output } => {
-            let message = to_u8_vec(read_heap_vector(memory, registers, message));
-            let bytes = blake2s(message.as_slice())?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes));
-            Ok(())
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let bytes = solver.blake2s(message.as_slice())?;
+            memory.write_slice(registers.try_get(output.pointer)?.to_usize(), &to_value_vec(&bytes))
         }
         BlackBoxOp::Keccak256 { message, output } => {
-            let message = to_u8_vec(read_heap_vector(memory, registers, message));
-            let bytes = keccak256(message.as_slice())?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &to_value_vec(&bytes));
-            Ok(())
+            let message = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let bytes = solver.keccak256(message.as_slice())?;
+            memory.write_slice(registers.try_get(output.pointer)?.to_usize(), &to_value_vec(&bytes))
         }
         BlackBoxOp::HashToField128Security { message, output } => {
-            let field = hash_to_field_128_security(&to_u8_vec(read_heap_vector(
+            let field = solver.hash_to_field_128_security(&to_u8_vec(read_heap_vector(
                 memory, registers, message,
-            )))?;
+            )?))?;
             registers.set(*output, field.into());
             Ok(())
         }
@@ -95,7 +91,7 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
                 memory,
                 registers,
                 public_key_x,
-            ))
+            )?)
             .try_into()
             .map_err(|_| {
                 BlackBoxResolutionError::Failed(bb_func, "Invalid public key x length".to_string())
@@ -104,26 +100,32 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
                 memory,
                 registers,
                 public_key_y,
-            ))
+            )?)
             .try_into()
             .map_err(|_| {
                 BlackBoxResolutionError::Failed(bb_func, "Invalid public key y length".to_string())
             })?;
-            let signature: [u8; 64] = to_u8_vec(read_heap_array(memory, registers, signature))
+            let signature: [u8; 64] = to_u8_vec(read_heap_array(memory, registers, signature)?)
                 .try_into()
                 .map_err(|_| {
                     BlackBoxResolutionError::Failed(bb_func, "Invalid signature length".to_string())
                 })?;
 
-            let hashed_msg = to_u8_vec(read_heap_vector(memory, registers, hashed_msg));
+            let hashed_msg = to_u8_vec(read_heap_vector(memory, registers, hashed_msg)?);
 
             let result = match op {
-                BlackBoxOp::EcdsaSecp256k1 { .. } => {
-                    ecdsa_secp256k1_verify(&hashed_msg, &public_key_x, &public_key_y, &signature)?
-                }
-                BlackBoxOp::EcdsaSecp256r1 { .. } => {
-                    ecdsa_secp256r1_verify(&hashed_msg, &public_key_x, &public_key_y, &signature)?
-                }
+                BlackBoxOp::EcdsaSecp256k1 { .. } => solver.ecdsa_secp256k1_verify(
+                    &hashed_msg,
+                    &public_key_x,
+                    &public_key_y,
+                    &signature,
+                )?,
+                BlackBoxOp::EcdsaSecp256r1 { .. } => solver.ecdsa_secp256r1_verify(
+                    &hashed_msg,
+                    &public_key_x,
+                    &public_key_y,
+                    &signature,
+                )?,
                 _ => unreachable!(),
             };
 
@@ -131,35 +133,33 @@ pub(crate) fn evaluate_black_box<Solver: BlackBoxFunctionSolver>(
             Ok(())
         }
         BlackBoxOp::SchnorrVerify { public_key_x, public_key_y, message, signature, result } => {
-            let public_key_x = registers.get(*public_key_x).to_field();
-            let public_key_y = registers.get(*public_key_y).to_field();
-            let message: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, message));
-            let signature: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, signature));
+            let public_key_x = registers.try_get(*public_key_x)?.to_field();
+            let public_key_y = registers.try_get(*public_key_y)?.to_field();
+            let message: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, message)?);
+            let signature: Vec<u8> = to_u8_vec(read_heap_vector(memory, registers, signature)?);
             let verified =
                 solver.schnorr_verify(&public_key_x, &public_key_y, &signature, &message)?;
             registers.set(*result, verified.into());
             Ok(())
         }
         BlackBoxOp::FixedBaseScalarMul { low, high, result } => {
-            let low = registers.get(*low).to_field();
-            let high = registers.get(*high).to_field();
+            let low = registers.try_get(*low)?.to_field();
+            let high = registers.try_get(*high)?.to_field();
             let (x, y) = solver.fixed_base_scalar_mul(&low, &high)?;
-            memory.write_slice(registers.get(result.pointer).to_usize(), &[x.into(), y.into()]);
-            Ok(())
+            memory.write_slice(registers.try_get(result.pointer)?.to_usize(), &[x.into(), y.into()])
         }
         BlackBoxOp::Pedersen { inputs, domain_separator, output } => {
             let inputs: Vec<FieldElement> =
-                read_heap_vector(memory, registers, inputs).iter().map(|x| x.to_field()).collect();
+                read_heap_vector(memory, registers, inputs)?.iter().map(|x| x.to_field()).collect();
             let domain_separator: u32 =
-                registers.get(*domain_separator).to_u128().try_into().map_err(|_| {
+                registers.try_get(*domain_separator)?.to_u128().try_into().map_err(|_| {
                     BlackBoxResolutionError::Failed(
                         BlackBoxFunc::Pedersen,
                         "Invalid signature length".to_string(),
                     )
                 })?;
             let (x, y) = solver.pedersen(&inputs, domain_separator)?;
-            memory.write_slice(registers.get(output.pointer).to_usize(), &[x.into(), y.into()]);
-            Ok(())
+            memory.write_slice(registers.try_get(output.pointer)?.to_usize(), &[x.into(), y.into()])
         }
     }
 }
@@ -181,7 +181,7 @@ mod test {
         let mut memory = Memory::from(vec![]);
         let message_pointer = 0;
         let result_pointer = message_pointer + message_length;
-        memory.write_slice(message_pointer, to_value_vec(&message).as_slice());
+        memory.write_slice(message_pointer, to_value_vec(&message).as_slice()).unwrap();
 
         let mut registers = Registers {
             inner: vec![
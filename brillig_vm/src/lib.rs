@@ -10,8 +10,8 @@
 //! [acvm]: https://crates.io/crates/acvm
 
 use acir::brillig::{
-    BinaryFieldOp, BinaryIntOp, ForeignCallOutput, ForeignCallResult, HeapArray, HeapVector,
-    Opcode, RegisterIndex, RegisterOrMemory, Value,
+    BinaryFieldOp, BinaryIntOp, ForeignCallParam, ForeignCallParamType, ForeignCallResult,
+    HeapArray, HeapNestedArray, HeapVector, Opcode, RegisterIndex, RegisterOrMemory, Value,
 };
 use acir::FieldElement;
 // Re-export `brillig`.
@@ -23,22 +23,79 @@ mod memory;
 mod registers;
 
 use acvm_blackbox_solver::{BlackBoxFunctionSolver, BlackBoxResolutionError};
-use arithmetic::{evaluate_binary_bigint_op, evaluate_binary_field_op};
+use arithmetic::{evaluate_binary_bigint_op, evaluate_binary_field_op, to_typed_constant};
 use black_box::evaluate_black_box;
 
 pub use memory::Memory;
 use num_bigint::BigUint;
 pub use registers::Registers;
+use std::time::Instant;
+use thiserror::Error;
+
+/// The number of opcodes executed between checks of the VM's deadline.
+///
+/// Brillig opcodes are cheap relative to reading the clock, so it is only read periodically.
+const DEADLINE_CHECK_INTERVAL: u64 = 1024;
 
 /// The error call stack contains the opcode indexes of the call stack at the time of failure, plus the index of the opcode that failed.
 pub type ErrorCallStack = Vec<usize>;
 
+/// The reason for which the VM failed to execute a Brillig process.
+#[derive(Debug, PartialEq, Eq, Clone, Error)]
+pub enum BrilligError {
+    #[error("explicit trap hit in brillig")]
+    Trap,
+    #[error("return opcode hit, but callstack already empty")]
+    EmptyCallStack,
+    #[error("{results} output values were provided as a foreign call result for {destinations} destination slots")]
+    ForeignCallResultCountMismatch { results: usize, destinations: usize },
+    #[error("Function result size does not match brillig bytecode")]
+    InvalidForeignCallResult,
+    /// A foreign call output does not match the type declared for its destination.
+    #[error("foreign call output {index} does not match its declared type {expected:?}")]
+    ForeignCallResultTypeMismatch { index: usize, expected: ForeignCallParamType },
+    /// A foreign call declares a different number of types than it has inputs or destinations.
+    #[error("foreign call declares {types} types for {operands} operands")]
+    ForeignCallTypeCountMismatch { types: usize, operands: usize },
+    #[error(transparent)]
+    BlackBox(#[from] BlackBoxResolutionError),
+    /// The divisor of an integer division was zero.
+    #[error("attempted to divide by zero")]
+    DivisionByZero,
+    /// A [`HeapArray`] or [`HeapVector`] extends past the end of the VM's memory.
+    #[error("memory access out of bounds: offset {offset} of a {size} element slice at pointer {pointer} lies past the end of memory")]
+    MemoryOutOfBounds { pointer: usize, offset: usize, size: usize },
+    /// The VM executed its maximum number of opcodes without finishing.
+    #[error("brillig step limit exceeded after executing {steps} opcodes")]
+    StepLimitExceeded { steps: u64 },
+    /// The VM's deadline passed before it finished.
+    #[error("brillig deadline exceeded after executing {steps} opcodes")]
+    DeadlineExceeded { steps: u64 },
+    /// A register was read before any value was assigned to it or to a higher register.
+    #[error("register {index} was read but only {max} registers have been assigned")]
+    InvalidRegister { index: usize, max: usize },
+    /// The condition of a [`Opcode::Select`] was neither zero nor one.
+    #[error("select condition must be 0 or 1 but was {}", value.to_field())]
+    NonBooleanCondition { value: Value },
+    /// A write would have grown the VM's memory past its limit.
+    #[error(
+        "memory limit exceeded: a write requires {size} memory cells but only {limit} are allowed"
+    )]
+    MemoryLimitExceeded { size: usize, limit: usize },
+    /// A [`Opcode::TrapWithMessage`] was executed.
+    ///
+    /// `message` indexes the `trap_messages` of the ACIR `Brillig` opcode being executed. `pc` is the same as the
+    /// last entry of the failure's call stack, and is repeated so that the error is self-contained when displayed.
+    #[error("explicit trap hit in brillig at program counter {pc} with message {message}")]
+    Trapped { message: usize, pc: usize },
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum VMStatus {
     Finished,
     InProgress,
     Failure {
-        message: String,
+        error: BrilligError,
         call_stack: ErrorCallStack,
     },
     /// The VM process is not solvable as a [foreign call][Opcode::ForeignCall] has been
@@ -51,14 +108,100 @@ pub enum VMStatus {
         /// Interpreted by simulator context
         function: String,
         /// Input values
-        /// Each input is a list of values as an input can be either a single value or a memory pointer
-        inputs: Vec<Vec<Value>>,
+        /// Each input is either a single value or an array, which may be nested, read from memory
+        inputs: Vec<ForeignCallParam>,
     },
 }
 
+/// A [foreign call][Opcode::ForeignCall] which the VM is paused on, see [`VM::pending_foreign_call`].
+///
+/// The caller must compute the result of the call, e.g. by querying an external service, and pass it
+/// to [`VM::resolve_foreign_call`] before resuming the VM.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PendingForeignCall {
+    /// An identifier interpreted by the caller process
+    pub function: String,
+    /// Resolved inputs to the foreign call
+    pub inputs: Vec<ForeignCallParam>,
+    /// The types of the inputs, if declared by the foreign call opcode
+    pub input_types: Option<Vec<ForeignCallParamType>>,
+}
+
+/// The outcome of executing a single opcode with [`VM::step`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum StepResult {
+    /// The opcode was executed and the VM can continue onto the next opcode.
+    Continue,
+    /// The VM has finished executing the bytecode.
+    Finished,
+    /// The VM is paused on a [foreign call][Opcode::ForeignCall] whose result has not been provided yet.
+    ForeignCallWait {
+        /// Interpreted by simulator context
+        function: String,
+        /// Input values
+        inputs: Vec<ForeignCallParam>,
+    },
+    /// The VM failed while executing the opcode.
+    Failure { error: BrilligError, call_stack: ErrorCallStack },
+}
+
+impl From<VMStatus> for StepResult {
+    fn from(status: VMStatus) -> Self {
+        match status {
+            VMStatus::InProgress => StepResult::Continue,
+            VMStatus::Finished => StepResult::Finished,
+            VMStatus::ForeignCallWait { function, inputs } => {
+                StepResult::ForeignCallWait { function, inputs }
+            }
+            VMStatus::Failure { error, call_stack } => StepResult::Failure { error, call_stack },
+        }
+    }
+}
+
+/// A record of a single opcode executed by the VM, see [`VM::with_tracing`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TraceEntry {
+    /// The program counter of the executed opcode.
+    pub pc: usize,
+    /// The executed opcode.
+    pub opcode: Opcode,
+    /// The contents of the registers before the opcode was executed.
+    pub registers_before: Vec<Value>,
+}
+
+/// An owned copy of the execution state of a [`VM`], see [`VM::snapshot`].
+///
+/// The VM's bytecode, black box solver and step limit are not part of its state and so are not captured.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VMSnapshot {
+    registers: Registers,
+    program_counter: usize,
+    foreign_call_counter: usize,
+    foreign_call_results: Vec<ForeignCallResult>,
+    status: VMStatus,
+    memory: Memory,
+    call_stack: Vec<Value>,
+    steps: u64,
+    trace: Option<Vec<TraceEntry>>,
+}
+
+impl VMSnapshot {
+    pub fn program_counter(&self) -> usize {
+        self.program_counter
+    }
+
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 /// VM encapsulates the state of the Brillig VM during execution.
-pub struct VM<'bb_solver, B: BlackBoxFunctionSolver> {
+pub struct VM<'bb_solver, B: BlackBoxFunctionSolver + ?Sized> {
     /// Register storage
     registers: Registers,
     /// Instruction pointer
@@ -79,9 +222,17 @@ pub struct VM<'bb_solver, B: BlackBoxFunctionSolver> {
     call_stack: Vec<Value>,
     /// The solver for blackbox functions
     black_box_solver: &'bb_solver B,
+    /// The maximum number of opcodes which may be executed before the VM fails.
+    max_steps: Option<u64>,
+    /// The time after which the VM fails rather than executing further opcodes.
+    deadline: Option<Instant>,
+    /// The number of opcodes which have been executed so far.
+    steps: u64,
+    /// A record of every executed opcode, if tracing has been enabled.
+    trace: Option<Vec<TraceEntry>>,
 }
 
-impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
+impl<'bb_solver, B: BlackBoxFunctionSolver + ?Sized> VM<'bb_solver, B> {
     /// Constructs a new VM instance
     pub fn new(
         inputs: Registers,
@@ -89,20 +240,86 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         bytecode: Vec<Opcode>,
         foreign_call_results: Vec<ForeignCallResult>,
         black_box_solver: &'bb_solver B,
+    ) -> Self {
+        let mut vm =
+            Self::with_initial_state(inputs.inner, memory.into(), bytecode, black_box_solver);
+        vm.foreign_call_results = foreign_call_results;
+        vm
+    }
+
+    /// Constructs a new VM instance whose registers and memory start with the provided values.
+    ///
+    /// This allows resuming a computation from a previously captured state, or executing
+    /// a single opcode in isolation.
+    pub fn with_initial_state(
+        registers: Vec<Value>,
+        memory: Memory,
+        bytecode: Vec<Opcode>,
+        black_box_solver: &'bb_solver B,
     ) -> Self {
         Self {
-            registers: inputs,
+            registers: Registers::load(registers),
             program_counter: 0,
             foreign_call_counter: 0,
-            foreign_call_results,
+            foreign_call_results: Vec::new(),
             bytecode,
             status: VMStatus::InProgress,
-            memory: memory.into(),
+            memory,
             call_stack: Vec::new(),
             black_box_solver,
+            max_steps: None,
+            deadline: None,
+            steps: 0,
+            trace: None,
         }
     }
 
+    /// Records a [`TraceEntry`] for every opcode executed by the VM.
+    ///
+    /// The trace is kept across [foreign call][Opcode::ForeignCall] pauses and can be read with [`VM::trace`].
+    pub fn with_tracing(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    /// Returns the opcodes executed so far, or an empty slice if tracing is disabled.
+    pub fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or_default()
+    }
+
+    /// Limits the number of opcodes the VM may execute, failing with
+    /// [`BrilligError::StepLimitExceeded`] once `max_steps` opcodes have been executed.
+    ///
+    /// This guards against bytecode which never terminates, e.g. due to a backwards [`Opcode::Jump`].
+    pub fn with_max_steps(mut self, max_steps: Option<u64>) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Limits the time the VM may run for, failing with [`BrilligError::DeadlineExceeded`] once `deadline` has
+    /// passed.
+    ///
+    /// The clock is checked before the first opcode and then every 1024 opcodes, so the VM may run slightly past
+    /// the deadline.
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Limits the number of memory cells the VM may use, failing with [`BrilligError::MemoryLimitExceeded`] on the
+    /// first write which would grow the memory past `memory_limit_cells`.
+    ///
+    /// This guards against bytecode which allocates without bound, e.g. by writing to a large pointer.
+    pub fn with_memory_limit(mut self, memory_limit_cells: Option<usize>) -> Self {
+        self.memory.set_limit(memory_limit_cells);
+        self
+    }
+
+    /// Returns the number of opcodes which have been executed so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
     /// Updates the current status of the VM.
     /// Returns the given status.
     fn status(&mut self, status: VMStatus) -> VMStatus {
@@ -117,30 +334,60 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
 
     /// Sets the status of the VM to `ForeignCallWait`.
     /// Indicating that the VM is now waiting for a foreign call to be resolved.
-    fn wait_for_foreign_call(&mut self, function: String, inputs: Vec<Vec<Value>>) -> VMStatus {
+    fn wait_for_foreign_call(
+        &mut self,
+        function: String,
+        inputs: Vec<ForeignCallParam>,
+    ) -> VMStatus {
         self.status(VMStatus::ForeignCallWait { function, inputs })
     }
 
     /// Sets the current status of the VM to `fail`.
     /// Indicating that the VM encountered a `Trap` Opcode
     /// or an invalid state.
-    fn fail(&mut self, message: String) -> VMStatus {
+    fn fail(&mut self, error: BrilligError) -> VMStatus {
         let mut error_stack: Vec<_> =
             self.call_stack.iter().map(|value| value.to_usize()).collect();
         error_stack.push(self.program_counter);
-        self.status(VMStatus::Failure { call_stack: error_stack, message });
+        self.status(VMStatus::Failure { call_stack: error_stack, error });
         self.status.clone()
     }
 
     /// Loop over the bytecode and update the program counter
     pub fn process_opcodes(&mut self) -> VMStatus {
-        while !matches!(
-            self.process_opcode(),
-            VMStatus::Finished | VMStatus::Failure { .. } | VMStatus::ForeignCallWait { .. }
-        ) {}
+        while self.step() == StepResult::Continue {}
         self.status.clone()
     }
 
+    /// Executes exactly one opcode, leaving the VM's state available for inspection
+    /// through [`VM::registers`] and [`VM::memory`] before the next step.
+    pub fn step(&mut self) -> StepResult {
+        self.process_opcode().into()
+    }
+
+    /// Returns the foreign call which the VM is waiting on, if one exists.
+    pub fn pending_foreign_call(&self) -> Option<PendingForeignCall> {
+        let VMStatus::ForeignCallWait { function, inputs } = &self.status else {
+            return None;
+        };
+        let input_types = match &self.bytecode[self.program_counter] {
+            Opcode::ForeignCall { input_types, .. } => input_types.clone(),
+            _ => None,
+        };
+        Some(PendingForeignCall { function: function.clone(), inputs: inputs.clone(), input_types })
+    }
+
+    /// Resolves the [pending foreign call][VM::pending_foreign_call] using a result calculated outside of the VM.
+    ///
+    /// The VM can then be resumed from the foreign call with all of its registers and memory intact.
+    pub fn resolve_foreign_call(&mut self, foreign_call_result: ForeignCallResult) {
+        if !matches!(self.status, VMStatus::ForeignCallWait { .. }) {
+            panic!("Brillig VM is not expecting a foreign call response as no call was made");
+        }
+        self.foreign_call_results.push(foreign_call_result);
+        self.status(VMStatus::InProgress);
+    }
+
     /// Returns all of the registers in the VM.
     pub fn get_registers(&self) -> &Registers {
         &self.registers
@@ -150,32 +397,118 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         self.memory.values()
     }
 
+    /// Returns the VM's registers.
+    pub fn registers(&self) -> &Registers {
+        &self.registers
+    }
+
+    /// Returns the VM's memory.
+    pub fn memory(&self) -> &Memory {
+        &self.memory
+    }
+
+    /// Captures the current execution state of the VM, which can later be returned to with [`VM::restore`].
+    pub fn snapshot(&self) -> VMSnapshot {
+        VMSnapshot {
+            registers: self.registers.clone(),
+            program_counter: self.program_counter,
+            foreign_call_counter: self.foreign_call_counter,
+            foreign_call_results: self.foreign_call_results.clone(),
+            status: self.status.clone(),
+            memory: self.memory.clone(),
+            call_stack: self.call_stack.clone(),
+            steps: self.steps,
+            trace: self.trace.clone(),
+        }
+    }
+
+    /// Returns the VM to the execution state captured by [`VM::snapshot`].
+    ///
+    /// The snapshot should have been taken from a VM executing the same bytecode.
+    pub fn restore(&mut self, snapshot: VMSnapshot) {
+        let VMSnapshot {
+            registers,
+            program_counter,
+            foreign_call_counter,
+            foreign_call_results,
+            status,
+            memory,
+            call_stack,
+            steps,
+            trace,
+        } = snapshot;
+        self.registers = registers;
+        self.program_counter = program_counter;
+        self.foreign_call_counter = foreign_call_counter;
+        self.foreign_call_results = foreign_call_results;
+        self.status = status;
+        self.memory = memory;
+        self.call_stack = call_stack;
+        self.steps = steps;
+        self.trace = trace;
+    }
+
     /// Process a single opcode and modify the program counter.
+    ///
+    /// Fails with [`BrilligError::StepLimitExceeded`] if the VM has already executed its maximum number of opcodes,
+    /// or with [`BrilligError::DeadlineExceeded`] if its deadline was found to have passed.
     pub fn process_opcode(&mut self) -> VMStatus {
+        if matches!(self.max_steps, Some(max_steps) if self.steps >= max_steps) {
+            return self.fail(BrilligError::StepLimitExceeded { steps: self.steps });
+        }
+        if let Some(deadline) = self.deadline {
+            if self.steps % DEADLINE_CHECK_INTERVAL == 0 && Instant::now() >= deadline {
+                return self.fail(BrilligError::DeadlineExceeded { steps: self.steps });
+            }
+        }
+
+        let pc = self.program_counter;
+        let registers_before = self.trace.is_some().then(|| self.registers.inner.clone());
+
+        let status = self.execute_opcode();
+        // A foreign call which is waiting on its result is executed again once the VM is resumed,
+        // so it only counts as a step once it has been resolved.
+        if !matches!(status, VMStatus::ForeignCallWait { .. }) {
+            self.steps += 1;
+            if let (Some(trace), Some(registers_before)) = (&mut self.trace, registers_before) {
+                trace.push(TraceEntry { pc, opcode: self.bytecode[pc].clone(), registers_before });
+            }
+        }
+        status
+    }
+
+    fn execute_opcode(&mut self) -> VMStatus {
+        match self.try_execute_opcode() {
+            Ok(status) => status,
+            Err(error) => self.fail(error),
+        }
+    }
+
+    fn try_execute_opcode(&mut self) -> Result<VMStatus, BrilligError> {
         let opcode = &self.bytecode[self.program_counter];
-        match opcode {
+        let status = match opcode {
             Opcode::BinaryFieldOp { op, lhs, rhs, destination: result } => {
-                self.process_binary_field_op(*op, *lhs, *rhs, *result);
+                self.process_binary_field_op(*op, *lhs, *rhs, *result)?;
                 self.increment_program_counter()
             }
             Opcode::BinaryIntOp { op, bit_size, lhs, rhs, destination: result } => {
-                self.process_binary_int_op(*op, *bit_size, *lhs, *rhs, *result);
+                self.process_binary_int_op(*op, *bit_size, *lhs, *rhs, *result)?;
                 self.increment_program_counter()
             }
             Opcode::Jump { location: destination } => self.set_program_counter(*destination),
             Opcode::JumpIf { condition, location: destination } => {
                 // Check if condition is true
                 // We use 0 to mean false and any other value to mean true
-                let condition_value = self.registers.get(*condition);
+                let condition_value = self.registers.try_get(*condition)?;
                 if !condition_value.is_zero() {
-                    return self.set_program_counter(*destination);
+                    return Ok(self.set_program_counter(*destination));
                 }
                 self.increment_program_counter()
             }
             Opcode::JumpIfNot { condition, location: destination } => {
-                let condition_value = self.registers.get(*condition);
+                let condition_value = self.registers.try_get(*condition)?;
                 if condition_value.is_zero() {
-                    return self.set_program_counter(*destination);
+                    return Ok(self.set_program_counter(*destination));
                 }
                 self.increment_program_counter()
             }
@@ -183,10 +516,29 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
                 if let Some(register) = self.call_stack.pop() {
                     self.set_program_counter(register.to_usize() + 1)
                 } else {
-                    self.fail("return opcode hit, but callstack already empty".to_string())
+                    return Err(BrilligError::EmptyCallStack);
                 }
             }
-            Opcode::ForeignCall { function, destinations, inputs } => {
+            Opcode::ForeignCall {
+                function,
+                destinations,
+                destination_types,
+                inputs,
+                input_types,
+            } => {
+                for (types, operands) in [(destination_types, destinations), (input_types, inputs)]
+                {
+                    match types {
+                        Some(types) if types.len() != operands.len() => {
+                            return Err(BrilligError::ForeignCallTypeCountMismatch {
+                                types: types.len(),
+                                operands: operands.len(),
+                            });
+                        }
+                        _ => (),
+                    }
+                }
+
                 if self.foreign_call_counter >= self.foreign_call_results.len() {
                     // When this opcode is called, it is possible that the results of a foreign call are
                     // not yet known (not enough entries in `foreign_call_results`).
@@ -197,18 +549,35 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
                     // but has the necessary results to proceed with execution.
                     let resolved_inputs = inputs
                         .iter()
-                        .map(|input| self.get_register_value_or_memory_values(*input))
-                        .collect::<Vec<_>>();
-                    return self.wait_for_foreign_call(function.clone(), resolved_inputs);
+                        .map(|input| self.get_foreign_call_param(*input))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    return Ok(self.wait_for_foreign_call(function.clone(), resolved_inputs));
                 }
 
                 let values = &self.foreign_call_results[self.foreign_call_counter].values;
 
+                if destinations.len() != values.len() {
+                    return Err(BrilligError::ForeignCallResultCountMismatch {
+                        results: values.len(),
+                        destinations: destinations.len(),
+                    });
+                }
+                let mismatched_output = destination_types.as_ref().and_then(|types| {
+                    types
+                        .iter()
+                        .zip(values)
+                        .position(|(typ, output)| !typ.matches(output))
+                        .map(|index| (index, types[index]))
+                });
+                if let Some((index, expected)) = mismatched_output {
+                    return Err(BrilligError::ForeignCallResultTypeMismatch { index, expected });
+                }
+
                 let mut invalid_foreign_call_result = false;
                 for (destination, output) in destinations.iter().zip(values) {
                     match destination {
                         RegisterOrMemory::RegisterIndex(value_index) => match output {
-                            ForeignCallOutput::Single(value) => {
+                            ForeignCallParam::Single(value) => {
                                 self.registers.set(*value_index, *value)
                             }
                             _ => unreachable!(
@@ -217,15 +586,16 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
                         },
                         RegisterOrMemory::HeapArray(HeapArray { pointer: pointer_index, size }) => {
                             match output {
-                                ForeignCallOutput::Array(values) => {
+                                ForeignCallParam::Array(_) => {
+                                    let values = output.values();
                                     if values.len() != *size {
                                         invalid_foreign_call_result = true;
                                         break;
                                     }
                                     // Convert the destination pointer to a usize
-                                    let destination = self.registers.get(*pointer_index).to_usize();
+                                    let destination = self.registers.try_get(*pointer_index)?.to_usize();
                                     // Write to our destination memory
-                                    self.memory.write_slice(destination, values);
+                                    self.memory.write_slice(destination, &values)?;
                                 }
                                 _ => {
                                     unreachable!("Function result size does not match brillig bytecode size")
@@ -234,53 +604,77 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
                         }
                         RegisterOrMemory::HeapVector(HeapVector { pointer: pointer_index, size: size_index }) => {
                             match output {
-                                ForeignCallOutput::Array(values) => {
+                                ForeignCallParam::Array(_) => {
+                                    let values = output.values();
                                     // Set our size in the size register
                                     self.registers.set(*size_index, Value::from(values.len()));
                                     // Convert the destination pointer to a usize
-                                    let destination = self.registers.get(*pointer_index).to_usize();
+                                    let destination = self.registers.try_get(*pointer_index)?.to_usize();
                                     // Write to our destination memory
-                                    self.memory.write_slice(destination, values);
+                                    self.memory.write_slice(destination, &values)?;
                                 }
                                 _ => {
                                     unreachable!("Function result size does not match brillig bytecode size")
                                 }
                             }
                         }
+                        RegisterOrMemory::HeapNestedArray(HeapNestedArray { pointer: pointer_index, shape }) => {
+                            let shape = self.read_shape(*shape)?;
+                            let mut values = Vec::new();
+                            if !flatten_with_shape(output, &shape, &mut values) {
+                                invalid_foreign_call_result = true;
+                                break;
+                            }
+                            // Convert the destination pointer to a usize
+                            let destination = self.registers.try_get(*pointer_index)?.to_usize();
+                            // Write to our destination memory
+                            self.memory.write_slice(destination, &values)?;
+                        }
                     }
                 }
 
-                // These checks must come after resolving the foreign call outputs as `fail` uses a mutable reference
-                if destinations.len() != values.len() {
-                    self.fail(format!("{} output values were provided as a foreign call result for {} destination slots", values.len(), destinations.len()));
-                }
+                // This check must come after resolving the foreign call outputs as `fail` uses a mutable reference
                 if invalid_foreign_call_result {
-                    self.fail("Function result size does not match brillig bytecode".to_owned());
+                    return Err(BrilligError::InvalidForeignCallResult);
                 }
 
                 self.foreign_call_counter += 1;
                 self.increment_program_counter()
             }
             Opcode::Mov { destination: destination_register, source: source_register } => {
-                let source_value = self.registers.get(*source_register);
+                let source_value = self.registers.try_get(*source_register)?;
                 self.registers.set(*destination_register, source_value);
                 self.increment_program_counter()
             }
-            Opcode::Trap => self.fail("explicit trap hit in brillig".to_string()),
+            Opcode::Trap => return Err(BrilligError::Trap),
+            Opcode::TrapWithMessage { message } => {
+                return Err(BrilligError::Trapped { message: *message, pc: self.program_counter })
+            }
             Opcode::Stop => self.finish(),
+            Opcode::Select { condition, if_true, if_false, destination } => {
+                let condition = self.registers.try_get(*condition)?;
+                if !condition.is_zero() && !condition.to_field().is_one() {
+                    return Err(BrilligError::NonBooleanCondition { value: condition });
+                }
+                let if_true = self.registers.try_get(*if_true)?;
+                let if_false = self.registers.try_get(*if_false)?;
+                // if_false + condition * (if_true - if_false)
+                self.registers.set(*destination, if_false + condition * (if_true - if_false));
+                self.increment_program_counter()
+            }
             Opcode::Load { destination: destination_register, source_pointer } => {
                 // Convert our source_pointer to a usize
-                let source = self.registers.get(*source_pointer);
+                let source = self.registers.try_get(*source_pointer)?;
                 // Use our usize source index to lookup the value in memory
-                let value = &self.memory.read(source.to_usize());
-                self.registers.set(*destination_register, *value);
+                let value = self.memory.try_read(source.to_usize())?;
+                self.registers.set(*destination_register, value);
                 self.increment_program_counter()
             }
             Opcode::Store { destination_pointer, source: source_register } => {
                 // Convert our destination_pointer to a usize
-                let destination = self.registers.get(*destination_pointer).to_usize();
+                let destination = self.registers.try_get(*destination_pointer)?.to_usize();
                 // Use our usize destination index to set the value in memory
-                self.memory.write(destination, self.registers.get(*source_register));
+                self.memory.write(destination, self.registers.try_get(*source_register)?)?;
                 self.increment_program_counter()
             }
             Opcode::Call { location } => {
@@ -288,22 +682,22 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
                 self.call_stack.push(Value::from(self.program_counter));
                 self.set_program_counter(*location)
             }
-            Opcode::Const { destination, value } => {
-                self.registers.set(*destination, *value);
+            Opcode::Const { destination, value, bit_size } => {
+                self.registers
+                    .set(*destination, to_typed_constant(value.to_field(), *bit_size).into());
                 self.increment_program_counter()
             }
             Opcode::BlackBox(black_box_op) => {
-                match evaluate_black_box(
+                evaluate_black_box(
                     black_box_op,
                     self.black_box_solver,
                     &mut self.registers,
                     &mut self.memory,
-                ) {
-                    Ok(()) => self.increment_program_counter(),
-                    Err(e) => self.fail(e.to_string()),
-                }
+                )?;
+                self.increment_program_counter()
             }
-        }
+        };
+        Ok(status)
     }
 
     /// Returns the current value of the program counter.
@@ -328,26 +722,53 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         self.status.clone()
     }
 
-    fn get_register_value_or_memory_values(&self, input: RegisterOrMemory) -> Vec<Value> {
+    fn get_foreign_call_param(
+        &self,
+        input: RegisterOrMemory,
+    ) -> Result<ForeignCallParam, BrilligError> {
         match input {
             RegisterOrMemory::RegisterIndex(value_index) => {
-                vec![self.registers.get(value_index)]
+                Ok(ForeignCallParam::Single(self.registers.try_get(value_index)?))
             }
             RegisterOrMemory::HeapArray(HeapArray { pointer: pointer_index, size }) => {
-                let start = self.registers.get(pointer_index);
-                self.memory.read_slice(start.to_usize(), size).to_vec()
+                let start = self.registers.try_get(pointer_index)?;
+                Ok(self.memory.try_read_slice(start.to_usize(), size)?.to_vec().into())
             }
             RegisterOrMemory::HeapVector(HeapVector {
                 pointer: pointer_index,
                 size: size_index,
             }) => {
-                let start = self.registers.get(pointer_index);
-                let size = self.registers.get(size_index);
-                self.memory.read_slice(start.to_usize(), size.to_usize()).to_vec()
+                let start = self.registers.try_get(pointer_index)?;
+                let size = self.registers.try_get(size_index)?;
+                Ok(self.memory.try_read_slice(start.to_usize(), size.to_usize())?.to_vec().into())
+            }
+            RegisterOrMemory::HeapNestedArray(HeapNestedArray {
+                pointer: pointer_index,
+                shape,
+            }) => {
+                let shape = self.read_shape(shape)?;
+                let start = self.registers.try_get(pointer_index)?.to_usize();
+                // The shape is read from memory so its size may not be representable.
+                let size = shape_size(&shape);
+                let out_of_bounds = BrilligError::MemoryOutOfBounds {
+                    pointer: start,
+                    offset: self.memory.values().len().saturating_sub(start),
+                    size: size.unwrap_or(usize::MAX),
+                };
+                let size = size.ok_or_else(|| out_of_bounds.clone())?;
+                let values = self.memory.try_read_slice(start, size)?;
+                nest_with_shape(values, &shape).ok_or(out_of_bounds)
             }
         }
     }
 
+    /// Reads the length of each dimension of a [`HeapNestedArray`] from memory.
+    fn read_shape(&self, shape: HeapArray) -> Result<Vec<usize>, BrilligError> {
+        let start = self.registers.try_get(shape.pointer)?;
+        let dimensions = self.memory.try_read_slice(start.to_usize(), shape.size)?;
+        Ok(dimensions.iter().map(Value::to_usize).collect())
+    }
+
     /// Process a binary operation.
     /// This method will not modify the program counter.
     fn process_binary_field_op(
@@ -356,14 +777,15 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         lhs: RegisterIndex,
         rhs: RegisterIndex,
         result: RegisterIndex,
-    ) {
-        let lhs_value = self.registers.get(lhs);
-        let rhs_value = self.registers.get(rhs);
+    ) -> Result<(), BrilligError> {
+        let lhs_value = self.registers.try_get(lhs)?;
+        let rhs_value = self.registers.try_get(rhs)?;
 
         let result_value =
             evaluate_binary_field_op(&op, lhs_value.to_field(), rhs_value.to_field());
 
-        self.registers.set(result, result_value.into())
+        self.registers.set(result, result_value.into());
+        Ok(())
     }
 
     /// Process a binary operation.
@@ -375,17 +797,59 @@ impl<'bb_solver, B: BlackBoxFunctionSolver> VM<'bb_solver, B> {
         lhs: RegisterIndex,
         rhs: RegisterIndex,
         result: RegisterIndex,
-    ) {
-        let lhs_value = self.registers.get(lhs);
-        let rhs_value = self.registers.get(rhs);
+    ) -> Result<(), BrilligError> {
+        let lhs_value = self.registers.try_get(lhs)?;
+        let rhs_value = self.registers.try_get(rhs)?;
 
         // Convert to big integers
         let lhs_big = BigUint::from_bytes_be(&lhs_value.to_field().to_be_bytes());
         let rhs_big = BigUint::from_bytes_be(&rhs_value.to_field().to_be_bytes());
-        let result_value = evaluate_binary_bigint_op(&op, lhs_big, rhs_big, bit_size);
+        let result_value = evaluate_binary_bigint_op(&op, lhs_big, rhs_big, bit_size)?;
         // Convert back to field element
         self.registers
             .set(result, FieldElement::from_be_bytes_reduce(&result_value.to_bytes_be()).into());
+        Ok(())
+    }
+}
+
+/// Returns the number of values in an array of the given `shape`, or `None` if it overflows a `usize`.
+fn shape_size(shape: &[usize]) -> Option<usize> {
+    shape.iter().try_fold(1_usize, |size, &len| size.checked_mul(len))
+}
+
+/// Nests the row-major `values` into arrays with the given `shape`.
+///
+/// Returns `None` if `values` is too short for `shape`.
+fn nest_with_shape(values: &[Value], shape: &[usize]) -> Option<ForeignCallParam> {
+    match shape.split_first() {
+        None => values.first().copied().map(ForeignCallParam::Single),
+        Some((&len, inner_shape)) => {
+            let inner_size = shape_size(inner_shape)?;
+            let elements = (0..len)
+                .map(|index| {
+                    let start = index.checked_mul(inner_size)?;
+                    let inner_values = values.get(start..start.checked_add(inner_size)?)?;
+                    nest_with_shape(inner_values, inner_shape)
+                })
+                .collect::<Option<_>>()?;
+            Some(ForeignCallParam::Array(elements))
+        }
+    }
+}
+
+/// Appends the values of `param` to `values` in row-major order, returning false if `param` does not have the given
+/// `shape`.
+fn flatten_with_shape(param: &ForeignCallParam, shape: &[usize], values: &mut Vec<Value>) -> bool {
+    match (param, shape.split_first()) {
+        (ForeignCallParam::Single(value), None) => {
+            values.push(*value);
+            true
+        }
+        (ForeignCallParam::Array(elements), Some((&len, inner_shape))) => {
+            elements.len() == len
+                && elements.iter().all(|element| flatten_with_shape(element, inner_shape, values))
+        }
+        _ => false,
     }
 }
 
@@ -420,6 +884,7 @@ impl BlackBoxFunctionSolver for DummyBlackBoxSolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use acir::brillig::Typ;
 
     #[test]
     fn add_single_step_smoke() {
@@ -456,6 +921,94 @@ mod tests {
         assert_eq!(output_value, Value::from(3u128))
     }
 
+    #[test]
+    fn field_addition_from_initial_state() {
+        let opcode = Opcode::BinaryFieldOp {
+            op: BinaryFieldOp::Add,
+            lhs: RegisterIndex::from(0),
+            rhs: RegisterIndex::from(1),
+            destination: RegisterIndex::from(2),
+        };
+        let memory = Memory::from(vec![Value::from(7u128)]);
+
+        let mut vm = VM::with_initial_state(
+            vec![Value::from(3u128), Value::from(4u128)],
+            memory.clone(),
+            vec![opcode],
+            &DummyBlackBoxSolver,
+        );
+
+        assert_eq!(vm.process_opcode(), VMStatus::Finished);
+        assert_eq!(vm.registers().get(RegisterIndex::from(2)), Value::from(7u128));
+        assert_eq!(vm.memory(), &memory);
+    }
+
+    #[test]
+    fn restore_snapshot() {
+        // Doubles r1 and stores it at the address in r0, repeatedly.
+        let double = Opcode::BinaryFieldOp {
+            op: BinaryFieldOp::Add,
+            lhs: RegisterIndex::from(1),
+            rhs: RegisterIndex::from(1),
+            destination: RegisterIndex::from(1),
+        };
+        let store = Opcode::Store {
+            destination_pointer: RegisterIndex::from(0),
+            source: RegisterIndex::from(1),
+        };
+        let bytecode = vec![double.clone(), store.clone(), double.clone(), store, double];
+
+        let mut vm = VM::with_initial_state(
+            vec![Value::from(0u128), Value::from(1u128)],
+            Memory::from(vec![]),
+            bytecode,
+            &DummyBlackBoxSolver,
+        )
+        .with_tracing();
+        vm.process_opcode();
+        vm.process_opcode();
+
+        let snapshot = vm.snapshot();
+        assert_eq!(snapshot.program_counter(), 2);
+        let registers = vm.registers().clone();
+        let memory = vm.memory().clone();
+
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+        assert_eq!(vm.registers().get(RegisterIndex::from(1)), Value::from(8u128));
+
+        vm.restore(snapshot.clone());
+        assert_eq!(vm.program_counter, 2);
+        assert_eq!(vm.registers(), &registers);
+        assert_eq!(vm.memory(), &memory);
+        assert_eq!(vm.steps(), 2);
+        assert_eq!(vm.trace().len(), 2);
+        assert_eq!(vm.snapshot(), snapshot);
+
+        // Execution resumes from the restored state.
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+        assert_eq!(vm.registers().get(RegisterIndex::from(1)), Value::from(8u128));
+    }
+
+    #[test]
+    fn reading_unassigned_register_fails() {
+        let opcode =
+            Opcode::Mov { destination: RegisterIndex::from(0), source: RegisterIndex::from(999) };
+        let mut vm = VM::with_initial_state(
+            vec![Value::from(1u128), Value::from(2u128), Value::from(3u128)],
+            Memory::from(vec![]),
+            vec![opcode],
+            &DummyBlackBoxSolver,
+        );
+
+        assert_eq!(
+            vm.process_opcode(),
+            VMStatus::Failure {
+                error: BrilligError::InvalidRegister { index: 999, max: 3 },
+                call_stack: vec![0]
+            }
+        );
+    }
+
     #[test]
     fn jmpif_opcode() {
         let mut registers = vec![];
@@ -498,6 +1051,66 @@ mod tests {
         assert_eq!(status, VMStatus::Finished);
     }
 
+    #[test]
+    fn select_opcode() {
+        let select = |condition: u128| {
+            let registers = vec![Value::from(condition), Value::from(7u128), Value::from(9u128)];
+            let opcode = Opcode::Select {
+                condition: RegisterIndex::from(0),
+                if_true: RegisterIndex::from(1),
+                if_false: RegisterIndex::from(2),
+                destination: RegisterIndex::from(3),
+            };
+            let mut vm = VM::new(
+                Registers::load(registers),
+                vec![],
+                vec![opcode],
+                vec![],
+                &DummyBlackBoxSolver,
+            );
+            let status = vm.process_opcode();
+            (status, vm.registers.get(RegisterIndex::from(3)))
+        };
+
+        assert_eq!(select(1), (VMStatus::Finished, Value::from(7u128)));
+        assert_eq!(select(0), (VMStatus::Finished, Value::from(9u128)));
+
+        let (status, _) = select(2);
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                error: BrilligError::NonBooleanCondition { value: Value::from(2u128) },
+                call_stack: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn trap_with_message_reports_program_counter() {
+        let bytecode = vec![
+            Opcode::Const {
+                destination: RegisterIndex::from(0),
+                value: Value::from(1u128),
+                bit_size: 64,
+            },
+            Opcode::JumpIf { condition: RegisterIndex::from(0), location: 3 },
+            Opcode::Stop,
+            Opcode::TrapWithMessage { message: 7 },
+        ];
+
+        let mut vm =
+            VM::new(Registers::load(vec![]), vec![], bytecode, vec![], &DummyBlackBoxSolver);
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                error: BrilligError::Trapped { message: 7, pc: 3 },
+                call_stack: vec![3]
+            }
+        );
+    }
+
     #[test]
     fn jmpifnot_opcode() {
         let input_registers =
@@ -545,18 +1158,114 @@ mod tests {
         assert_eq!(status, VMStatus::InProgress);
 
         let status = vm.process_opcode();
+        assert_eq!(status, VMStatus::Failure { error: BrilligError::Trap, call_stack: vec![1] });
+
+        // The register at index `2` should have not changed as we jumped over the add opcode
+        let VM { registers, .. } = vm;
+        let output_value = registers.get(RegisterIndex::from(2));
+        assert_eq!(output_value, Value::from(false));
+    }
+
+    #[test]
+    fn max_steps_stops_infinite_loop() {
+        let infinite_loop = vec![
+            Opcode::Const {
+                destination: RegisterIndex::from(0),
+                value: Value::from(1u128),
+                bit_size: 64,
+            },
+            Opcode::Jump { location: 0 },
+        ];
+
+        let mut vm =
+            VM::new(Registers::load(vec![]), vec![], infinite_loop, vec![], &DummyBlackBoxSolver)
+                .with_max_steps(Some(10));
+
+        let status = vm.process_opcodes();
         assert_eq!(
             status,
             VMStatus::Failure {
-                message: "explicit trap hit in brillig".to_string(),
-                call_stack: vec![1]
+                error: BrilligError::StepLimitExceeded { steps: 10 },
+                call_stack: vec![0]
             }
         );
+        assert_eq!(vm.steps(), 10);
+    }
+
+    #[test]
+    fn deadline_stops_infinite_loop() {
+        let infinite_loop = vec![
+            Opcode::Const {
+                destination: RegisterIndex::from(0),
+                value: Value::from(1u128),
+                bit_size: 64,
+            },
+            Opcode::Jump { location: 0 },
+        ];
+
+        let deadline = Instant::now() + std::time::Duration::from_millis(10);
+        let mut vm =
+            VM::new(Registers::load(vec![]), vec![], infinite_loop, vec![], &DummyBlackBoxSolver)
+                .with_deadline(Some(deadline));
+
+        let status = vm.process_opcodes();
+        assert!(Instant::now() >= deadline);
+        assert!(matches!(
+            status,
+            VMStatus::Failure { error: BrilligError::DeadlineExceeded { steps }, .. }
+                if steps > 0 && steps % DEADLINE_CHECK_INTERVAL == 0
+        ));
+    }
+
+    #[test]
+    fn memory_limit_stops_large_allocation() {
+        let r_pointer = RegisterIndex::from(0);
+        let r_value = RegisterIndex::from(1);
+        let bytecode = vec![
+            Opcode::Const { destination: r_value, value: Value::from(1u128), bit_size: 64 },
+            // Writing to the last cell within the limit succeeds.
+            Opcode::Const { destination: r_pointer, value: Value::from(15u128), bit_size: 64 },
+            Opcode::Store { destination_pointer: r_pointer, source: r_value },
+            Opcode::Const { destination: r_pointer, value: Value::from(1u128 << 32), bit_size: 64 },
+            Opcode::Store { destination_pointer: r_pointer, source: r_value },
+        ];
+
+        let mut vm =
+            VM::new(Registers::load(vec![]), vec![], bytecode, vec![], &DummyBlackBoxSolver)
+                .with_memory_limit(Some(16));
+
+        let status = vm.process_opcodes();
+        assert_eq!(
+            status,
+            VMStatus::Failure {
+                error: BrilligError::MemoryLimitExceeded { size: (1 << 32) + 1, limit: 16 },
+                call_stack: vec![4]
+            }
+        );
+        assert_eq!(vm.memory().values().len(), 16);
+    }
+
+    #[test]
+    fn const_opcode() {
+        let const_opcode = Opcode::Const {
+            destination: RegisterIndex::from(0),
+            value: Value::from(FieldElement::from(7u128)),
+            bit_size: FieldElement::max_num_bits(),
+        };
+
+        let mut vm = VM::new(
+            Registers::load(vec![Value::from(0u128)]),
+            vec![],
+            vec![const_opcode],
+            vec![],
+            &DummyBlackBoxSolver,
+        );
+
+        let status = vm.process_opcode();
+        assert_eq!(status, VMStatus::Finished);
 
-        // The register at index `2` should have not changed as we jumped over the add opcode
         let VM { registers, .. } = vm;
-        let output_value = registers.get(RegisterIndex::from(2));
-        assert_eq!(output_value, Value::from(false));
+        assert_eq!(registers.get(RegisterIndex::from(0)), Value::from(7u128));
     }
 
     #[test]
@@ -673,15 +1382,19 @@ mod tests {
             let r_tmp = RegisterIndex::from(2);
             let start = [
                 // i = 0
-                Opcode::Const { destination: r_i, value: 0u128.into() },
+                Opcode::Const { destination: r_i, value: 0u128.into(), bit_size: 64 },
                 // len = memory.len() (approximation)
-                Opcode::Const { destination: r_len, value: Value::from(memory.len() as u128) },
+                Opcode::Const {
+                    destination: r_len,
+                    value: Value::from(memory.len() as u128),
+                    bit_size: 64,
+                },
             ];
             let loop_body = [
                 // *i = i
                 Opcode::Store { destination_pointer: r_i, source: r_i },
                 // tmp = 1
-                Opcode::Const { destination: r_tmp, value: 1u128.into() },
+                Opcode::Const { destination: r_tmp, value: 1u128.into(), bit_size: 64 },
                 // i = i + 1 (tmp)
                 Opcode::BinaryIntOp {
                     destination: r_i,
@@ -738,11 +1451,15 @@ mod tests {
             let r_tmp = RegisterIndex::from(3);
             let start = [
                 // sum = 0
-                Opcode::Const { destination: r_sum, value: 0u128.into() },
+                Opcode::Const { destination: r_sum, value: 0u128.into(), bit_size: 64 },
                 // i = 0
-                Opcode::Const { destination: r_i, value: 0u128.into() },
+                Opcode::Const { destination: r_i, value: 0u128.into(), bit_size: 64 },
                 // len = array.len() (approximation)
-                Opcode::Const { destination: r_len, value: Value::from(memory.len() as u128) },
+                Opcode::Const {
+                    destination: r_len,
+                    value: Value::from(memory.len() as u128),
+                    bit_size: 64,
+                },
             ];
             let loop_body = [
                 // tmp = *i
@@ -756,7 +1473,7 @@ mod tests {
                     bit_size,
                 },
                 // tmp = 1
-                Opcode::Const { destination: r_tmp, value: 1u128.into() },
+                Opcode::Const { destination: r_tmp, value: 1u128.into(), bit_size: 64 },
                 // i = i + 1 (tmp)
                 Opcode::BinaryIntOp {
                     destination: r_i,
@@ -812,9 +1529,13 @@ mod tests {
 
             let start = [
                 // i = 0
-                Opcode::Const { destination: r_i, value: 0u128.into() },
+                Opcode::Const { destination: r_i, value: 0u128.into(), bit_size: 64 },
                 // len = memory.len() (approximation)
-                Opcode::Const { destination: r_len, value: Value::from(memory.len() as u128) },
+                Opcode::Const {
+                    destination: r_len,
+                    value: Value::from(memory.len() as u128),
+                    bit_size: 64,
+                },
                 // call recursive_fn
                 Opcode::Call {
                     location: 4, // Call after 'start'
@@ -840,7 +1561,7 @@ mod tests {
                 // *i = i
                 Opcode::Store { destination_pointer: r_i, source: r_i },
                 // tmp = 1
-                Opcode::Const { destination: r_tmp, value: 1u128.into() },
+                Opcode::Const { destination: r_tmp, value: 1u128.into(), bit_size: 64 },
                 // i = i + 1 (tmp)
                 Opcode::BinaryIntOp {
                     destination: r_i,
@@ -904,12 +1625,14 @@ mod tests {
 
         let double_program = vec![
             // Load input register with value 5
-            Opcode::Const { destination: r_input, value: Value::from(5u128) },
+            Opcode::Const { destination: r_input, value: Value::from(5u128), bit_size: 64 },
             // Call foreign function "double" with the input register
             Opcode::ForeignCall {
                 function: "double".into(),
                 destinations: vec![RegisterOrMemory::RegisterIndex(r_result)],
                 inputs: vec![RegisterOrMemory::RegisterIndex(r_input)],
+                destination_types: None,
+                input_types: None,
             },
         ];
 
@@ -920,7 +1643,7 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "double".into(),
-                inputs: vec![vec![Value::from(5u128)]]
+                inputs: vec![Value::from(5u128).into()]
             }
         );
 
@@ -942,6 +1665,277 @@ mod tests {
         // Ensure the foreign call counter has been incremented
         assert_eq!(vm.foreign_call_counter, 1);
     }
+    #[test]
+    fn step_through_foreign_call() {
+        let input = Value::from(5u128);
+        let r_input = RegisterIndex::from(0);
+
+        // The bytecode of the `simple_brillig_foreign_call` program.
+        let invert_program = vec![Opcode::ForeignCall {
+            function: "invert".into(),
+            destinations: vec![RegisterOrMemory::RegisterIndex(r_input)],
+            inputs: vec![RegisterOrMemory::RegisterIndex(r_input)],
+            destination_types: None,
+            input_types: None,
+        }];
+
+        let mut vm = VM::new(
+            Registers::load(vec![input]),
+            vec![],
+            invert_program,
+            vec![],
+            &DummyBlackBoxSolver,
+        );
+
+        assert_eq!(
+            vm.step(),
+            StepResult::ForeignCallWait { function: "invert".into(), inputs: vec![input.into()] }
+        );
+        assert_eq!(vm.registers().get(r_input), input);
+
+        let inverted = Value::from(input.to_field().inverse());
+        vm.foreign_call_results.push(inverted.into());
+
+        assert_eq!(vm.step(), StepResult::Finished);
+        assert_eq!(vm.registers().get(r_input), inverted);
+    }
+
+    #[test]
+    fn resolve_foreign_call_with_external_resolver() {
+        let input = Value::from(5u128);
+        // The bytecode of the `simple_brillig_foreign_call` program.
+        let invert_program = vec![Opcode::ForeignCall {
+            function: "invert".into(),
+            destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            destination_types: None,
+            input_types: None,
+        }];
+
+        // Stands in for an oracle which is queried outside of the VM, e.g. over RPC.
+        let resolver = |call: &PendingForeignCall| -> ForeignCallResult {
+            assert_eq!(call.function, "invert");
+            Value::from(call.inputs[0].unwrap_value().to_field().inverse()).into()
+        };
+
+        let mut vm = VM::new(
+            Registers::load(vec![input]),
+            vec![],
+            invert_program,
+            vec![],
+            &DummyBlackBoxSolver,
+        );
+        assert_eq!(vm.pending_foreign_call(), None);
+
+        while let VMStatus::ForeignCallWait { .. } = vm.process_opcodes() {
+            let call = vm.pending_foreign_call().expect("VM should be waiting on a foreign call");
+            // The VM's state is untouched while the call is pending.
+            assert_eq!(vm.registers().get(RegisterIndex::from(0)), input);
+            let result = resolver(&call);
+            vm.resolve_foreign_call(result);
+        }
+
+        assert_eq!(vm.status, VMStatus::Finished);
+        assert_eq!(
+            vm.registers().get(RegisterIndex::from(0)),
+            Value::from(input.to_field().inverse())
+        );
+    }
+
+    #[test]
+    fn typed_foreign_call_rejects_mismatched_results() {
+        let r_input = RegisterIndex::from(0);
+        let r_result = RegisterIndex::from(1);
+        let u8_type = ForeignCallParamType::Simple(Typ::Unsigned { bit_size: 8 });
+        let program = vec![Opcode::ForeignCall {
+            function: "double".into(),
+            destinations: vec![RegisterOrMemory::RegisterIndex(r_result)],
+            destination_types: Some(vec![u8_type]),
+            inputs: vec![RegisterOrMemory::RegisterIndex(r_input)],
+            input_types: Some(vec![u8_type]),
+        }];
+        let new_vm = |program| {
+            VM::new(
+                Registers::load(vec![Value::from(5u128)]),
+                vec![],
+                program,
+                vec![],
+                &DummyBlackBoxSolver,
+            )
+        };
+
+        let mut vm = new_vm(program.clone());
+        vm.process_opcodes();
+        assert_eq!(vm.pending_foreign_call().unwrap().input_types, Some(vec![u8_type]));
+
+        // Two outputs are returned for a single destination.
+        vm.resolve_foreign_call(
+            vec![
+                ForeignCallParam::Single(Value::from(10u128)),
+                ForeignCallParam::Single(Value::from(10u128)),
+            ]
+            .into(),
+        );
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                error: BrilligError::ForeignCallResultCountMismatch { results: 2, destinations: 1 },
+                call_stack: vec![0]
+            }
+        );
+        assert_eq!(vm.registers().get(r_result), Value::from(0u128));
+
+        // The output does not fit in a `u8`.
+        let mut vm = new_vm(program.clone());
+        vm.process_opcodes();
+        vm.resolve_foreign_call(Value::from(256u128).into());
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                error: BrilligError::ForeignCallResultTypeMismatch { index: 0, expected: u8_type },
+                call_stack: vec![0]
+            }
+        );
+
+        // An array is returned in place of a single value.
+        let mut vm = new_vm(program);
+        vm.process_opcodes();
+        vm.resolve_foreign_call(vec![Value::from(10u128)].into());
+        assert!(matches!(
+            vm.process_opcodes(),
+            VMStatus::Failure { error: BrilligError::ForeignCallResultTypeMismatch { .. }, .. }
+        ));
+    }
+
+    #[test]
+    fn typed_foreign_call_rejects_mismatched_type_count() {
+        let u8_type = ForeignCallParamType::Simple(Typ::Unsigned { bit_size: 8 });
+        let program = vec![Opcode::ForeignCall {
+            function: "double".into(),
+            destinations: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(1))],
+            destination_types: Some(vec![u8_type]),
+            inputs: vec![RegisterOrMemory::RegisterIndex(RegisterIndex::from(0))],
+            input_types: Some(vec![u8_type, u8_type]),
+        }];
+        let mut vm = VM::new(
+            Registers::load(vec![Value::from(5u128)]),
+            vec![],
+            program,
+            vec![],
+            &DummyBlackBoxSolver,
+        );
+
+        // The opcode is rejected before the foreign call is made.
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                error: BrilligError::ForeignCallTypeCountMismatch { types: 2, operands: 1 },
+                call_stack: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn trace_survives_foreign_call() {
+        let initial_registers = vec![Value::from(0u128), Value::from(6u128)];
+        // The bytecode of the `complex_brillig_foreign_call` program.
+        let complex_program = vec![Opcode::ForeignCall {
+            function: "complex".into(),
+            inputs: vec![
+                RegisterOrMemory::HeapArray(HeapArray { pointer: 0.into(), size: 3 }),
+                RegisterOrMemory::RegisterIndex(RegisterIndex::from(1)),
+            ],
+            destinations: vec![
+                RegisterOrMemory::HeapArray(HeapArray { pointer: 0.into(), size: 3 }),
+                RegisterOrMemory::RegisterIndex(RegisterIndex::from(1)),
+                RegisterOrMemory::RegisterIndex(RegisterIndex::from(2)),
+            ],
+            destination_types: None,
+            input_types: None,
+        }];
+
+        let mut vm = VM::new(
+            Registers::load(initial_registers.clone()),
+            vec![Value::from(1u128), Value::from(2u128), Value::from(3u128)],
+            complex_program.clone(),
+            vec![],
+            &DummyBlackBoxSolver,
+        )
+        .with_tracing();
+
+        let status = vm.process_opcodes();
+        assert!(matches!(status, VMStatus::ForeignCallWait { .. }));
+        // The foreign call has not been executed yet.
+        assert!(vm.trace().is_empty());
+
+        vm.foreign_call_results.push(ForeignCallResult {
+            values: vec![
+                vec![Value::from(2u128), Value::from(6u128), Value::from(12u128)].into(),
+                ForeignCallParam::Single(Value::from(6u128)),
+                ForeignCallParam::Single(Value::from(12u128)),
+            ],
+        });
+        assert_eq!(vm.process_opcodes(), VMStatus::Finished);
+
+        assert_eq!(
+            vm.trace(),
+            &[TraceEntry {
+                pc: 0,
+                opcode: complex_program[0].clone(),
+                registers_before: initial_registers
+            }]
+        );
+    }
+
+    #[test]
+    fn heap_array_out_of_bounds() {
+        // The `HeapArray` starts at the second element of a 3 element memory so its last element lies past the end.
+        let program = vec![Opcode::ForeignCall {
+            function: "complex".into(),
+            inputs: vec![RegisterOrMemory::HeapArray(HeapArray { pointer: 0.into(), size: 3 })],
+            destinations: vec![],
+            destination_types: None,
+            input_types: None,
+        }];
+
+        let mut vm = VM::new(
+            Registers::load(vec![Value::from(1u128)]),
+            vec![Value::from(1u128), Value::from(2u128), Value::from(3u128)],
+            program,
+            vec![],
+            &DummyBlackBoxSolver,
+        );
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                error: BrilligError::MemoryOutOfBounds { pointer: 1, offset: 2, size: 3 },
+                call_stack: vec![0]
+            }
+        );
+    }
+
+    #[test]
+    fn load_out_of_bounds() {
+        let program = vec![Opcode::Load { destination: 1.into(), source_pointer: 0.into() }];
+
+        let mut vm = VM::new(
+            Registers::load(vec![Value::from(3u128)]),
+            vec![Value::from(1u128), Value::from(2u128), Value::from(3u128)],
+            program,
+            vec![],
+            &DummyBlackBoxSolver,
+        );
+
+        assert_eq!(
+            vm.process_opcodes(),
+            VMStatus::Failure {
+                error: BrilligError::MemoryOutOfBounds { pointer: 3, offset: 0, size: 1 },
+                call_stack: vec![0]
+            }
+        );
+    }
+
     #[test]
     fn foreign_call_opcode_memory_result() {
         let r_input = RegisterIndex::from(0);
@@ -957,9 +1951,9 @@ mod tests {
 
         let invert_program = vec![
             // input = 0
-            Opcode::Const { destination: r_input, value: Value::from(0u128) },
+            Opcode::Const { destination: r_input, value: Value::from(0u128), bit_size: 64 },
             // output = 0
-            Opcode::Const { destination: r_output, value: Value::from(0u128) },
+            Opcode::Const { destination: r_output, value: Value::from(0u128), bit_size: 64 },
             // *output = matrix_2x2_transpose(*input)
             Opcode::ForeignCall {
                 function: "matrix_2x2_transpose".into(),
@@ -971,6 +1965,8 @@ mod tests {
                     pointer: r_input,
                     size: initial_matrix.len(),
                 })],
+                destination_types: None,
+                input_types: None,
             },
         ];
 
@@ -981,7 +1977,7 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "matrix_2x2_transpose".into(),
-                inputs: vec![initial_matrix]
+                inputs: vec![initial_matrix.into()]
             }
         );
 
@@ -1002,6 +1998,138 @@ mod tests {
         assert_eq!(vm.foreign_call_counter, 1);
     }
 
+    #[test]
+    fn foreign_call_opcode_nested_array_round_trip() {
+        let r_input = RegisterIndex::from(0);
+        let r_output = RegisterIndex::from(1);
+        let r_input_shape = RegisterIndex::from(2);
+        let r_output_shape = RegisterIndex::from(3);
+
+        // A 2x3 matrix stored in row-major order, followed by space for its transpose and the shapes of both
+        let mut initial_memory: Vec<Value> = (1u128..=6).map(Value::from).collect();
+        initial_memory.extend(vec![Value::from(0u128); 6]);
+        initial_memory.extend([2u128, 3, 3, 2].into_iter().map(Value::from));
+
+        let transpose_program = vec![
+            Opcode::Const { destination: r_input, value: Value::from(0u128), bit_size: 64 },
+            Opcode::Const { destination: r_output, value: Value::from(6u128), bit_size: 64 },
+            Opcode::Const { destination: r_input_shape, value: Value::from(12u128), bit_size: 64 },
+            Opcode::Const { destination: r_output_shape, value: Value::from(14u128), bit_size: 64 },
+            // *output = transpose(*input)
+            Opcode::ForeignCall {
+                function: "transpose".into(),
+                destinations: vec![RegisterOrMemory::HeapNestedArray(HeapNestedArray {
+                    pointer: r_output,
+                    shape: HeapArray { pointer: r_output_shape, size: 2 },
+                })],
+                inputs: vec![RegisterOrMemory::HeapNestedArray(HeapNestedArray {
+                    pointer: r_input,
+                    shape: HeapArray { pointer: r_input_shape, size: 2 },
+                })],
+                destination_types: None,
+                input_types: None,
+            },
+        ];
+
+        let mut vm = brillig_execute_and_get_vm(initial_memory, transpose_program);
+
+        // The resolver receives the matrix as rows rather than a flat list of values
+        let call = vm.pending_foreign_call().unwrap();
+        let rows: Vec<Vec<Value>> =
+            vec![(1u128..=3).map(Value::from).collect(), (4u128..=6).map(Value::from).collect()];
+        assert_eq!(
+            call.inputs,
+            vec![ForeignCallParam::Array(
+                rows.iter().cloned().map(ForeignCallParam::from).collect()
+            )]
+        );
+
+        // Mock resolver which transposes the matrix it is given
+        let ForeignCallParam::Array(input_rows) = &call.inputs[0] else {
+            panic!("expected a nested array");
+        };
+        let input_rows: Vec<Vec<Value>> = input_rows.iter().map(ForeignCallParam::values).collect();
+        let transposed: Vec<ForeignCallParam> = (0..input_rows[0].len())
+            .map(|column| input_rows.iter().map(|row| row[column]).collect::<Vec<_>>().into())
+            .collect();
+        vm.resolve_foreign_call(vec![ForeignCallParam::Array(transposed)].into());
+
+        brillig_execute(&mut vm);
+        assert_eq!(vm.status, VMStatus::Finished);
+
+        let expected_result: Vec<Value> =
+            [1u128, 4, 2, 5, 3, 6].into_iter().map(Value::from).collect();
+        assert_eq!(vm.memory.read_slice(6, 6), expected_result);
+    }
+
+    #[test]
+    fn foreign_call_opcode_rejects_misshapen_nested_result() {
+        let r_output = RegisterIndex::from(0);
+        let r_shape = RegisterIndex::from(1);
+        let program = vec![
+            Opcode::Const { destination: r_output, value: Value::from(2u128), bit_size: 64 },
+            Opcode::Const { destination: r_shape, value: Value::from(0u128), bit_size: 64 },
+            Opcode::ForeignCall {
+                function: "matrix".into(),
+                destinations: vec![RegisterOrMemory::HeapNestedArray(HeapNestedArray {
+                    pointer: r_output,
+                    shape: HeapArray { pointer: r_shape, size: 2 },
+                })],
+                inputs: vec![],
+                destination_types: None,
+                input_types: None,
+            },
+        ];
+
+        // The output is a 2x2 matrix
+        let mut vm = brillig_execute_and_get_vm(vec![Value::from(2u128); 2], program);
+        // Four values of the right count, but not split into two rows of two
+        let flat: Vec<Value> = (1u128..=4).map(Value::from).collect();
+        vm.resolve_foreign_call(flat.into());
+
+        assert_eq!(
+            vm.process_opcode(),
+            VMStatus::Failure {
+                error: BrilligError::InvalidForeignCallResult,
+                call_stack: vec![2]
+            }
+        );
+    }
+
+    #[test]
+    fn foreign_call_opcode_rejects_overflowing_nested_input_shape() {
+        let r_input = RegisterIndex::from(0);
+        let r_shape = RegisterIndex::from(1);
+        let program = vec![
+            Opcode::Const { destination: r_input, value: Value::from(2u128), bit_size: 64 },
+            Opcode::Const { destination: r_shape, value: Value::from(0u128), bit_size: 64 },
+            Opcode::ForeignCall {
+                function: "matrix".into(),
+                destinations: vec![],
+                inputs: vec![RegisterOrMemory::HeapNestedArray(HeapNestedArray {
+                    pointer: r_input,
+                    shape: HeapArray { pointer: r_shape, size: 2 },
+                })],
+                destination_types: None,
+                input_types: None,
+            },
+        ];
+
+        // The number of values in a `usize::MAX` x 2 matrix does not fit in a `usize`
+        let memory = vec![Value::from(usize::MAX as u128), Value::from(2u128)];
+        let mut vm = VM::new(empty_registers(), memory, program, vec![], &DummyBlackBoxSolver);
+        vm.process_opcode();
+        vm.process_opcode();
+
+        assert_eq!(
+            vm.process_opcode(),
+            VMStatus::Failure {
+                error: BrilligError::MemoryOutOfBounds { pointer: 2, offset: 0, size: usize::MAX },
+                call_stack: vec![2]
+            }
+        );
+    }
+
     /// Calling a simple foreign call function that takes any string input, concatenates it with itself, and reverses the concatenation
     #[test]
     fn foreign_call_opcode_vector_input_and_output() {
@@ -1023,15 +2151,24 @@ mod tests {
         // First call:
         let string_double_program = vec![
             // input_pointer = 0
-            Opcode::Const { destination: r_input_pointer, value: Value::from(0u128) },
+            Opcode::Const { destination: r_input_pointer, value: Value::from(0u128), bit_size: 64 },
             // input_size = input_string.len() (constant here)
-            Opcode::Const { destination: r_input_size, value: Value::from(input_string.len()) },
+            Opcode::Const {
+                destination: r_input_size,
+                value: Value::from(input_string.len()),
+                bit_size: 64,
+            },
             // output_pointer = 0 + input_size = input_size
-            Opcode::Const { destination: r_output_pointer, value: Value::from(input_string.len()) },
+            Opcode::Const {
+                destination: r_output_pointer,
+                value: Value::from(input_string.len()),
+                bit_size: 64,
+            },
             // output_size = input_size * 2
             Opcode::Const {
                 destination: r_output_size,
                 value: Value::from(input_string.len() * 2),
+                bit_size: 64,
             },
             // output_pointer[0..output_size] = string_double(input_pointer[0...input_size])
             Opcode::ForeignCall {
@@ -1044,6 +2181,8 @@ mod tests {
                     pointer: r_input_pointer,
                     size: r_input_size,
                 })],
+                destination_types: None,
+                input_types: None,
             },
         ];
 
@@ -1054,14 +2193,13 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "string_double".into(),
-                inputs: vec![input_string.clone()]
+                inputs: vec![input_string.clone().into()]
             }
         );
 
         // Push result we're waiting for
-        vm.foreign_call_results.push(ForeignCallResult {
-            values: vec![ForeignCallOutput::Array(output_string.clone())],
-        });
+        vm.foreign_call_results
+            .push(ForeignCallResult { values: vec![output_string.clone().into()] });
 
         // Resume VM
         brillig_execute(&mut vm);
@@ -1092,9 +2230,9 @@ mod tests {
 
         let invert_program = vec![
             // input = 0
-            Opcode::Const { destination: r_input, value: Value::from(0u128) },
+            Opcode::Const { destination: r_input, value: Value::from(0u128), bit_size: 64 },
             // output = 0
-            Opcode::Const { destination: r_output, value: Value::from(4u128) },
+            Opcode::Const { destination: r_output, value: Value::from(4u128), bit_size: 64 },
             // *output = matrix_2x2_transpose(*input)
             Opcode::ForeignCall {
                 function: "matrix_2x2_transpose".into(),
@@ -1106,6 +2244,8 @@ mod tests {
                     pointer: r_input,
                     size: initial_matrix.len(),
                 })],
+                destination_types: None,
+                input_types: None,
             },
         ];
 
@@ -1116,7 +2256,7 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "matrix_2x2_transpose".into(),
-                inputs: vec![initial_matrix.clone()]
+                inputs: vec![initial_matrix.clone().into()]
             }
         );
 
@@ -1168,11 +2308,11 @@ mod tests {
 
         let matrix_mul_program = vec![
             // input = 0
-            Opcode::Const { destination: r_input_a, value: Value::from(0u128) },
+            Opcode::Const { destination: r_input_a, value: Value::from(0u128), bit_size: 64 },
             // input = 0
-            Opcode::Const { destination: r_input_b, value: Value::from(4u128) },
+            Opcode::Const { destination: r_input_b, value: Value::from(4u128), bit_size: 64 },
             // output = 0
-            Opcode::Const { destination: r_output, value: Value::from(0u128) },
+            Opcode::Const { destination: r_output, value: Value::from(0u128), bit_size: 64 },
             // *output = matrix_2x2_transpose(*input)
             Opcode::ForeignCall {
                 function: "matrix_2x2_transpose".into(),
@@ -1190,6 +2330,8 @@ mod tests {
                         size: matrix_b.len(),
                     }),
                 ],
+                destination_types: None,
+                input_types: None,
             },
         ];
         let mut initial_memory = matrix_a.clone();
@@ -1201,7 +2343,7 @@ mod tests {
             vm.status,
             VMStatus::ForeignCallWait {
                 function: "matrix_2x2_transpose".into(),
-                inputs: vec![matrix_a, matrix_b]
+                inputs: vec![matrix_a.into(), matrix_b.into()]
             }
         );
 
@@ -1,15 +1,17 @@
-use crate::Value;
+use crate::{BrilligError, Value};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Memory {
     // Memory is a vector of values.
     // We grow the memory when values past the end are set, extending with 0s.
     inner: Vec<Value>,
+    // The maximum number of cells which the memory may grow to, if limited.
+    limit: Option<usize>,
 }
 
 impl From<Vec<Value>> for Memory {
     fn from(values: Vec<Value>) -> Self {
-        Memory { inner: values }
+        Memory { inner: values, limit: None }
     }
 }
 
@@ -19,23 +21,66 @@ impl Memory {
         self.inner[ptr]
     }
 
+    /// Gets the value at pointer `ptr`.
+    ///
+    /// Returns [`BrilligError::MemoryOutOfBounds`] rather than panicking if `ptr` is past the end of memory.
+    pub fn try_read(&self, ptr: usize) -> Result<Value, BrilligError> {
+        self.try_read_slice(ptr, 1).map(|values| values[0])
+    }
+
     pub fn read_slice(&self, ptr: usize, len: usize) -> &[Value] {
         &self.inner[ptr..ptr + len]
     }
 
+    /// Gets the `len` values starting at pointer `ptr`.
+    ///
+    /// Returns [`BrilligError::MemoryOutOfBounds`] rather than panicking if the slice extends past the end of memory.
+    pub fn try_read_slice(&self, ptr: usize, len: usize) -> Result<&[Value], BrilligError> {
+        ptr.checked_add(len).and_then(|end| self.inner.get(ptr..end)).ok_or(
+            BrilligError::MemoryOutOfBounds {
+                pointer: ptr,
+                offset: self.inner.len().saturating_sub(ptr),
+                size: len,
+            },
+        )
+    }
+
+    /// Limits the number of cells which the memory may grow to.
+    ///
+    /// Writes which would grow the memory past `limit` cells fail with [`BrilligError::MemoryLimitExceeded`].
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+    }
+
     /// Sets the value at pointer `ptr` to `value`
-    pub fn write(&mut self, ptr: usize, value: Value) {
-        self.write_slice(ptr, &[value]);
+    pub fn write(&mut self, ptr: usize, value: Value) -> Result<(), BrilligError> {
+        self.write_slice(ptr, &[value])
     }
 
     /// Sets the values after pointer `ptr` to `values`
-    pub fn write_slice(&mut self, ptr: usize, values: &[Value]) {
+    ///
+    /// Returns [`BrilligError::MemoryLimitExceeded`] without modifying the memory if the write would grow it
+    /// past its limit.
+    pub fn write_slice(&mut self, ptr: usize, values: &[Value]) -> Result<(), BrilligError> {
+        let end = ptr.checked_add(values.len());
+        if let Some(limit) = self.limit {
+            match end {
+                Some(end) if end <= limit => (),
+                _ => {
+                    return Err(BrilligError::MemoryLimitExceeded {
+                        size: end.unwrap_or(usize::MAX),
+                        limit,
+                    })
+                }
+            }
+        }
         // Calculate new memory size
         let new_size = std::cmp::max(self.inner.len(), ptr + values.len());
         // Expand memory to new size with default values if needed
         self.inner.resize(new_size, Value::from(0_usize));
 
         self.inner[ptr..ptr + values.len()].copy_from_slice(values);
+        Ok(())
     }
 
     /// Returns the values of the memory
@@ -1,5 +1,7 @@
 use acir::brillig::{RegisterIndex, Value};
 
+use crate::BrilligError;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Registers {
     // Registers are a vector of values.
@@ -32,6 +34,16 @@ impl Registers {
         }
     }
 
+    /// Gets the value at register with address `index`, returning an error rather than zero if
+    /// the register lies beyond the highest register which has been assigned a value.
+    pub fn try_get(&self, register_index: RegisterIndex) -> Result<Value, BrilligError> {
+        let index = register_index.to_usize();
+        self.inner
+            .get(index)
+            .copied()
+            .ok_or(BrilligError::InvalidRegister { index, max: self.inner.len() })
+    }
+
     /// Sets the value at register with address `index` to `value`
     pub fn set(&mut self, RegisterIndex(index): RegisterIndex, value: Value) {
         assert!(index < MAX_REGISTERS, "Writing register past maximum!");
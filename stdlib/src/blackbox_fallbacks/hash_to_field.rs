@@ -110,6 +110,7 @@ fn field_addition(
             rhs: RegisterIndex::from(1),
             destination: RegisterIndex::from(0),
         }],
+        trap_messages: vec![],
         predicate: None,
     });
     new_opcodes.push(brillig_opcode);
@@ -156,6 +157,7 @@ pub(crate) fn field_mul(
             rhs: RegisterIndex::from(1),
             destination: RegisterIndex::from(0),
         }],
+        trap_messages: vec![],
         predicate: None,
     });
     new_opcodes.push(brillig_opcode);
@@ -157,6 +157,7 @@ pub(crate) fn pad(number: u32, bit_size: u32, mut num_witness: u32) -> (u32, Wit
         outputs: vec![BrilligOutputs::Simple(pad)],
         foreign_call_results: vec![],
         bytecode: vec![brillig::Opcode::Stop],
+        trap_messages: vec![],
         predicate: None,
     });
     new_opcodes.push(brillig_opcode);
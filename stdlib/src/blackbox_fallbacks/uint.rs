@@ -57,6 +57,7 @@ macro_rules! impl_uint {
                     outputs: vec![BrilligOutputs::Simple(new_witness)],
                     foreign_call_results: vec![],
                     bytecode: vec![brillig::Opcode::Stop],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -83,6 +84,7 @@ macro_rules! impl_uint {
                     outputs: vec![BrilligOutputs::Simple(new_witness)],
                     foreign_call_results: vec![],
                     bytecode: vec![brillig::Opcode::Stop],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -238,6 +240,7 @@ macro_rules! impl_uint {
                         rhs: RegisterIndex::from(1),
                         destination: RegisterIndex::from(0),
                     }],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -306,6 +309,7 @@ macro_rules! impl_uint {
                             destination: RegisterIndex::from(0),
                         },
                     ],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -381,6 +385,7 @@ macro_rules! impl_uint {
                             destination: RegisterIndex::from(0),
                         },
                     ],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -428,6 +433,7 @@ macro_rules! impl_uint {
                         rhs: RegisterIndex::from(1),
                         destination: RegisterIndex::from(0),
                     }],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -527,6 +533,7 @@ macro_rules! impl_uint {
                         rhs: RegisterIndex::from(0),
                         destination: RegisterIndex::from(0),
                     }],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);
@@ -590,6 +597,7 @@ macro_rules! impl_uint {
                             destination: RegisterIndex::from(0),
                         },
                     ],
+                    trap_messages: vec![],
                     predicate: None,
                 });
                 new_opcodes.push(brillig_opcode);